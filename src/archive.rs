@@ -0,0 +1,209 @@
+use std::fs;
+use std::io::{BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::exit::Exit;
+use crate::sessions::{self, SessionItem};
+use crate::ui;
+
+/// Name of the metadata sidecar stored at the archive root, next to `sessions/`.
+const MANIFEST_NAME: &str = "manifest.json";
+
+#[derive(Debug, Serialize)]
+struct Manifest {
+    created_at: String,
+    codex_home: PathBuf,
+    sessions: Vec<SessionItem>,
+}
+
+/// Bundle the given sessions' rollouts (laid out as under `codex_home`) plus a manifest into a
+/// tar archive, zstd-compressed unless `out` ends in `.tar`.
+pub fn backup(cfg: &Config, items: Vec<SessionItem>, out: &Path) -> Result<()> {
+    if out.exists() {
+        anyhow::bail!("refusing to overwrite existing file: {}", out.display());
+    }
+    if items.is_empty() {
+        anyhow::bail!(Exit::NoMatch(
+            "no sessions matched; nothing to back up".to_string()
+        ));
+    }
+
+    // Written next to `out` and renamed into place, so a failed backup leaves nothing behind.
+    let mut tmp = out.as_os_str().to_owned();
+    tmp.push(".partial");
+    let tmp = PathBuf::from(tmp);
+    let count = match write_archive(cfg, items, &tmp, is_plain_tar(out)) {
+        Ok(count) => count,
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            return Err(e);
+        }
+    };
+    fs::rename(&tmp, out).with_context(|| format!("failed to write {}", out.display()))?;
+
+    ui::print_info(&format!("Backed up {count} sessions to {}", out.display()));
+    Ok(())
+}
+
+/// The archive [`backup`] describes, written to `out`; returns how many sessions went in.
+fn write_archive(cfg: &Config, items: Vec<SessionItem>, out: &Path, plain: bool) -> Result<usize> {
+    let file =
+        fs::File::create(out).with_context(|| format!("failed to create {}", out.display()))?;
+    let writer: Box<dyn Write> = if plain {
+        Box::new(file)
+    } else {
+        Box::new(zstd::Encoder::new(file, 0)?.auto_finish())
+    };
+    let mut tar = tar::Builder::new(writer);
+
+    let codex_home = &cfg.sessions.codex_home;
+    let mut archived = Vec::new();
+    for s in items {
+        // Sessions only known from history.jsonl have no rollout to back up.
+        let Some(path) = s.path.as_deref() else {
+            continue;
+        };
+        let Ok(rel) = path.strip_prefix(codex_home) else {
+            ui::print_warn(&format!(
+                "skipping {} (outside {})",
+                path.display(),
+                codex_home.display()
+            ));
+            continue;
+        };
+        tar.append_path_with_name(path, rel)
+            .with_context(|| format!("failed to add {}", path.display()))?;
+        archived.push(s);
+    }
+
+    let manifest = Manifest {
+        created_at: now_rfc3339(),
+        codex_home: codex_home.clone(),
+        sessions: archived,
+    };
+    let data = serde_json::to_vec_pretty(&manifest).context("failed to serialize manifest")?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST_NAME, data.as_slice())?;
+    tar.into_inner()?.flush()?;
+    Ok(manifest.sessions.len())
+}
+
+/// Unpack rollouts from a `backup` archive into `codex_home/sessions/YYYY/MM/DD`, skipping
+/// sessions whose id (or file) already exists.
+pub fn restore(cfg: &Config, archive: &Path, dry_run: bool) -> Result<()> {
+    let file =
+        fs::File::open(archive).with_context(|| format!("failed to open {}", archive.display()))?;
+    let mut reader = BufReader::new(file);
+    let reader: Box<dyn Read> = if is_zstd(&mut reader)? {
+        Box::new(zstd::Decoder::with_buffer(reader)?)
+    } else {
+        Box::new(reader)
+    };
+    let mut tar = tar::Archive::new(reader);
+
+    let existing = sessions::rollout_file_ids(cfg)?;
+
+    let codex_home = &cfg.sessions.codex_home;
+    let (mut restored, mut skipped) = (0usize, 0usize);
+    for entry in tar.entries().context("failed to read archive")? {
+        let mut entry = entry.context("failed to read archive entry")?;
+        let rel = entry.path()?.into_owned();
+        if rel == Path::new(MANIFEST_NAME) {
+            continue;
+        }
+        if !is_rollout_path(&rel) {
+            ui::print_warn(&format!("skipping unexpected entry {}", rel.display()));
+            continue;
+        }
+        let dest = codex_home.join(&rel);
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        let id = sessions::parse_session_meta(data.as_slice(), &dest)
+            .ok()
+            .flatten()
+            .map(|s| s.id);
+        if dest.exists() || id.as_ref().is_some_and(|id| existing.contains(id)) {
+            skipped += 1;
+            continue;
+        }
+        if dry_run {
+            ui::print_info(&format!("DRY RUN: would restore {}", dest.display()));
+        } else {
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            fs::write(&dest, &data)
+                .with_context(|| format!("failed to write {}", dest.display()))?;
+        }
+        restored += 1;
+    }
+
+    ui::print_info(&format!(
+        "Restored {restored} sessions into {} ({skipped} already present)",
+        codex_home.join("sessions").display()
+    ));
+    Ok(())
+}
+
+/// Only accept `sessions/YYYY/MM/DD/rollout-*.jsonl`, so an archive can't write elsewhere.
+fn is_rollout_path(rel: &Path) -> bool {
+    let parts = rel
+        .components()
+        .map(|c| match c {
+            Component::Normal(s) => s.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    let Some(parts) = parts else {
+        return false;
+    };
+    let [root, year, month, day, name] = parts.as_slice() else {
+        return false;
+    };
+    let digits = |s: &str, n: usize| s.len() == n && s.chars().all(|c| c.is_ascii_digit());
+    *root == "sessions"
+        && digits(year, 4)
+        && digits(month, 2)
+        && digits(day, 2)
+        && name.starts_with("rollout-")
+        && name.ends_with(".jsonl")
+}
+
+fn is_zstd(reader: &mut BufReader<fs::File>) -> Result<bool> {
+    use std::io::BufRead;
+    Ok(reader.fill_buf()?.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]))
+}
+
+fn is_plain_tar(p: &Path) -> bool {
+    p.extension().is_some_and(|e| e == "tar")
+}
+
+fn now_rfc3339() -> String {
+    time::OffsetDateTime::now_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_rollout_paths_are_restorable() {
+        assert!(is_rollout_path(Path::new(
+            "sessions/2026/02/01/rollout-2026-02-01T10-00-00-abc.jsonl"
+        )));
+        assert!(!is_rollout_path(Path::new("sessions/../../.bashrc")));
+        assert!(!is_rollout_path(Path::new("/etc/passwd")));
+        assert!(!is_rollout_path(Path::new("sessions/2026/02/01/notes.txt")));
+    }
+}
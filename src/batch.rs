@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::RunOpts;
+use crate::config::Config;
+use crate::export;
+use crate::procs::{self, CodexProcess};
+use crate::sessions::{Outcome, SessionItem};
+use crate::state::State;
+use crate::ui;
+
+/// Actions applied to the sessions marked in the picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchAction {
+    /// Remove the rollout files (asks first).
+    Delete,
+    /// Move rollouts to `<codex_home>/archived_sessions/`, where Codex keeps archived sessions.
+    Archive,
+    /// Add a tag (stored in the launcher's state file).
+    Tag,
+    /// Write Markdown transcripts to a directory.
+    Export,
+    /// Send SIGINT/SIGTERM to the codex process of active (`⋯`) sessions (asks first).
+    Signal,
+}
+
+/// Run `action` on `sessions`, prompting for whatever it needs. Returns a one-line summary
+/// for the picker's footer.
+pub fn apply(
+    cfg: &Config,
+    state_path: &std::path::Path,
+    action: BatchAction,
+    sessions: &[SessionItem],
+    run: RunOpts,
+) -> Result<String> {
+    let n = sessions.len();
+    // Sessions only known from history.jsonl have no rollout to delete, move or export.
+    let rollouts: Vec<&Path> = sessions.iter().filter_map(|s| s.path.as_deref()).collect();
+    if rollouts.is_empty()
+        && matches!(
+            action,
+            BatchAction::Delete | BatchAction::Archive | BatchAction::Export
+        )
+    {
+        return Ok("No rollout files among the selected session(s)".to_string());
+    }
+    match action {
+        BatchAction::Delete => {
+            let n = rollouts.len();
+            if !ui::confirm(&format!(
+                "Delete {n} session(s)? Rollout files are removed."
+            ))? {
+                return Ok("Delete cancelled".to_string());
+            }
+            for path in rollouts {
+                if run.dry_run {
+                    ui::print_info(&format!("DRY RUN: rm {}", path.display()));
+                    continue;
+                }
+                fs::remove_file(path)
+                    .with_context(|| format!("failed to delete {}", path.display()))?;
+            }
+            Ok(format!("Deleted {n} session(s)"))
+        }
+        BatchAction::Archive => {
+            let n = rollouts.len();
+            let dir = cfg.sessions.codex_home.join("archived_sessions");
+            for path in rollouts {
+                let Some(name) = path.file_name() else {
+                    continue;
+                };
+                let dest = dir.join(name);
+                if run.dry_run {
+                    ui::print_info(&format!(
+                        "DRY RUN: mv {} {}",
+                        path.display(),
+                        dest.display()
+                    ));
+                    continue;
+                }
+                fs::create_dir_all(&dir)
+                    .with_context(|| format!("failed to create {}", dir.display()))?;
+                fs::rename(path, &dest)
+                    .with_context(|| format!("failed to move {}", path.display()))?;
+            }
+            Ok(format!("Archived {n} session(s) to {}", dir.display()))
+        }
+        BatchAction::Tag => {
+            let Some(tag) = ui::prompt_text("Tag:", None)? else {
+                return Ok("Tag cancelled".to_string());
+            };
+            let mut state = State::load_for_update(state_path)?;
+            for s in sessions {
+                state
+                    .session_tags
+                    .entry(s.id.clone())
+                    .or_default()
+                    .insert(tag.clone());
+            }
+            if !run.dry_run {
+                state.save(state_path)?;
+            }
+            Ok(format!("Tagged {n} session(s) #{tag}"))
+        }
+        BatchAction::Export => {
+            let Some(dir) = ui::prompt_text("Export to directory:", Some("codex-export"))? else {
+                return Ok("Export cancelled".to_string());
+            };
+            let dir = PathBuf::from(dir);
+            if run.dry_run {
+                return Ok(format!(
+                    "DRY RUN: would export {} session(s) to {}",
+                    rollouts.len(),
+                    dir.display()
+                ));
+            }
+            let written = export::export_markdown(sessions, &dir)?;
+            Ok(format!(
+                "Exported {} session(s) to {}",
+                written.len(),
+                dir.display()
+            ))
+        }
+        BatchAction::Signal => {
+            let active: Vec<&SessionItem> = sessions
+                .iter()
+                .filter(|s| s.outcome == Outcome::Running)
+                .collect();
+            if active.is_empty() {
+                return Ok("No active session selected".to_string());
+            }
+            // A session's process is the codex running in its cwd; ask when several do.
+            let running = procs::codex_processes()?;
+            let mut targets: Vec<CodexProcess> = Vec::new();
+            for s in active {
+                let candidates: Vec<CodexProcess> = running
+                    .iter()
+                    .filter(|p| p.cwd == s.cwd && !targets.iter().any(|t| t.pid == p.pid))
+                    .cloned()
+                    .collect();
+                let picked = match candidates.len() {
+                    0 => None,
+                    1 => candidates.into_iter().next(),
+                    _ => ui::pick_process(s, candidates)?,
+                };
+                targets.extend(picked);
+            }
+            if targets.is_empty() {
+                return Ok("No running codex process found for the selected session(s)".to_string());
+            }
+            let Some(signal) = ui::pick_signal(&targets)? else {
+                return Ok("Signal cancelled".to_string());
+            };
+            for p in targets.iter() {
+                if run.dry_run {
+                    ui::print_info(&format!("DRY RUN: kill -s {signal} {}", p.pid));
+                    continue;
+                }
+                procs::signal(p.pid, signal)?;
+            }
+            Ok(format!(
+                "Sent SIG{signal} to {} codex process(es)",
+                targets.len()
+            ))
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The subset of Codex CLI's own `config.toml` (under `codex_home`) that the launcher reads.
+/// Unknown keys are ignored; a missing or malformed file yields `None` from [`load`].
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CodexCliConfig {
+    #[serde(default)]
+    pub approval_policy: Option<String>,
+
+    #[serde(default)]
+    pub sandbox_mode: Option<String>,
+
+    #[serde(default)]
+    pub model: Option<String>,
+
+    #[serde(default)]
+    pub model_provider: Option<String>,
+
+    /// Named profiles (`[profiles.<name>]`), selectable with `codex --profile <name>`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, CodexProfile>,
+
+    /// Extra model providers (`[model_providers.<id>]`) on top of Codex's built-ins.
+    #[serde(default)]
+    pub model_providers: BTreeMap<String, CodexModelProvider>,
+
+    /// Per-directory settings, keyed by absolute path.
+    #[serde(default)]
+    pub projects: BTreeMap<PathBuf, CodexProjectSettings>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CodexProjectSettings {
+    #[serde(default)]
+    pub trust_level: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CodexProfile {
+    #[serde(default)]
+    pub model: Option<String>,
+
+    #[serde(default)]
+    pub model_provider: Option<String>,
+
+    #[serde(default)]
+    pub approval_policy: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CodexModelProvider {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trust {
+    /// Not listed in Codex's config (or no config): Codex will ask on first launch.
+    #[default]
+    Unknown,
+    Trusted,
+    Untrusted,
+}
+
+impl Trust {
+    pub fn marker(self) -> char {
+        match self {
+            Trust::Unknown => ' ',
+            Trust::Trusted => 'T',
+            Trust::Untrusted => 'U',
+        }
+    }
+}
+
+pub const SANDBOX_MODES: &[&str] = &["read-only", "workspace-write", "danger-full-access"];
+
+pub const APPROVAL_POLICIES: &[&str] = &["untrusted", "on-failure", "on-request", "never"];
+
+pub fn config_path(codex_home: &Path) -> PathBuf {
+    codex_home.join("config.toml")
+}
+
+pub fn load(codex_home: &Path) -> Option<CodexCliConfig> {
+    let s = fs::read_to_string(config_path(codex_home)).ok()?;
+    toml::from_str(&s).ok()
+}
+
+impl CodexCliConfig {
+    /// Models mentioned anywhere in Codex's config (top level and profiles), deduplicated.
+    pub fn known_models(&self) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        let profile_models = self.profiles.values().filter_map(|p| p.model.as_deref());
+        for m in self.model.as_deref().into_iter().chain(profile_models) {
+            if !out.iter().any(|x| x == m) {
+                out.push(m.to_string());
+            }
+        }
+        out
+    }
+
+    /// One-line description of a profile for pickers and listings, e.g. `o3 @ azure (Azure OpenAI), approval on-request`.
+    pub fn describe_profile(&self, name: &str) -> String {
+        let Some(profile) = self.profiles.get(name) else {
+            return String::new();
+        };
+        let model = profile
+            .model
+            .as_deref()
+            .or(self.model.as_deref())
+            .unwrap_or("default model");
+        let mut out = model.to_string();
+        if let Some(provider) = profile
+            .model_provider
+            .as_deref()
+            .or(self.model_provider.as_deref())
+        {
+            out.push_str(&format!(" @ {provider}"));
+            if let Some(display) = self
+                .model_providers
+                .get(provider)
+                .and_then(|p| p.name.as_deref())
+            {
+                out.push_str(&format!(" ({display})"));
+            }
+        }
+        if let Some(policy) = profile.approval_policy.as_deref() {
+            out.push_str(&format!(", approval {policy}"));
+        }
+        out
+    }
+
+    /// Trust level of the closest configured ancestor of `dir` (or `dir` itself).
+    pub fn trust_for(&self, dir: &Path) -> Trust {
+        let best = self
+            .projects
+            .iter()
+            .filter(|(p, _)| dir.starts_with(p))
+            .max_by_key(|(p, _)| p.components().count());
+        match best.and_then(|(_, s)| s.trust_level.as_deref()) {
+            Some("trusted") => Trust::Trusted,
+            Some("untrusted") => Trust::Untrusted,
+            _ => Trust::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_project_entry_decides_trust() {
+        let cfg: CodexCliConfig = toml::from_str(
+            r#"
+approval_policy = "on-request"
+
+[projects."/work"]
+trust_level = "trusted"
+
+[projects."/work/vendor"]
+trust_level = "untrusted"
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.trust_for(Path::new("/work/api")), Trust::Trusted);
+        assert_eq!(cfg.trust_for(Path::new("/work/vendor/x")), Trust::Untrusted);
+        assert_eq!(cfg.trust_for(Path::new("/tmp")), Trust::Unknown);
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::sessions;
+
+/// One line of Codex's `history.jsonl`: a prompt the user typed, with its session.
+#[derive(Debug, Deserialize)]
+struct Entry {
+    session_id: String,
+    /// Unix seconds.
+    ts: i64,
+    text: String,
+}
+
+/// The first prompt of each session in `<codex_home>/history.jsonl`.
+#[derive(Debug, Default)]
+pub struct PromptHistory {
+    first: HashMap<String, (i64, String)>,
+}
+
+pub fn history_path(codex_home: &Path) -> PathBuf {
+    codex_home.join("history.jsonl")
+}
+
+/// A missing or unreadable file is an empty history; malformed lines are skipped.
+pub fn load(codex_home: &Path) -> PromptHistory {
+    let path = history_path(codex_home);
+    let mut first: HashMap<String, (i64, String)> = HashMap::new();
+    if let Ok(file) = fs::File::open(&path) {
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            let Ok(e) = serde_json::from_str::<Entry>(&line) else {
+                continue;
+            };
+            if e.text.trim().is_empty() || sessions::looks_like_boilerplate(&e.text) {
+                continue;
+            }
+            first.entry(e.session_id).or_insert((e.ts, e.text));
+        }
+    }
+    PromptHistory { first }
+}
+
+impl PromptHistory {
+    pub fn first_prompt(&self, session_id: &str) -> Option<&str> {
+        self.first.get(session_id).map(|(_, text)| text.as_str())
+    }
+
+    /// `(session id, unix time of its first prompt, prompt)` for every session.
+    pub fn sessions(&self) -> impl Iterator<Item = (&str, i64, &str)> {
+        self.first
+            .iter()
+            .map(|(id, (ts, text))| (id.as_str(), *ts, text.as_str()))
+    }
+}
@@ -1,9 +1,14 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use globset::GlobBuilder;
 use serde::{Deserialize, Serialize};
 
+use crate::remote;
+use crate::sessions;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
@@ -14,6 +19,70 @@ pub struct Config {
 
     #[serde(default)]
     pub sessions: SessionsConfig,
+
+    #[serde(default)]
+    pub launch: LaunchConfig,
+
+    #[serde(default)]
+    pub sync: SyncConfig,
+
+    #[serde(default)]
+    pub search: SearchConfig,
+
+    #[serde(default)]
+    pub display: DisplayConfig,
+
+    #[serde(default)]
+    pub open: OpenConfig,
+
+    /// Extra entries for the project action menu (`[[actions]]`).
+    #[serde(default)]
+    pub actions: Vec<CustomAction>,
+
+    /// Other agent CLIs that can stand in for codex (`[agents.<name>]`).
+    #[serde(default)]
+    pub agents: BTreeMap<String, AgentConfig>,
+
+    /// Folder the config was loaded from, where machine-local caches are kept.
+    #[serde(skip)]
+    pub dir: Option<PathBuf>,
+
+    /// Agent picked with `--agent` for this run; beats `projects.agents` and `launch.agent`.
+    #[serde(skip)]
+    pub agent_override: Option<String>,
+
+    /// Codex profile for this run: `--profile`, else a matching `projects.overrides` entry's;
+    /// see [`Config::codex_args`].
+    #[serde(skip)]
+    pub profile_override: Option<String>,
+
+    /// Model from a matching `projects.overrides` entry; see [`Config::codex_args`].
+    #[serde(skip)]
+    pub model_override: Option<String>,
+}
+
+/// An agent CLI launched instead of codex, e.g. `[agents.claude] bin = "claude"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentConfig {
+    pub bin: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Arguments that resume a session; `{id}` is replaced by the session id.
+    #[serde(default = "default_resume_style")]
+    pub resume_style: Vec<String>,
+
+    /// `model_provider`s of recorded sessions this agent resumes instead of codex.
+    #[serde(default)]
+    pub providers: Vec<String>,
+}
+
+/// A project action-menu entry: a shell command run in the project folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomAction {
+    pub name: String,
+    pub command: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +92,44 @@ pub struct CodexConfig {
 
     #[serde(default)]
     pub args: Vec<String>,
+
+    /// Ask which Codex profile (from Codex's own config.toml) to use before starting a new session.
+    #[serde(default)]
+    pub pick_profile: bool,
+
+    /// Ask for sandbox mode, approval policy and model before starting a new session.
+    #[serde(default)]
+    pub launch_options: bool,
+
+    /// Extra models offered by the launch-options prompt (on top of those in Codex's config).
+    #[serde(default)]
+    pub models: Vec<String>,
+
+    /// The codex executable on the other end of `ssh://` targets.
+    #[serde(default = "default_codex_bin")]
+    pub remote_bin: String,
+
+    /// Arguments that resume a session, after `args`; `{id}` is replaced by the session id.
+    /// Codex builds differ: `["resume", "{id}"]`, `["--resume", "{id}"]`, `["exec", "resume", "{id}"]`.
+    #[serde(default = "default_resume_style")]
+    pub resume_style: Vec<String>,
+}
+
+impl CodexConfig {
+    /// `resume_style` filled in for session `id`.
+    pub fn resume_args(&self, id: &str) -> Result<Vec<String>> {
+        if !self.resume_style.iter().any(|a| a.contains("{id}")) {
+            anyhow::bail!(
+                "resume_style must contain \"{{id}}\" (got {:?})",
+                self.resume_style
+            );
+        }
+        Ok(self
+            .resume_style
+            .iter()
+            .map(|a| a.replace("{id}", id))
+            .collect())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,13 +142,115 @@ pub struct ProjectsConfig {
     #[serde(default)]
     pub paths: Vec<PathBuf>,
 
+    /// Display labels for targets (instead of the folder name), keyed by path.
+    #[serde(default)]
+    pub labels: BTreeMap<PathBuf, String>,
+
     /// Also populate targets based on recent Codex sessions (from `sessions/` JSONL).
     #[serde(default = "default_projects_from_sessions")]
     pub from_sessions: bool,
 
+    /// Whether the picker puts the current directory's target first.
+    #[serde(default)]
+    pub prioritize_cwd: PrioritizeCwd,
+
+    /// Folders never added to the picker as the current directory (besides `~` and the roots).
+    #[serde(default)]
+    pub cwd_exclude: Vec<PathBuf>,
+
+    /// Globs (or `re:` regexes) for folders that root scans and session history don't turn into
+    /// targets; see `ignore::Excludes`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
     /// How many recent sessions to scan to infer targets.
     #[serde(default = "default_projects_sessions_limit")]
     pub sessions_limit: usize,
+
+    /// Stop scanning a root after this many repos (a root like `~` would flood the picker).
+    #[serde(default = "default_root_scan_limit")]
+    pub root_scan_limit: usize,
+
+    /// List monorepo packages as their own targets (`repo/package`), right after their repo.
+    #[serde(default)]
+    pub sub_targets: bool,
+
+    /// List a repo's initialized git submodules as its own targets (`repo/submodule`).
+    #[serde(default)]
+    pub submodules: bool,
+
+    /// Per-target overrides of how many sessions a project's session list shows
+    /// (default: `sessions.limit`), keyed by target path.
+    #[serde(default)]
+    pub session_limits: BTreeMap<PathBuf, usize>,
+
+    /// Running containers to start codex in (`<runtime> exec -it -w <path> <container>`),
+    /// keyed by target path.
+    #[serde(default)]
+    pub containers: BTreeMap<PathBuf, String>,
+
+    /// Per-target overrides of `launch.direnv` / `launch.env_file`, keyed by target path
+    /// (also used for sessions resumed in a subfolder).
+    #[serde(default)]
+    pub env: BTreeMap<PathBuf, ProjectEnv>,
+
+    /// Per-target agent for new sessions (a name from `[agents]`, or `codex`), keyed by target
+    /// path.
+    #[serde(default)]
+    pub agents: BTreeMap<PathBuf, String>,
+
+    /// Codex settings for launches in particular folders (`[[projects.overrides]]`), applied
+    /// in order over `codex.*` and `launch.env`.
+    #[serde(default)]
+    pub overrides: Vec<ProjectOverride>,
+}
+
+/// `[[projects.overrides]]`: what changes for launches in and below `path`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectOverride {
+    /// A folder, or a glob such as `~/work/*` for a root's children.
+    pub path: PathBuf,
+
+    /// Replaces `codex.args`.
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+
+    /// Passed as `--profile`.
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// Passed as `--model`.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Added to `launch.env`, replacing variables of the same name.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+impl ProjectOverride {
+    fn matches(&self, dir: &Path) -> Result<bool> {
+        let path = normalize(self.path.clone())?;
+        let pattern = path.to_string_lossy();
+        if !pattern.contains(['*', '?', '[', '{']) {
+            return Ok(dir.starts_with(&path));
+        }
+        let glob = GlobBuilder::new(&pattern)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("invalid projects.overrides path: {pattern}"))?
+            .compile_matcher();
+        Ok(dir.ancestors().any(|a| glob.is_match(a)))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectEnv {
+    #[serde(default)]
+    pub direnv: Option<bool>,
+
+    #[serde(default)]
+    pub env_file: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,12 +262,304 @@ pub struct SessionsConfig {
     /// Default number of sessions to show.
     #[serde(default = "default_sessions_limit")]
     pub limit: usize,
+
+    /// Prefix rewrites for session cwds recorded elsewhere (another machine, a container),
+    /// e.g. `"/home/me" = "/Users/me"`; `~` and `$VAR` expand on both sides.
+    #[serde(default)]
+    pub path_rewrites: BTreeMap<String, String>,
+
+    /// Also list sessions only found in Codex's `history.jsonl` (their rollout is gone).
+    #[serde(default)]
+    pub history_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchConfig {
+    /// How codex is started once a target/session is picked.
+    #[serde(default)]
+    pub mode: LaunchMode,
+
+    /// Extra environment variables for the spawned codex process.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Shell commands run (in the launch directory) before codex starts; a failing hook aborts.
+    #[serde(default)]
+    pub hooks: Vec<String>,
+
+    /// Whether new sessions in a folder with `.devcontainer/devcontainer.json` run inside it.
+    #[serde(default)]
+    pub devcontainer: DevcontainerMode,
+
+    /// `docker` or `podman` (anything with a compatible `exec`) for `projects.containers`.
+    #[serde(default = "default_container_runtime")]
+    pub container_runtime: String,
+
+    /// Terminal title while codex runs; `{label}` and `{path}` are filled in, `""` turns it off.
+    #[serde(default = "default_launch_title")]
+    pub title: String,
+
+    /// Ask for a subfolder of the project (monorepo packages, or any path) to start new
+    /// sessions in; they still count as the project's sessions.
+    #[serde(default)]
+    pub pick_subdir: bool,
+
+    /// Shell command prefix codex runs through, e.g. `zsh -ic`, for PATH set up in rc files.
+    #[serde(default)]
+    pub shell: Option<String>,
+
+    /// Run codex through `direnv exec <dir>` so it gets the folder's `.envrc` environment.
+    #[serde(default)]
+    pub direnv: bool,
+
+    /// Dotenv file (relative to the launch folder) whose variables codex inherits, if present.
+    #[serde(default)]
+    pub env_file: Option<String>,
+
+    /// Agent for new sessions where `projects.agents` names none (default: codex).
+    #[serde(default)]
+    pub agent: Option<String>,
+
+    /// Whether `zellij` mode opens codex in a new pane or a new tab.
+    #[serde(default)]
+    pub zellij: ZellijPlace,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ZellijPlace {
+    #[default]
+    Pane,
+    Tab,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrioritizeCwd {
+    /// Move the current directory's target to the top, adding the directory when it isn't one.
+    #[default]
+    Always,
+    /// Only move up the target the current directory is in; never add one.
+    InsideTargets,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DevcontainerMode {
+    /// Ask each time (on a terminal; otherwise run on the host).
+    #[default]
+    Ask,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMode {
+    /// Run codex in this terminal and wait for it.
+    #[default]
+    Exec,
+    /// One persistent tmux session per project (named after its label): attach when it's
+    /// already running, otherwise create it with codex running inside.
+    Tmux,
+    /// A new tab of the running kitty (`kitty @ launch`; needs `allow_remote_control`).
+    Kitty,
+    /// A new tab of the running WezTerm (`wezterm cli spawn`).
+    Wezterm,
+    /// A new pane or tab (`launch.zellij`) of the Zellij session codex-launch runs in
+    /// (`zellij action new-pane`).
+    Zellij,
+}
+
+/// `codex-launch config sync` settings (the config directory is kept in a git repo).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConfig {
+    /// Git remote URL to pull from / push to; without it, sync only commits locally.
+    #[serde(default)]
+    pub remote: Option<String>,
+
+    #[serde(default = "default_sync_branch")]
+    pub branch: String,
+}
+
+/// Session content search settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchConfig {
+    /// Keep a full-text index of session messages under `<config dir>/index/` (updated by mtime in
+    /// the background while the picker is open) so `search` and `--resume` can match on content.
+    #[serde(default)]
+    pub index: bool,
+}
+
+/// `codex-launch open` settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenConfig {
+    /// What `open` opens a project in when `--with` isn't given.
+    #[serde(default)]
+    pub with: OpenWith,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenWith {
+    /// `$VISUAL` / `$EDITOR`.
+    #[default]
+    Editor,
+    /// The system file manager (`open` / `xdg-open`).
+    Files,
+    /// `$SHELL` in the project folder.
+    Shell,
+}
+
+/// How lists look in the picker.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// Session badge colors, keyed by badge text (`openai`, `vscode`, `gpt-5-codex`) or by kind
+    /// (`provider`, `source`, `model`); values are color names like `green` or `dark_cyan`.
+    #[serde(default)]
+    pub badge_colors: BTreeMap<String, String>,
+    /// Prefix rows with Nerd Font icons: language and git for projects, origin for sessions.
+    #[serde(default)]
+    pub icons: bool,
+    /// Show a preview pane (README head, language, last commit) under the project list.
+    #[serde(default)]
+    pub preview: bool,
+    /// Add a column with each project's HEAD commit age and subject.
+    #[serde(default)]
+    pub last_commit: bool,
+    /// Add a column with each project's branch, marked `*` with uncommitted changes.
+    #[serde(default)]
+    pub git_status: bool,
+    /// How session times are shown in rows.
+    #[serde(default)]
+    pub dates: DateStyle,
+    /// Add a column with how often (and when last) codex was launched for each project.
+    #[serde(default)]
+    pub launches: bool,
+    /// How folders are shown in project and session rows.
+    #[serde(default)]
+    pub paths: PathStyle,
+    /// Vim-style list motions in the picker (`gg`, `G`, `ctrl+d/u/f/b`).
+    #[serde(default)]
+    pub vim_keys: bool,
+
+    /// Numbered lists and typed answers instead of the full-screen picker (`--plain-ui`).
+    #[serde(default)]
+    pub plain_ui: bool,
+
+    /// Which picker the no-args run opens (`--picker`).
+    #[serde(default)]
+    pub picker: Picker,
+    /// Fuzzy finder the `fzf` picker pipes lists into, run through the shell (default: `fzf`).
+    #[serde(default)]
+    pub fzf_command: Option<String>,
+
+    /// Order of the picker's Projects tab; `s` there cycles it and saves the choice here.
+    #[serde(default)]
+    pub sort: ProjectSort,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectSort {
+    /// Latest session first, then by label, with the current folder's project on top.
+    #[default]
+    Recent,
+    Label,
+    Path,
+    /// Most sessions first.
+    Sessions,
+}
+
+impl ProjectSort {
+    pub fn next(self) -> Self {
+        match self {
+            ProjectSort::Recent => ProjectSort::Label,
+            ProjectSort::Label => ProjectSort::Path,
+            ProjectSort::Path => ProjectSort::Sessions,
+            ProjectSort::Sessions => ProjectSort::Recent,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectSort::Recent => "recent",
+            ProjectSort::Label => "label",
+            ProjectSort::Path => "path",
+            ProjectSort::Sessions => "sessions",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Picker {
+    /// The built-in full-screen picker.
+    #[default]
+    Tui,
+    /// An external fuzzy finder (`display.fzf_command`: fzf, sk, fzy, …).
+    Fzf,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateStyle {
+    /// `Today 14:02`, `Yesterday 09:30`, `Mon 18:12`, then `Jan17 10:11`.
+    #[default]
+    Calendar,
+    /// Age plus date: `3d Jan17 10:11`.
+    Age,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// `~` for home, and the head elided (`…/api/crates/core`) when it doesn't fit.
+    #[default]
+    Compact,
+    /// The whole absolute path.
+    Full,
+}
+
+impl PathStyle {
+    pub fn toggled(self) -> Self {
+        match self {
+            PathStyle::Compact => PathStyle::Full,
+            PathStyle::Full => PathStyle::Compact,
+        }
+    }
+}
+
+fn default_sync_branch() -> String {
+    "main".to_string()
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            remote: None,
+            branch: default_sync_branch(),
+        }
+    }
 }
 
 fn default_codex_bin() -> String {
     "codex".to_string()
 }
 
+fn default_resume_style() -> Vec<String> {
+    vec!["resume".to_string(), "{id}".to_string()]
+}
+
+fn default_launch_title() -> String {
+    "codex: {label}".to_string()
+}
+
+fn default_container_runtime() -> String {
+    "docker".to_string()
+}
+
 fn default_codex_home() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("/"))
@@ -73,6 +574,10 @@ fn default_projects_from_sessions() -> bool {
     true
 }
 
+fn default_root_scan_limit() -> usize {
+    300
+}
+
 fn default_projects_sessions_limit() -> usize {
     200
 }
@@ -82,6 +587,30 @@ impl Default for CodexConfig {
         Self {
             bin: default_codex_bin(),
             args: Vec::new(),
+            pick_profile: false,
+            launch_options: false,
+            models: Vec::new(),
+            remote_bin: default_codex_bin(),
+            resume_style: default_resume_style(),
+        }
+    }
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            mode: LaunchMode::default(),
+            env: BTreeMap::new(),
+            hooks: Vec::new(),
+            devcontainer: DevcontainerMode::default(),
+            container_runtime: default_container_runtime(),
+            title: default_launch_title(),
+            pick_subdir: false,
+            shell: None,
+            direnv: false,
+            env_file: None,
+            agent: None,
+            zellij: ZellijPlace::default(),
         }
     }
 }
@@ -96,8 +625,20 @@ impl Default for ProjectsConfig {
         Self {
             roots,
             paths: Vec::new(),
+            labels: BTreeMap::new(),
             from_sessions: default_projects_from_sessions(),
+            prioritize_cwd: PrioritizeCwd::default(),
+            cwd_exclude: Vec::new(),
+            exclude: Vec::new(),
             sessions_limit: default_projects_sessions_limit(),
+            root_scan_limit: default_root_scan_limit(),
+            sub_targets: false,
+            submodules: false,
+            session_limits: BTreeMap::new(),
+            containers: BTreeMap::new(),
+            env: BTreeMap::new(),
+            agents: BTreeMap::new(),
+            overrides: Vec::new(),
         }
     }
 }
@@ -107,6 +648,8 @@ impl Default for SessionsConfig {
         Self {
             codex_home: default_codex_home(),
             limit: default_sessions_limit(),
+            path_rewrites: BTreeMap::new(),
+            history_only: false,
         }
     }
 }
@@ -124,12 +667,16 @@ impl Config {
         if path.exists() {
             let s = fs::read_to_string(path)
                 .with_context(|| format!("failed to read {}", path.display()))?;
-            let cfg: Config =
+            let mut cfg: Config =
                 toml::from_str(&s).with_context(|| format!("invalid TOML: {}", path.display()))?;
+            cfg.dir = path.parent().map(Path::to_path_buf);
             return Ok(cfg);
         }
 
-        let cfg = Config::default();
+        let cfg = Config {
+            dir: path.parent().map(Path::to_path_buf),
+            ..Config::default()
+        };
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("failed to create {}", parent.display()))?;
@@ -148,6 +695,159 @@ impl Config {
         Ok(())
     }
 
+    /// A session's recorded cwd mapped through `sessions.path_rewrites` (longest prefix wins).
+    pub fn rewrite_session_path(&self, cwd: &Path) -> PathBuf {
+        let best = self
+            .sessions
+            .path_rewrites
+            .iter()
+            .map(|(from, to)| (expand_path(from), to))
+            .filter(|(from, _)| cwd.starts_with(from))
+            .max_by_key(|(from, _)| from.components().count());
+        match best {
+            Some((from, to)) => {
+                let rest = cwd.strip_prefix(&from).unwrap_or(Path::new(""));
+                let to = expand_path(to);
+                if rest.as_os_str().is_empty() {
+                    to
+                } else {
+                    to.join(rest)
+                }
+            }
+            None => cwd.to_path_buf(),
+        }
+    }
+
+    /// The container `projects.containers` assigns to the target at `path`.
+    pub fn container_for(&self, path: &Path) -> Option<&str> {
+        self.projects
+            .containers
+            .iter()
+            .find(|(p, _)| normalize(p.to_path_buf()).is_ok_and(|p| p == path))
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// `launch.direnv` / `launch.env_file` for a launch in `dir`, with the `projects.env` entry
+    /// of the deepest configured folder containing it taking precedence.
+    pub fn project_env(&self, dir: &Path) -> (bool, Option<&str>) {
+        let project = self
+            .projects
+            .env
+            .iter()
+            .filter_map(|(p, env)| Some((normalize(p.to_path_buf()).ok()?, env)))
+            .filter(|(p, _)| dir.starts_with(p))
+            .max_by_key(|(p, _)| p.components().count())
+            .map(|(_, env)| env);
+        let direnv = project.and_then(|e| e.direnv).unwrap_or(self.launch.direnv);
+        let env_file = project
+            .and_then(|e| e.env_file.as_deref())
+            .or(self.launch.env_file.as_deref());
+        (direnv, env_file)
+    }
+
+    /// How many sessions to list for the target at `path`.
+    /// The agent for new sessions in `dir`: `--agent`, else the deepest `projects.agents` folder
+    /// containing `dir`, else `launch.agent`. `None` is codex.
+    pub fn agent_for(&self, dir: &Path) -> Option<&str> {
+        let project = self
+            .projects
+            .agents
+            .iter()
+            .filter_map(|(p, agent)| Some((normalize(p.to_path_buf()).ok()?, agent)))
+            .filter(|(p, _)| dir.starts_with(p))
+            .max_by_key(|(p, _)| p.components().count())
+            .map(|(_, agent)| agent);
+        self.agent_override
+            .as_ref()
+            .or(project)
+            .or(self.launch.agent.as_ref())
+            .map(String::as_str)
+            .filter(|name| *name != "codex")
+    }
+
+    /// The arguments codex is started with: `codex.args`, then `--profile` / `--model` for this
+    /// run's profile and model, which replace any given in `codex.args`.
+    pub fn codex_args(&self) -> Vec<String> {
+        let mut args = self.codex.args.clone();
+        if let Some(profile) = &self.profile_override {
+            remove_flag(&mut args, "--profile", "-p");
+            args.extend(["--profile".to_string(), profile.clone()]);
+        }
+        if let Some(model) = &self.model_override {
+            remove_flag(&mut args, "--model", "-m");
+            args.extend(["--model".to_string(), model.clone()]);
+        }
+        args
+    }
+
+    /// A copy with the `projects.overrides` entries matching `dir` applied, or `None` when no
+    /// entry matches.
+    pub fn with_overrides(&self, dir: &Path) -> Result<Option<Config>> {
+        let mut matching = Vec::new();
+        for o in self.projects.overrides.iter() {
+            if o.matches(dir)? {
+                matching.push(o);
+            }
+        }
+        if matching.is_empty() {
+            return Ok(None);
+        }
+        let mut cfg = self.clone();
+        let (mut profile, mut model) = (None, None);
+        for o in matching {
+            if let Some(args) = &o.args {
+                cfg.codex.args = args.clone();
+            }
+            profile = o.profile.clone().or(profile);
+            model = o.model.clone().or(model);
+            cfg.launch.env.extend(o.env.clone());
+        }
+        // `--profile` on the command line beats the overrides.
+        cfg.profile_override = self.profile_override.clone().or(profile);
+        cfg.model_override = model.or(cfg.model_override);
+        Ok(Some(cfg))
+    }
+
+    /// The agent that resumes a session recorded with `provider`: `--agent`, else the first agent
+    /// listing it in `providers`. `None` is codex.
+    pub fn agent_for_provider(&self, provider: Option<&str>) -> Option<&str> {
+        if let Some(name) = self.agent_override.as_deref() {
+            return (name != "codex").then_some(name);
+        }
+        let provider = provider?;
+        self.agents
+            .iter()
+            .find(|(_, a)| a.providers.iter().any(|p| p == provider))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// A copy whose `codex.*` command settings are agent `name`'s, so launches run it instead.
+    /// Codex-only prompts (profile, launch options) are turned off.
+    pub fn with_agent(&self, name: &str) -> Result<Config> {
+        let Some(agent) = self.agents.get(name) else {
+            anyhow::bail!("unknown agent: {name} (define it under [agents.{name}] in the config)");
+        };
+        let mut cfg = self.clone();
+        cfg.codex.bin = agent.bin.clone();
+        cfg.codex.remote_bin = agent.bin.clone();
+        cfg.codex.args = agent.args.clone();
+        cfg.codex.resume_style = agent.resume_style.clone();
+        cfg.profile_override = None;
+        cfg.model_override = None;
+        cfg.codex.pick_profile = false;
+        cfg.codex.launch_options = false;
+        Ok(cfg)
+    }
+
+    pub fn project_session_limit(&self, path: &Path) -> usize {
+        self.projects
+            .session_limits
+            .iter()
+            .find(|(p, _)| normalize(p.to_path_buf()).is_ok_and(|p| p == path))
+            .map(|(_, limit)| *limit)
+            .unwrap_or(self.sessions.limit)
+    }
+
     pub fn add_root(&mut self, path: PathBuf) -> Result<()> {
         let p = normalize(path)?;
         if !p.exists() {
@@ -162,13 +862,18 @@ impl Config {
         Ok(())
     }
 
-    pub fn add_path(&mut self, path: PathBuf) -> Result<()> {
+    pub fn add_path(&mut self, path: PathBuf, label: Option<String>) -> Result<()> {
         let p = normalize(path)?;
-        if !p.exists() {
-            anyhow::bail!("path does not exist: {}", p.display());
+        if !remote::is_remote(&p) {
+            if !p.exists() {
+                anyhow::bail!("path does not exist: {}", p.display());
+            }
+            if !p.is_dir() {
+                anyhow::bail!("not a directory: {}", p.display());
+            }
         }
-        if !p.is_dir() {
-            anyhow::bail!("not a directory: {}", p.display());
+        if let Some(label) = label {
+            self.projects.labels.insert(p.clone(), label);
         }
         if !self.projects.paths.contains(&p) {
             self.projects.paths.push(p);
@@ -176,38 +881,266 @@ impl Config {
         Ok(())
     }
 
+    /// Set (or with an empty label, clear) the label of a configured path.
+    pub fn set_label(&mut self, path: PathBuf, label: String) -> Result<()> {
+        let p = normalize(path)?;
+        if !self.projects.paths.contains(&p) {
+            anyhow::bail!(
+                "not a configured path: {} (add it with `codex-launch add-path`)",
+                p.display()
+            );
+        }
+        if label.trim().is_empty() {
+            self.projects.labels.remove(&p);
+        } else {
+            self.projects.labels.insert(p, label);
+        }
+        Ok(())
+    }
+
+    /// The configured label for the target at `path`, if any.
+    pub fn label_for(&self, path: &Path) -> Option<&str> {
+        self.projects
+            .labels
+            .iter()
+            .find(|(p, _)| normalize(p.to_path_buf()).is_ok_and(|p| p == path))
+            .map(|(_, label)| label.as_str())
+    }
+
     pub fn remove_path_or_root(&mut self, path: PathBuf) -> Result<()> {
         let p = normalize(path)?;
         let before_roots = self.projects.roots.len();
         self.projects.roots.retain(|r| r != &p);
         let before_paths = self.projects.paths.len();
         self.projects.paths.retain(|r| r != &p);
+        self.projects.labels.remove(&p);
         if before_roots == self.projects.roots.len() && before_paths == self.projects.paths.len() {
             anyhow::bail!("not found in config: {}", p.display());
         }
         Ok(())
     }
 
-    pub fn is_scoped_target(&self, cwd: &Path) -> bool {
+    /// The configured paths and roots, resolved once for matching many sessions against them.
+    pub fn scope(&self) -> Scope {
+        let configured = |p: &PathBuf| normalize(p.clone()).unwrap_or_else(|_| p.clone());
+        // Remote targets' sessions live on the remote machine, never in the local sessions dir.
+        let (repos, dirs) = self
+            .projects
+            .paths
+            .iter()
+            .filter(|p| !remote::is_remote(p))
+            .map(configured)
+            .partition(|p| p.join(".git").exists());
+        Scope {
+            repos,
+            dirs,
+            roots: self.projects.roots.iter().map(configured).collect(),
+        }
+    }
+}
+
+/// Which session cwds belong to a configured target. Matches `sessions_for_target` in the
+/// picker: a path that is a repo root owns every session whose git root is that repo (not
+/// nested checkouts); other paths own everything below them.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    repos: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    roots: Vec<PathBuf>,
+}
+
+impl Scope {
+    pub fn contains(&self, cwd: &Path) -> bool {
         let cwd = match normalize(cwd.to_path_buf()) {
             Ok(p) => p,
             Err(_) => cwd.to_path_buf(),
         };
-        self.projects.paths.iter().any(|p| cwd.starts_with(p))
-            || self.projects.roots.iter().any(|r| cwd.starts_with(r))
+        if self.dirs.iter().any(|p| cwd.starts_with(p)) {
+            return true;
+        }
+        if self.repos.is_empty() && self.roots.is_empty() {
+            return false;
+        }
+        let repo_root = sessions::git_root_for_path(&cwd);
+        self.repos.iter().any(|p| repo_root.as_ref() == Some(p))
+            || self
+                .roots
+                .iter()
+                .any(|r| repo_root.as_ref().unwrap_or(&cwd).starts_with(r))
     }
 }
 
+/// `args` without `long`/`short` and its value (`--profile work`, `-p work`, `--profile=work`).
+fn remove_flag(args: &mut Vec<String>, long: &str, short: &str) {
+    let mut i = 0;
+    while i < args.len() {
+        let a = &args[i];
+        if a == long || a == short {
+            let end = (i + 2).min(args.len());
+            args.drain(i..end);
+        } else if a
+            .strip_prefix(long)
+            .is_some_and(|rest| rest.starts_with('='))
+        {
+            args.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// `~` and `$VAR` / `${VAR}` expanded (unset variables are left as written).
+fn expand_path(s: &str) -> PathBuf {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let (name, len) = match after.strip_prefix('{') {
+            Some(inner) => match inner.find('}') {
+                Some(end) => (&inner[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => out.push_str(&value),
+            _ => out.push_str(&rest[i..i + 1 + len]),
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    normalize(PathBuf::from(out)).unwrap_or_default()
+}
+
 pub fn normalize(p: PathBuf) -> Result<PathBuf> {
-    let expanded = if let Some(s) = p.to_str()
-        && s.starts_with("~/")
-        && let Some(home) = dirs::home_dir()
-    {
-        home.join(s.trim_start_matches("~/"))
-    } else if p.to_string_lossy() == "~" {
-        dirs::home_dir().unwrap_or(p.clone())
-    } else {
-        p
+    let expanded = match (p.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) if rest.as_os_str().is_empty() => home,
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => p,
     };
     Ok(expanded)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoping_follows_the_session_repo_root() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let base = tmp.path();
+        let repo = base.join("repo");
+        let nested = repo.join("vendor/lib");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(nested.join(".git")).unwrap();
+        fs::create_dir_all(base.join("notes/2026")).unwrap();
+
+        let mut cfg = Config::default();
+        cfg.projects.roots.clear();
+        cfg.projects.paths = vec![repo.clone(), base.join("notes")];
+
+        let scope = cfg.scope();
+        assert!(scope.contains(&repo.join("src")));
+        assert!(!scope.contains(&nested));
+        assert!(scope.contains(&base.join("notes/2026")));
+        assert!(!scope.contains(base));
+    }
+
+    #[test]
+    fn session_paths_rewrite_by_longest_prefix() {
+        let mut cfg = Config::default();
+        cfg.sessions.path_rewrites = BTreeMap::from([
+            ("/home/me".to_string(), "/Users/me".to_string()),
+            ("/home/me/work".to_string(), "/srv/work".to_string()),
+        ]);
+        assert_eq!(
+            cfg.rewrite_session_path(Path::new("/home/me/code/api")),
+            PathBuf::from("/Users/me/code/api")
+        );
+        assert_eq!(
+            cfg.rewrite_session_path(Path::new("/home/me/work/x")),
+            PathBuf::from("/srv/work/x")
+        );
+        assert_eq!(
+            cfg.rewrite_session_path(Path::new("/home/meow")),
+            PathBuf::from("/home/meow")
+        );
+    }
+
+    #[test]
+    fn agent_follows_override_then_deepest_project_then_default() {
+        let mut cfg = Config::default();
+        cfg.launch.agent = Some("aider".into());
+        cfg.projects.agents = BTreeMap::from([
+            (PathBuf::from("/work"), "claude".to_string()),
+            (PathBuf::from("/work/legacy"), "codex".to_string()),
+        ]);
+        assert_eq!(cfg.agent_for(Path::new("/work/api")), Some("claude"));
+        assert_eq!(cfg.agent_for(Path::new("/work/legacy/app")), None);
+        assert_eq!(cfg.agent_for(Path::new("/home")), Some("aider"));
+        cfg.agent_override = Some("codex".into());
+        assert_eq!(cfg.agent_for(Path::new("/work/api")), None);
+    }
+
+    #[test]
+    fn project_overrides_apply_in_order_over_codex_settings() {
+        let mut cfg = Config::default();
+        cfg.codex.args = vec!["--search".into()];
+        cfg.launch.env = BTreeMap::from([("A".to_string(), "1".to_string())]);
+        cfg.projects.overrides = vec![
+            ProjectOverride {
+                path: "/work/*".into(),
+                profile: Some("work".into()),
+                env: BTreeMap::from([("A".to_string(), "2".to_string())]),
+                ..Default::default()
+            },
+            ProjectOverride {
+                path: "/work/api".into(),
+                args: Some(Vec::new()),
+                model: Some("o3".into()),
+                ..Default::default()
+            },
+        ];
+        assert!(cfg.with_overrides(Path::new("/home/me")).unwrap().is_none());
+
+        let web = cfg
+            .with_overrides(Path::new("/work/web/src"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(web.codex_args(), ["--search", "--profile", "work"]);
+        assert_eq!(web.launch.env["A"], "2");
+
+        let api = cfg.with_overrides(Path::new("/work/api")).unwrap().unwrap();
+        assert_eq!(api.codex_args(), ["--profile", "work", "--model", "o3"]);
+
+        // `--profile` on the command line wins, and each flag is passed once.
+        cfg.codex.args = vec!["-p".into(), "old".into(), "--model=o1".into()];
+        cfg.profile_override = Some("cli".into());
+        let web = cfg
+            .with_overrides(Path::new("/work/web/src"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(web.codex_args(), ["--model=o1", "--profile", "cli"]);
+        let api = cfg.with_overrides(Path::new("/work/api")).unwrap().unwrap();
+        assert_eq!(api.codex_args(), ["--profile", "cli", "--model", "o3"]);
+    }
+
+    #[test]
+    fn resume_style_fills_in_the_session_id() {
+        let mut codex = CodexConfig::default();
+        assert_eq!(codex.resume_args("abc").unwrap(), ["resume", "abc"]);
+        codex.resume_style = vec!["exec".into(), "resume".into(), "--id={id}".into()];
+        assert_eq!(
+            codex.resume_args("abc").unwrap(),
+            ["exec", "resume", "--id=abc"]
+        );
+        codex.resume_style = vec!["resume".into(), "--last".into()];
+        assert!(codex.resume_args("abc").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::Chars;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::ui;
+
+/// A target's `.devcontainer/devcontainer.json`, reduced to what's needed to run codex inside it.
+#[derive(Debug, Clone)]
+pub struct Devcontainer {
+    /// The project folder (`--workspace-folder` for the devcontainer CLI).
+    pub dir: PathBuf,
+    pub compose: Option<Compose>,
+}
+
+/// Compose-based devcontainers are entered with `docker compose exec` on their service.
+#[derive(Debug, Clone)]
+pub struct Compose {
+    pub files: Vec<PathBuf>,
+    pub service: String,
+    pub workspace_folder: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DevcontainerJson {
+    #[serde(default)]
+    docker_compose_file: Option<OneOrMany>,
+    #[serde(default)]
+    service: Option<String>,
+    #[serde(default)]
+    workspace_folder: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+pub fn config_path(dir: &Path) -> Option<PathBuf> {
+    let p = dir.join(".devcontainer").join("devcontainer.json");
+    p.is_file().then_some(p)
+}
+
+/// `None` when the folder has no devcontainer.
+pub fn load(dir: &Path) -> Result<Option<Devcontainer>> {
+    let Some(path) = config_path(dir) else {
+        return Ok(None);
+    };
+    let s =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let json: DevcontainerJson = serde_json::from_str(&strip_jsonc(&s))
+        .with_context(|| format!("invalid devcontainer.json: {}", path.display()))?;
+    let compose = match (json.docker_compose_file, json.service) {
+        (Some(files), Some(service)) => {
+            let files = match files {
+                OneOrMany::One(f) => vec![f],
+                OneOrMany::Many(fs) => fs,
+            };
+            let base = path.parent().unwrap_or(dir);
+            Some(Compose {
+                files: files.iter().map(|f| base.join(f)).collect(),
+                service,
+                workspace_folder: json.workspace_folder,
+            })
+        }
+        _ => None,
+    };
+    Ok(Some(Devcontainer {
+        dir: dir.to_path_buf(),
+        compose,
+    }))
+}
+
+impl Devcontainer {
+    /// Shell command that starts the container (a no-op when it's already up).
+    pub fn up_command(&self) -> String {
+        match &self.compose {
+            Some(c) => format!(
+                "docker compose {} up -d {}",
+                c.file_args(),
+                shell(&c.service)
+            ),
+            None => format!(
+                "devcontainer up --workspace-folder {}",
+                ui::shell_escape(self.dir.as_os_str())
+            ),
+        }
+    }
+
+    /// `bin args…` run inside the container with `env` set there.
+    pub fn exec_command<'a>(
+        &self,
+        bin: &str,
+        env: &BTreeMap<String, String>,
+        args: impl IntoIterator<Item = &'a OsStr>,
+    ) -> Command {
+        let mut cmd;
+        match &self.compose {
+            Some(c) => {
+                cmd = Command::new("docker");
+                cmd.arg("compose");
+                for f in c.files.iter() {
+                    cmd.arg("-f").arg(f);
+                }
+                cmd.arg("exec");
+                if let Some(w) = &c.workspace_folder {
+                    cmd.args(["-w", w]);
+                }
+                for (k, v) in env.iter() {
+                    cmd.arg("-e").arg(format!("{k}={v}"));
+                }
+                cmd.arg(&c.service);
+            }
+            None => {
+                cmd = Command::new("devcontainer");
+                cmd.arg("exec").arg("--workspace-folder").arg(&self.dir);
+                for (k, v) in env.iter() {
+                    cmd.arg("--remote-env").arg(format!("{k}={v}"));
+                }
+            }
+        }
+        cmd.current_dir(&self.dir).arg(bin).args(args);
+        cmd
+    }
+}
+
+impl Compose {
+    fn file_args(&self) -> String {
+        self.files
+            .iter()
+            .map(|f| format!("-f {}", ui::shell_escape(f.as_os_str())))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn shell(s: &str) -> String {
+    ui::shell_escape(OsStr::new(s))
+}
+
+/// devcontainer.json is JSONC: drop `//` and `/* */` comments, then trailing commas.
+fn strip_jsonc(s: &str) -> String {
+    let no_comments = outside_strings(s, |c, rest, out| match (c, rest.peek()) {
+        ('/', Some('/')) => {
+            for n in rest.by_ref() {
+                if n == '\n' {
+                    out.push('\n');
+                    break;
+                }
+            }
+        }
+        ('/', Some('*')) => {
+            rest.next();
+            let mut prev = ' ';
+            for n in rest.by_ref() {
+                if prev == '*' && n == '/' {
+                    break;
+                }
+                prev = n;
+            }
+        }
+        _ => out.push(c),
+    });
+    outside_strings(&no_comments, |c, rest, out| {
+        let next = rest.clone().find(|n| !n.is_whitespace());
+        if c != ',' || !matches!(next, Some('}' | ']')) {
+            out.push(c);
+        }
+    })
+}
+
+/// Copy `s`, letting `f` handle every char outside JSON string literals.
+fn outside_strings(s: &str, mut f: impl FnMut(char, &mut Peekable<Chars>, &mut String)) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                if let Some(n) = chars.next() {
+                    out.push(n);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            out.push(c);
+        } else {
+            f(c, &mut chars, &mut out);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let s = r#"{
+            // compose setup
+            "dockerComposeFile": ["../compose.yml",], /* app only */
+            "service": "app",
+            "workspaceFolder": "/workspaces/a//b", // where the repo is mounted
+        }"#;
+        let json: DevcontainerJson = serde_json::from_str(&strip_jsonc(s)).unwrap();
+        assert_eq!(json.service.as_deref(), Some("app"));
+        assert_eq!(json.workspace_folder.as_deref(), Some("/workspaces/a//b"));
+        assert!(matches!(json.docker_compose_file, Some(OneOrMany::Many(f)) if f.len() == 1));
+    }
+}
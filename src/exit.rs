@@ -0,0 +1,87 @@
+use std::fmt;
+use std::process::ExitCode;
+
+use inquire::InquireError;
+
+/// Outcomes that aren't plain errors and get their own exit code, so wrappers can tell
+/// "nothing matched" and "the user backed out" from a broken setup.
+#[derive(Debug)]
+pub enum Exit {
+    /// Nothing matched the query, or there was nothing to list (exit 2).
+    NoMatch(String),
+    /// The user quit the picker or cancelled a prompt (exit 130).
+    Cancelled,
+}
+
+impl fmt::Display for Exit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exit::NoMatch(msg) => write!(f, "{msg}"),
+            Exit::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for Exit {}
+
+/// How `main` reports a failure on stderr.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// `Error: <message>` (nothing when cancelled)
+    #[default]
+    Text,
+    /// `{"kind": "error"|"no_match"|"cancelled", "code": N, "message": "…"}`
+    Json,
+}
+
+pub const ERROR: u8 = 1;
+pub const NO_MATCH: u8 = 2;
+pub const CANCELLED: u8 = 130;
+
+/// `(kind, exit code)` of a failure; Esc/Ctrl-C in an inline prompt counts as cancelled.
+fn classify(e: &anyhow::Error) -> (&'static str, u8) {
+    for cause in e.chain() {
+        if let Some(exit) = cause.downcast_ref::<Exit>() {
+            return match exit {
+                Exit::NoMatch(_) => ("no_match", NO_MATCH),
+                Exit::Cancelled => ("cancelled", CANCELLED),
+            };
+        }
+        if matches!(
+            cause.downcast_ref::<InquireError>(),
+            Some(InquireError::OperationCanceled | InquireError::OperationInterrupted)
+        ) {
+            return ("cancelled", CANCELLED);
+        }
+    }
+    ("error", ERROR)
+}
+
+/// Print `e` in `format` and return the exit code for it.
+pub fn report(e: &anyhow::Error, format: ErrorFormat) -> ExitCode {
+    let (kind, code) = classify(e);
+    match format {
+        ErrorFormat::Text if code == CANCELLED => {}
+        ErrorFormat::Text => eprintln!("Error: {e:?}"),
+        ErrorFormat::Json => eprintln!(
+            "{}",
+            serde_json::json!({ "kind": kind, "code": code, "message": format!("{e:#}") })
+        ),
+    }
+    ExitCode::from(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_wrapped_outcomes() {
+        let no_match = anyhow::Error::new(Exit::NoMatch("no project matches for: x".into()))
+            .context("quick launch");
+        assert_eq!(classify(&no_match), ("no_match", NO_MATCH));
+        let esc = anyhow::Error::new(InquireError::OperationCanceled);
+        assert_eq!(classify(&esc), ("cancelled", CANCELLED));
+        assert_eq!(classify(&anyhow::anyhow!("bad config")), ("error", ERROR));
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::sessions::{self, SessionItem};
+
+/// Write each session's transcript to `<out_dir>/<date>-<title>.md`; returns the files written.
+/// Existing files are never overwritten (a short id suffix disambiguates instead).
+pub fn export_markdown(items: &[SessionItem], out_dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+    let mut written = Vec::new();
+    for s in items {
+        // Sessions only known from history.jsonl have no transcript.
+        let Some(rollout) = s.path.as_deref() else {
+            continue;
+        };
+        let content = sessions::read_rollout(rollout)?;
+        let mut path = out_dir.join(format!("{}.md", file_stem(s)));
+        if path.exists() {
+            let id_short = s.id.chars().take(8).collect::<String>();
+            path = out_dir.join(format!("{}-{id_short}.md", file_stem(s)));
+        }
+        if path.exists() {
+            continue;
+        }
+        fs::write(&path, render(s, &content))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// `2026-02-03-fix-the-login-bug`: the session date plus a slug of its summary.
+fn file_stem(s: &SessionItem) -> String {
+    let date = s
+        .created_at
+        .as_deref()
+        .and_then(|t| t.get(..10))
+        .unwrap_or("undated");
+    let slug = slug(s.summary.as_deref().unwrap_or(&s.id));
+    if slug.is_empty() {
+        date.to_string()
+    } else {
+        format!("{date}-{slug}")
+    }
+}
+
+fn slug(title: &str) -> String {
+    let mut out = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+        if out.chars().count() >= 60 {
+            break;
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
+fn render(s: &SessionItem, content: &sessions::RolloutContent) -> String {
+    let title = s.summary.as_deref().unwrap_or("Codex session");
+    let mut out = format!("# {title}\n\n");
+    out.push_str(&format!("- Session: `{}`\n", s.id));
+    if let Some(created) = s.created_at.as_deref() {
+        out.push_str(&format!("- Started: {created}\n"));
+    }
+    out.push_str(&format!("- Directory: `{}`\n", s.cwd.display()));
+    if let Some(model) = s.model.as_deref() {
+        out.push_str(&format!("- Model: {model}\n"));
+    }
+    if !content.touched_files.is_empty() {
+        let files = content
+            .touched_files
+            .keys()
+            .map(|f| format!("`{f}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push_str(&format!("- Edited: {files}\n"));
+    }
+    for m in content.messages.iter() {
+        if m.role == "user" && sessions::looks_like_boilerplate(&m.text) {
+            continue;
+        }
+        let who = match m.role.as_str() {
+            "user" => "User",
+            "assistant" => "Assistant",
+            _ => continue,
+        };
+        out.push_str(&format!("\n## {who}\n\n{}\n", m.text.trim()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_summaries_for_file_names() {
+        assert_eq!(slug("Fix the `login` bug!"), "fix-the-login-bug");
+        assert_eq!(slug("  ---  "), "");
+        assert_eq!(slug(&"x".repeat(100)).len(), 60);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::Stdio;
+use std::thread;
+
+use anyhow::{Context, Result};
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::batch::BatchAction;
+use crate::config::ProjectSort;
+use crate::launch;
+use crate::plain_ui;
+use crate::projects::ProjectTarget;
+use crate::sessions::SessionItem;
+use crate::timefmt;
+use crate::tui::{self, PickerContext, ProjectPick, SessionPages};
+use crate::ui;
+
+const NEW_SESSION: &str = "+ New session";
+
+/// The picker through an external fuzzy finder: projects, then the picked project's sessions,
+/// are piped into `command` and the lines it prints back are what was picked. Several projects
+/// open in tmux windows; several sessions get a batch action.
+pub fn pick_project(
+    command: &str,
+    targets: &[ProjectTarget],
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    sort: ProjectSort,
+) -> Result<ProjectPick> {
+    if let Some(status) = &ctx.status {
+        ui::print_info(status);
+    }
+    let matcher = SkimMatcherV2::default();
+    let shown = tui::shown_targets(targets, &matcher, "", &ctx.state.pins, sort);
+    loop {
+        let lines: Vec<String> = shown
+            .iter()
+            .map(|i| project_line(&targets[*i], ctx))
+            .collect();
+        let Some(picked) = choose(command, &lines)? else {
+            return Ok(ProjectPick::Quit);
+        };
+        if let [one] = picked[..] {
+            if let Some(pick) = pick_session(command, &targets[shown[one]], pages, ctx)? {
+                return Ok(pick);
+            }
+            continue;
+        }
+        let picked = picked.iter().map(|i| targets[shown[*i]].clone());
+        return Ok(ProjectPick::LaunchAll(picked.collect()));
+    }
+}
+
+/// "New session" and every session of `target`; `None` goes back to the projects.
+fn pick_session(
+    command: &str,
+    target: &ProjectTarget,
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+) -> Result<Option<ProjectPick>> {
+    // The finder does the filtering, so it gets the whole history at once.
+    pages.load_all()?;
+    let sessions = tui::load_project_sessions(target, pages, usize::MAX)?;
+    let lines: Vec<String> = std::iter::once(NEW_SESSION.to_string())
+        .chain(sessions.iter().map(|s| session_line(s, ctx)))
+        .collect();
+    let Some(picked) = choose(command, &lines)? else {
+        return Ok(None);
+    };
+    if picked.contains(&0) {
+        return Ok(Some(ProjectPick::New(target.clone())));
+    }
+    let picked: Vec<SessionItem> = picked.iter().map(|i| sessions[i - 1].clone()).collect();
+    if let [one] = &picked[..] {
+        return Ok(Some(ProjectPick::Resume(one.clone())));
+    }
+    let actions = [
+        ("Delete", BatchAction::Delete),
+        ("Archive", BatchAction::Archive),
+        ("Tag", BatchAction::Tag),
+        ("Export", BatchAction::Export),
+        ("Signal", BatchAction::Signal),
+    ];
+    let lines: Vec<String> = actions
+        .iter()
+        .map(|(name, _)| format!("{name} {} sessions", picked.len()))
+        .collect();
+    Ok(choose(command, &lines)?.map(|chosen| ProjectPick::Batch {
+        action: actions[chosen[0]].1,
+        sessions: picked,
+    }))
+}
+
+/// `label⇥path⇥last session`, so `--delimiter '\t'` makes `{2}` the folder.
+fn project_line(t: &ProjectTarget, ctx: &PickerContext) -> String {
+    let when = t
+        .last_session_at
+        .as_deref()
+        .and_then(timefmt::parse_rfc3339)
+        .map(|dt| timefmt::format_when(dt, ctx.display.dates));
+    let summary = t.last_session_summary.as_deref().map(plain_ui::one_line);
+    let last = [when, summary].into_iter().flatten().collect::<Vec<_>>();
+    format!("{}\t{}\t{}", t.label, t.path.display(), last.join(" "))
+}
+
+/// `time⇥cwd⇥summary⇥id`, so `{2}` is the folder here too and `{4}` the session id.
+fn session_line(s: &SessionItem, ctx: &PickerContext) -> String {
+    let when = s
+        .created_at
+        .as_deref()
+        .and_then(timefmt::parse_rfc3339)
+        .map(|dt| timefmt::format_when(dt, ctx.display.dates))
+        .unwrap_or_else(|| "-".to_string());
+    let summary = s.summary.as_deref().map(plain_ui::one_line);
+    format!(
+        "{} {when}\t{}\t{}\t{}",
+        s.outcome.glyph(),
+        s.cwd.display(),
+        summary.unwrap_or_default(),
+        s.id
+    )
+}
+
+/// Pipe `lines` into the finder and return the indices of the lines it printed back; `None`
+/// when nothing was picked (esc, or no match).
+fn choose(command: &str, lines: &[String]) -> Result<Option<Vec<usize>>> {
+    let mut child = launch::shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {command} (display.fzf_command)"))?;
+    let mut stdin = child.stdin.take().context("finder has no stdin")?;
+    let input = lines.join("\n");
+    // Fed from a thread: a finder can exit (or start printing) before it has read everything.
+    let feeder = thread::spawn(move || {
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let out = child
+        .wait_with_output()
+        .with_context(|| format!("failed to run {command}"))?;
+    let _ = feeder.join();
+    // fzf and sk exit with 1 when nothing matched and 130 on esc; 2 is an error.
+    match out.status.code() {
+        Some(0) => {}
+        Some(2) => anyhow::bail!("{command} failed ({})", out.status),
+        _ => return Ok(None),
+    }
+    let mut index = HashMap::new();
+    for (i, line) in lines.iter().enumerate() {
+        index.entry(line.as_str()).or_insert(i);
+    }
+    let picked: Vec<usize> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|l| index.get(l).copied())
+        .collect();
+    Ok((!picked.is_empty()).then_some(picked))
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::Deserialize;
+
+/// How many of the user's repos `gh repo list` is asked for.
+const REPO_LIMIT: &str = "1000";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repo {
+    pub name: String,
+    pub name_with_owner: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub url: String,
+}
+
+/// The signed-in user's repos, via the `gh` CLI (which handles authentication).
+pub fn list_repos() -> Result<Vec<Repo>> {
+    let out = Command::new("gh")
+        .args([
+            "repo",
+            "list",
+            "--limit",
+            REPO_LIMIT,
+            "--json",
+            "name,nameWithOwner,description,url",
+        ])
+        .output()
+        .context("failed to run `gh` (install GitHub CLI and run `gh auth login`)")?;
+    if !out.status.success() {
+        anyhow::bail!(
+            "gh repo list failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    serde_json::from_slice(&out.stdout).context("unexpected output from gh repo list")
+}
+
+/// Repos whose name or description fuzzy-match `query`, best first.
+pub fn matching(repos: Vec<Repo>, query: &str) -> Vec<Repo> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut scored = repos
+        .into_iter()
+        .filter_map(|r| {
+            let hay = format!(
+                "{} {}",
+                r.name_with_owner,
+                r.description.as_deref().unwrap_or("")
+            );
+            matcher.fuzzy_match(&hay, query).map(|score| (score, r))
+        })
+        .collect::<Vec<_>>();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, r)| r).collect()
+}
+
+/// `gh repo clone <owner/name> <dest>`.
+pub fn clone_command(repo: &Repo, dest: &Path) -> Command {
+    let mut cmd = Command::new("gh");
+    cmd.args(["repo", "clone", &repo.name_with_owner]).arg(dest);
+    cmd
+}
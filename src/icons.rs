@@ -0,0 +1,55 @@
+use std::path::Path;
+
+// Nerd Font glyphs for list rows (`display.icons`); each is one cell wide so columns stay aligned.
+
+/// Marker files at a project root and the language (glyph and name) they imply, checked in order.
+const LANGUAGES: &[(&str, char, &str)] = &[
+    ("Cargo.toml", '\u{e7a8}', "Rust"),
+    ("tsconfig.json", '\u{e628}', "TypeScript"),
+    ("package.json", '\u{e74e}', "JavaScript"),
+    ("go.mod", '\u{e626}', "Go"),
+    ("pyproject.toml", '\u{e73c}', "Python"),
+    ("requirements.txt", '\u{e73c}', "Python"),
+    ("setup.py", '\u{e73c}', "Python"),
+    ("Gemfile", '\u{e739}', "Ruby"),
+    ("pom.xml", '\u{e738}', "Java"),
+    ("build.gradle", '\u{e738}', "Java"),
+    ("build.gradle.kts", '\u{e738}', "Kotlin"),
+    ("Package.swift", '\u{e755}', "Swift"),
+    ("mix.exs", '\u{e62d}', "Elixir"),
+    ("composer.json", '\u{e73d}', "PHP"),
+];
+
+const FOLDER: char = '\u{f07b}';
+const GIT: char = '\u{e702}';
+const TERMINAL: char = '\u{f120}';
+const EDITOR: char = '\u{e70c}';
+const EXEC: char = '\u{f085}';
+
+/// Language glyph (a folder when unknown) followed by a git glyph for repos (else a space).
+pub fn project(dir: &Path) -> String {
+    let lang = detect(dir).map(|(icon, _)| icon).unwrap_or(FOLDER);
+    let vcs = if dir.join(".git").exists() { GIT } else { ' ' };
+    format!("{lang}{vcs}")
+}
+
+/// Name of the project's language, going by the marker files at its root.
+pub fn language_name(dir: &Path) -> Option<&'static str> {
+    detect(dir).map(|(_, name)| name)
+}
+
+fn detect(dir: &Path) -> Option<(char, &'static str)> {
+    LANGUAGES
+        .iter()
+        .find(|(marker, _, _)| dir.join(marker).is_file())
+        .map(|(_, icon, name)| (*icon, *name))
+}
+
+/// Glyph for where a session was started (`source` in its metadata).
+pub fn session_source(source: Option<&str>) -> char {
+    match source {
+        Some("vscode" | "ide") => EDITOR,
+        Some("exec" | "mcp") => EXEC,
+        _ => TERMINAL,
+    }
+}
@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+
+use crate::config;
+
+/// Name of the per-root ignore file (gitignore-style globs, one per line).
+pub const IGNORE_FILE_NAME: &str = ".codexlaunchignore";
+
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    matcher: GlobMatcher,
+    negated: bool,
+}
+
+impl IgnoreRules {
+    /// Load `<dir>/.codexlaunchignore` if present; a missing or unreadable file means "ignore nothing".
+    pub fn load(dir: &Path) -> Self {
+        match fs::read_to_string(dir.join(IGNORE_FILE_NAME)) {
+            Ok(s) => Self::parse(&s),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        let mut rules = Vec::new();
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, pat) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            // Only direct children of a root are considered, so anchoring (`/foo`) and
+            // directory-only markers (`foo/`) don't change the meaning.
+            let pat = pat.trim_start_matches('/').trim_end_matches('/');
+            if pat.is_empty() {
+                continue;
+            }
+            let Ok(glob) = GlobBuilder::new(pat).literal_separator(true).build() else {
+                continue;
+            };
+            rules.push(Rule {
+                matcher: glob.compile_matcher(),
+                negated,
+            });
+        }
+        Self { rules }
+    }
+
+    /// Whether `rel` (a path relative to the directory holding the rules) is ignored.
+    /// Like gitignore, the last matching rule wins and `!pattern` re-includes.
+    pub fn is_ignored(&self, rel: &Path) -> bool {
+        let mut ignored = false;
+        for rule in self.rules.iter() {
+            if rule.matcher.is_match(rel) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// `projects.exclude`: folders kept out of the picker however they were found. A glob is
+/// matched against the whole path when it contains a `/` (`~` expands) and against the folder
+/// name otherwise; `re:` starts a regex, searched for in the whole path.
+#[derive(Debug, Clone, Default)]
+pub struct Excludes {
+    patterns: Vec<Exclude>,
+}
+
+#[derive(Debug, Clone)]
+enum Exclude {
+    Name(GlobMatcher),
+    Path(GlobMatcher),
+    Regex(Regex),
+}
+
+impl Excludes {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut out = Vec::new();
+        for pat in patterns {
+            let invalid = || format!("invalid projects.exclude pattern: {pat}");
+            if let Some(re) = pat.strip_prefix("re:") {
+                out.push(Exclude::Regex(Regex::new(re).with_context(invalid)?));
+                continue;
+            }
+            let compile = |glob: &str| {
+                GlobBuilder::new(glob.trim_end_matches('/'))
+                    .literal_separator(true)
+                    .build()
+                    .map(|g| g.compile_matcher())
+                    .with_context(invalid)
+            };
+            if pat.contains('/') {
+                let path = config::normalize(PathBuf::from(pat))?;
+                out.push(Exclude::Path(compile(&path.to_string_lossy())?));
+            } else {
+                out.push(Exclude::Name(compile(pat)?));
+            }
+        }
+        Ok(Self { patterns: out })
+    }
+
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.patterns.iter().any(|p| match p {
+            Exclude::Name(m) => path.file_name().is_some_and(|n| m.is_match(n)),
+            Exclude::Path(m) => m.is_match(path),
+            Exclude::Regex(re) => re.is_match(&path.to_string_lossy()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let rules = IgnoreRules::parse("# comment\n\ntmp-*\n/archive/\n!tmp-keep\n");
+        assert!(rules.is_ignored(Path::new("tmp-scratch")));
+        assert!(rules.is_ignored(Path::new("archive")));
+        assert!(!rules.is_ignored(Path::new("tmp-keep")));
+        assert!(!rules.is_ignored(Path::new("api")));
+    }
+
+    #[test]
+    fn excludes_by_name_path_or_regex() {
+        let patterns = ["tmp-*", "/src/vendor/*", "re:/archive(d)?/"].map(String::from);
+        let excludes = Excludes::new(&patterns).unwrap();
+        assert!(excludes.is_excluded(Path::new("/code/tmp-scratch")));
+        assert!(excludes.is_excluded(Path::new("/src/vendor/lib")));
+        assert!(!excludes.is_excluded(Path::new("/src/vendor/lib/nested")));
+        assert!(excludes.is_excluded(Path::new("/code/archived/old-api")));
+        assert!(!excludes.is_excluded(Path::new("/code/api")));
+        assert!(Excludes::new(&["re:(".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use std::env;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use inquire::Confirm;
+
+use crate::ui;
+
+const RELEASES_URL: &str = "https://github.com/openai/codex/releases/latest";
+
+struct InstallMethod {
+    label: &'static str,
+    program: &'static str,
+    args: &'static [&'static str],
+}
+
+const NPM: InstallMethod = InstallMethod {
+    label: "npm",
+    program: "npm",
+    args: &["install", "-g", "@openai/codex"],
+};
+
+const BREW: InstallMethod = InstallMethod {
+    label: "Homebrew",
+    program: "brew",
+    args: &["install", "--cask", "codex"],
+};
+
+/// Called when spawning the codex binary failed with "not found". Explains how to install it
+/// and, on a TTY, offers to run the detected installer. Returns `true` if an install succeeded.
+pub fn offer_install(bin: &Path) -> Result<bool> {
+    if bin.components().count() > 1 {
+        ui::print_warn(&format!(
+            "`{}` does not exist; check `codex.bin` in your config",
+            bin.display()
+        ));
+        return Ok(false);
+    }
+
+    ui::print_warn(&format!("`{}` was not found on PATH", bin.display()));
+    let Some(method) = detect_method() else {
+        ui::print_info(&format!(
+            "Install Codex with `npm install -g @openai/codex`, or download a binary from {RELEASES_URL}"
+        ));
+        return Ok(false);
+    };
+    let line = format!("{} {}", method.program, method.args.join(" "));
+    if !std::io::stdin().is_terminal() {
+        ui::print_info(&format!("Install Codex via {} with: {line}", method.label));
+        return Ok(false);
+    }
+
+    let go = Confirm::new(&format!("Install Codex via {} (`{line}`)?", method.label))
+        .with_default(true)
+        .prompt()?;
+    if !go {
+        ui::print_info(&format!(
+            "Alternatively, download a binary from {RELEASES_URL}"
+        ));
+        return Ok(false);
+    }
+    let status = Command::new(method.program)
+        .args(method.args)
+        .status()
+        .with_context(|| format!("failed to run {line}"))?;
+    if !status.success() {
+        anyhow::bail!("`{line}` exited with status: {status}");
+    }
+    Ok(true)
+}
+
+fn detect_method() -> Option<InstallMethod> {
+    if cfg!(target_os = "macos") && find_on_path("brew").is_some() {
+        return Some(BREW);
+    }
+    if find_on_path("npm").is_some() {
+        return Some(NPM);
+    }
+    None
+}
+
+pub fn find_on_path(name: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths).find_map(|dir| {
+        let candidate = dir.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if cfg!(target_os = "windows") {
+            let exe = dir.join(format!("{name}.exe"));
+            if exe.is_file() {
+                return Some(exe);
+            }
+        }
+        None
+    })
+}
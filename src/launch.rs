@@ -0,0 +1,467 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::RunOpts;
+use crate::config::{Config, LaunchMode, ZellijPlace};
+use crate::install;
+use crate::pathfmt;
+use crate::ui;
+
+/// Everything that happens when codex is launched: the command itself plus the environment,
+/// hooks and launch mode layered on top of it from config.
+pub struct LaunchPlan {
+    pub cmd: Command,
+    pub mode: LaunchMode,
+    pub hooks: Vec<String>,
+    /// Dotenv file read into codex's environment right before it starts.
+    pub env_file: Option<PathBuf>,
+    /// Terminal title shown while codex runs (`launch.title`).
+    pub title: Option<String>,
+    /// The target's label; names its tmux session in `tmux` mode.
+    pub label: Option<String>,
+    /// In `tmux` mode, attach to the project's running session as is instead of opening a
+    /// new window in it (off for resumes, which must run the picked session).
+    pub reuse_session: bool,
+    /// Where `zellij` mode opens codex.
+    pub zellij: ZellijPlace,
+    /// `cmd` runs the codex binary itself, not ssh, a container, direnv, a shell or another
+    /// agent; only then is a missing program worth offering a Codex install for.
+    pub runs_codex: bool,
+}
+
+impl LaunchPlan {
+    pub fn new(cfg: &Config, mut cmd: Command) -> Self {
+        cmd.envs(cfg.launch.env.iter());
+        let shell = cfg.launch.shell.as_deref().filter(|s| !s.trim().is_empty());
+        let runs_codex = shell.is_none() && cmd.get_program() == cfg.codex.bin.as_str();
+        if let Some(shell) = shell {
+            // `zsh -ic 'exec codex …'`: the shell's rc files set up PATH, then codex replaces it.
+            let mut words = shell.split_whitespace();
+            let script = std::iter::once(cmd.get_program())
+                .chain(cmd.get_args())
+                .map(ui::shell_escape)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let mut wrapped = rewrap(&cmd, words.next().unwrap_or("sh"));
+            wrapped.args(words).arg(format!("exec {script}"));
+            cmd = wrapped;
+        }
+        Self {
+            cmd,
+            mode: cfg.launch.mode,
+            hooks: cfg.launch.hooks.clone(),
+            env_file: None,
+            title: None,
+            label: None,
+            reuse_session: true,
+            zellij: cfg.launch.zellij,
+            runs_codex,
+        }
+    }
+
+    /// Fill in the `launch.title` template (`{label}`, `{path}`) for a launch in `path`.
+    pub fn titled(mut self, cfg: &Config, label: &str, path: &Path) -> Self {
+        self.label = Some(label.to_string());
+        let template = cfg.launch.title.trim();
+        if !template.is_empty() {
+            self.title = Some(
+                template
+                    .replace("{label}", label)
+                    .replace("{path}", &pathfmt::compact_path(path, 48)),
+            );
+        }
+        self
+    }
+
+    /// Apply `launch.direnv` / `launch.env_file` (and their `projects.env` overrides) for a
+    /// launch on this machine in `dir`.
+    pub fn with_project_env(mut self, cfg: &Config, dir: &Path) -> Self {
+        let (direnv, env_file) = cfg.project_env(dir);
+        if direnv {
+            let mut wrapped = rewrap(&self.cmd, "direnv");
+            wrapped
+                .arg("exec")
+                .arg(dir)
+                .arg(self.cmd.get_program())
+                .args(self.cmd.get_args());
+            self.cmd = wrapped;
+            self.runs_codex = false;
+        }
+        self.env_file = env_file.map(|f| dir.join(f));
+        self
+    }
+
+    pub fn execute(mut self, run: RunOpts) -> Result<()> {
+        if let Some(format) = run.print_cmd {
+            println!("{}", ui::render_command(&self.cmd, &self.hooks, format));
+            return Ok(());
+        }
+        if run.dry_run {
+            ui::print_info(&format!("DRY RUN: {}", ui::format_command(&self.cmd)));
+            for line in self.describe() {
+                ui::print_info(&format!("  {line}"));
+            }
+            for cmd in self.backend_commands().unwrap_or_default() {
+                ui::print_info(&format!(
+                    "  {}: {}",
+                    mode_name(self.mode),
+                    ui::format_command(&cmd)
+                ));
+            }
+            return Ok(());
+        }
+        if self.mode == LaunchMode::Zellij && std::env::var_os("ZELLIJ").is_none() {
+            anyhow::bail!(
+                "launch.mode = \"zellij\" needs codex-launch to run inside a Zellij session"
+            );
+        }
+        if let Some(cmds) = self.backend_commands() {
+            for mut cmd in cmds {
+                let status = cmd
+                    .status()
+                    .with_context(|| format!("failed to run {}", ui::format_command(&cmd)))?;
+                if !status.success() {
+                    anyhow::bail!("command exited with status: {status}");
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(path) = self.env_file.as_ref().filter(|p| p.is_file()) {
+            let s = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            self.cmd.envs(parse_env_file(&s));
+        }
+
+        for hook in self.hooks.iter() {
+            let mut hook_cmd = shell_command(hook);
+            if let Some(dir) = self.cmd.get_current_dir() {
+                hook_cmd.current_dir(dir);
+            }
+            ui::print_info(&format!("hook: {hook}"));
+            let status = hook_cmd
+                .status()
+                .with_context(|| format!("failed to run hook: {hook}"))?;
+            if !status.success() {
+                anyhow::bail!("hook failed ({status}): {hook}");
+            }
+        }
+
+        match self.mode {
+            LaunchMode::Tmux | LaunchMode::Kitty | LaunchMode::Wezterm | LaunchMode::Zellij => {
+                unreachable!("launches through another program return above")
+            }
+            LaunchMode::Exec => {
+                let _title = TerminalTitle::set(self.title.as_deref());
+                let status = match self.cmd.status() {
+                    Err(e) if e.kind() == io::ErrorKind::NotFound && self.runs_codex => {
+                        let program = PathBuf::from(self.cmd.get_program());
+                        if !install::offer_install(&program)? {
+                            anyhow::bail!("failed to run {}: {e}", ui::format_command(&self.cmd));
+                        }
+                        self.cmd.status()
+                    }
+                    other => other,
+                }
+                .with_context(|| format!("failed to run {}", ui::format_command(&self.cmd)))?;
+                if !status.success() {
+                    anyhow::bail!("command exited with status: {status}");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// What starts codex when it isn't run directly (every mode but `exec`): the shell line is
+    /// handed to tmux or the terminal's remote control.
+    fn backend_commands(&self) -> Option<Vec<Command>> {
+        let dir = self.cmd.get_current_dir();
+        let label = self.label.as_deref().unwrap_or("codex");
+        match self.mode {
+            LaunchMode::Exec => None,
+            LaunchMode::Tmux => Some(self.tmux_commands()),
+            LaunchMode::Kitty => {
+                let mut cmd = Command::new("kitty");
+                cmd.args(["@", "launch", "--type=tab", "--tab-title", label]);
+                if let Some(dir) = dir {
+                    cmd.arg("--cwd").arg(dir);
+                }
+                cmd.args(["sh", "-c"]).arg(self.shell_line());
+                Some(vec![cmd])
+            }
+            LaunchMode::Wezterm => {
+                let mut cmd = Command::new("wezterm");
+                cmd.args(["cli", "spawn"]);
+                if let Some(dir) = dir {
+                    cmd.arg("--cwd").arg(dir);
+                }
+                cmd.args(["--", "sh", "-c"]).arg(self.shell_line());
+                Some(vec![cmd])
+            }
+            LaunchMode::Zellij => Some(self.zellij_commands()),
+        }
+    }
+
+    /// `zellij` mode: a pane running codex that closes when it exits. For a tab, the pane
+    /// takes the place of the new tab's shell (`--in-place`), which is back once codex exits.
+    fn zellij_commands(&self) -> Vec<Command> {
+        let dir = self.cmd.get_current_dir();
+        let label = self.label.as_deref().unwrap_or("codex");
+        let mut cmds = Vec::new();
+        let mut pane = Command::new("zellij");
+        pane.args(["action", "new-pane", "--close-on-exit", "--name", label]);
+        if self.zellij == ZellijPlace::Tab {
+            let mut tab = Command::new("zellij");
+            tab.args(["action", "new-tab", "--name", label]);
+            if let Some(dir) = dir {
+                tab.arg("--cwd").arg(dir);
+            }
+            cmds.push(tab);
+            pane.arg("--in-place");
+        }
+        if let Some(dir) = dir {
+            pane.arg("--cwd").arg(dir);
+        }
+        pane.args(["--", "sh", "-c"]).arg(self.shell_line());
+        cmds.push(pane);
+        cmds
+    }
+
+    /// `tmux` mode: attach to (or switch to) the project's session, creating it first with
+    /// codex running in it when there is none.
+    fn tmux_commands(&self) -> Vec<Command> {
+        let name = tmux_session_name(self.label.as_deref().unwrap_or("codex"));
+        let exact = format!("={name}");
+        let inside = std::env::var_os("TMUX").is_some();
+        let exists = Command::new("tmux")
+            .args(["has-session", "-t", &exact])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success());
+        let mut cmds = Vec::new();
+        if !exists || !self.reuse_session {
+            let mut create = Command::new("tmux");
+            if exists {
+                create.args(["new-window", "-t", &exact]);
+            } else {
+                create.args(["new-session", "-d", "-s", &name]);
+            }
+            if let Some(dir) = self.cmd.get_current_dir() {
+                create.arg("-c").arg(dir);
+            }
+            create.arg(self.shell_line());
+            cmds.push(create);
+        }
+        let mut attach = Command::new("tmux");
+        attach.args([
+            if inside {
+                "switch-client"
+            } else {
+                "attach-session"
+            },
+            "-t",
+            &exact,
+        ]);
+        cmds.push(attach);
+        cmds
+    }
+
+    /// The launch as one shell command line (`cd … && hooks && codex …`) for running it
+    /// elsewhere, e.g. in a tmux window; the env file is sourced by the shell.
+    pub fn shell_line(&self) -> String {
+        let line = ui::render_command(&self.cmd, &self.hooks, ui::CommandFormat::Shell);
+        match &self.env_file {
+            Some(path) if path.is_file() => format!(
+                "set -a && . {} && set +a && {line}",
+                ui::shell_escape(path.as_os_str())
+            ),
+            _ => line,
+        }
+    }
+
+    /// Dry-run detail lines: where codex would run, how, and with what around it.
+    fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(dir) = self.cmd.get_current_dir() {
+            lines.push(format!("cwd:  {}", dir.display()));
+        }
+        lines.push(format!("mode: {}", mode_name(self.mode)));
+        for (k, v) in self.cmd.get_envs() {
+            match v {
+                Some(v) => lines.push(format!(
+                    "env:  {}={}",
+                    k.to_string_lossy(),
+                    v.to_string_lossy()
+                )),
+                None => lines.push(format!("env:  unset {}", k.to_string_lossy())),
+            }
+        }
+        if let Some(title) = &self.title {
+            lines.push(format!("title: {title}"));
+        }
+        if let Some(path) = &self.env_file {
+            // Keys only: env files tend to hold secrets.
+            let line = match fs::read_to_string(path) {
+                Ok(s) => {
+                    let keys: Vec<String> =
+                        parse_env_file(&s).into_iter().map(|(k, _)| k).collect();
+                    format!("env:  from {}: {}", path.display(), keys.join(", "))
+                }
+                Err(_) => format!("env:  {} (not found, skipped)", path.display()),
+            };
+            lines.push(line);
+        }
+        for hook in self.hooks.iter() {
+            lines.push(format!("hook: {hook}"));
+        }
+        lines
+    }
+}
+
+/// Sets the terminal title (saving the current one on the xterm title stack) and restores it
+/// when dropped. Does nothing unless stdout is a terminal.
+struct TerminalTitle(bool);
+
+impl TerminalTitle {
+    fn set(title: Option<&str>) -> Self {
+        let Some(title) = title.filter(|_| io::stdout().is_terminal()) else {
+            return Self(false);
+        };
+        let title: String = title.chars().filter(|c| !c.is_control()).collect();
+        let mut out = io::stdout();
+        let _ = write!(out, "\x1b[22;0t\x1b]2;{title}\x07");
+        let _ = out.flush();
+        Self(true)
+    }
+}
+
+impl Drop for TerminalTitle {
+    fn drop(&mut self) {
+        if self.0 {
+            let mut out = io::stdout();
+            let _ = write!(out, "\x1b[23;0t");
+            let _ = out.flush();
+        }
+    }
+}
+
+/// tmux session names can't contain `.` or `:`; keep them to plain word characters.
+fn tmux_session_name(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// A new command for `program` with `inner`'s working directory and environment changes.
+fn rewrap(inner: &Command, program: &str) -> Command {
+    let mut cmd = Command::new(program);
+    if let Some(cwd) = inner.get_current_dir() {
+        cmd.current_dir(cwd);
+    }
+    for (k, v) in inner.get_envs() {
+        match v {
+            Some(v) => cmd.env(k, v),
+            None => cmd.env_remove(k),
+        };
+    }
+    cmd
+}
+
+fn mode_name(mode: LaunchMode) -> &'static str {
+    match mode {
+        LaunchMode::Exec => "exec",
+        LaunchMode::Tmux => "tmux",
+        LaunchMode::Kitty => "kitty",
+        LaunchMode::Wezterm => "wezterm",
+        LaunchMode::Zellij => "zellij",
+    }
+}
+
+/// `<runtime> exec -it -w <dir> [-e K=V…] <container> <bin> <args…>`.
+pub fn container_exec<'a>(
+    cfg: &Config,
+    container: &str,
+    dir: &Path,
+    args: impl IntoIterator<Item = &'a OsStr>,
+) -> Command {
+    let mut cmd = Command::new(&cfg.launch.container_runtime);
+    cmd.args(["exec", "-it", "-w"]).arg(dir);
+    for (k, v) in cfg.launch.env.iter() {
+        cmd.arg("-e").arg(format!("{k}={v}"));
+    }
+    // Hooks still run on the host, in the same folder.
+    cmd.current_dir(dir);
+    cmd.arg(container).arg(&cfg.codex.remote_bin).args(args);
+    cmd
+}
+
+/// `KEY=value` lines of a dotenv file; `export` prefixes, `#` comments and quotes are handled.
+fn parse_env_file(s: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].split(q).next().unwrap_or_default().to_string(),
+            _ => value
+                .split(" #")
+                .next()
+                .unwrap_or_default()
+                .trim_end()
+                .to_string(),
+        };
+        out.push((key.trim().to_string(), value));
+    }
+    out
+}
+
+pub(crate) fn shell_command(script: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", script]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", script]);
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dotenv_lines() {
+        let vars = parse_env_file(
+            "# api\nexport API_URL=http://localhost:8080 # dev\nNAME=\"a # b\"\n\nEMPTY=\nbogus\n",
+        );
+        assert_eq!(
+            vars,
+            [
+                ("API_URL".to_string(), "http://localhost:8080".to_string()),
+                ("NAME".to_string(), "a # b".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+}
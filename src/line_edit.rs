@@ -0,0 +1,172 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::Stylize;
+
+/// A one-line text input with readline-style editing, used for the picker's filters.
+#[derive(Debug, Default, Clone)]
+pub struct LineEditor {
+    text: String,
+    /// Byte offset of the cursor; always on a char boundary.
+    cursor: usize,
+}
+
+impl LineEditor {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Insert pasted text at the cursor; line breaks become spaces and other control
+    /// characters are dropped.
+    pub fn insert_str(&mut self, s: &str) {
+        let clean: String = s
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        self.text.insert_str(self.cursor, &clean);
+        self.cursor += clean.len();
+    }
+
+    /// Apply an editing key; returns whether the text changed. Keys it doesn't know (and
+    /// plain ←/→, which the views use) are left to the caller.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let before = self.text.len();
+        match key.code {
+            KeyCode::Char(ch)
+                if matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT)
+                    && !ch.is_control() =>
+            {
+                self.text.insert(self.cursor, ch);
+                self.cursor += ch.len_utf8();
+            }
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.text.len(),
+            KeyCode::Char('b') if ctrl => self.cursor = self.prev_char(),
+            KeyCode::Char('f') if ctrl => self.cursor = self.next_char(),
+            KeyCode::Char('b') if alt => self.cursor = self.prev_word(),
+            KeyCode::Char('f') if alt => self.cursor = self.next_word(),
+            KeyCode::Left if ctrl || alt => self.cursor = self.prev_word(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.next_word(),
+            KeyCode::Left if key.modifiers == KeyModifiers::SHIFT => {
+                self.cursor = self.prev_char();
+            }
+            KeyCode::Right if key.modifiers == KeyModifiers::SHIFT => {
+                self.cursor = self.next_char();
+            }
+            KeyCode::Char('w') if ctrl => self.delete_to(self.prev_word()),
+            KeyCode::Backspace if alt || ctrl => self.delete_to(self.prev_word()),
+            KeyCode::Backspace => self.delete_to(self.prev_char()),
+            KeyCode::Char('h') if ctrl => self.delete_to(self.prev_char()),
+            KeyCode::Delete => self.delete_to(self.next_char()),
+            KeyCode::Char('d') if ctrl => self.delete_to(self.next_char()),
+            KeyCode::Char('d') if alt => self.delete_to(self.next_word()),
+            KeyCode::Char('u') if ctrl => self.delete_to(0),
+            KeyCode::Char('k') if ctrl => self.delete_to(self.text.len()),
+            _ => return false,
+        }
+        self.text.len() != before
+    }
+
+    /// The text with the cursor drawn as a reversed cell.
+    pub fn render(&self) -> String {
+        let (head, tail) = self.text.split_at(self.cursor);
+        let mut chars = tail.chars();
+        let at = chars.next().map_or(" ".to_string(), String::from);
+        format!("{head}{}{}", at.reverse(), chars.as_str())
+    }
+
+    /// Remove the text between the cursor and `to` (either side).
+    fn delete_to(&mut self, to: usize) {
+        let (start, end) = if to < self.cursor {
+            (to, self.cursor)
+        } else {
+            (self.cursor, to)
+        };
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+    }
+
+    fn prev_char(&self) -> usize {
+        self.text[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_char(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// Start of the word before the cursor (skipping separators first), like readline's `M-b`.
+    fn prev_word(&self) -> usize {
+        let head = &self.text[..self.cursor];
+        let trimmed = head.trim_end_matches(|c: char| !is_word_char(c));
+        trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !is_word_char(*c))
+            .map_or(0, |(i, c)| i + c.len_utf8())
+    }
+
+    /// End of the word after the cursor, like readline's `M-f`.
+    fn next_word(&self) -> usize {
+        let tail = &self.text[self.cursor..];
+        let skipped = tail.len() - tail.trim_start_matches(|c: char| !is_word_char(c)).len();
+        tail[skipped..]
+            .char_indices()
+            .find(|(_, c)| !is_word_char(*c))
+            .map_or(self.text.len(), |(i, _)| self.cursor + skipped + i)
+    }
+}
+
+/// Path separators, dashes and spaces all end a word, so `^W` drops one path component.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(ed: &mut LineEditor, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        ed.handle_key(KeyEvent::new(code, modifiers))
+    }
+
+    fn type_str(ed: &mut LineEditor, s: &str) {
+        for ch in s.chars() {
+            press(ed, KeyCode::Char(ch), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn edits_in_the_middle_and_by_word() {
+        let mut ed = LineEditor::default();
+        type_str(&mut ed, "code/web-app");
+        press(&mut ed, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(ed.cursor, "code/web-".len());
+        press(&mut ed, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(ed.text(), "code/web-xapp");
+
+        assert!(press(&mut ed, KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(ed.text(), "code/web-app");
+        assert!(press(&mut ed, KeyCode::Backspace, KeyModifiers::ALT));
+        assert_eq!(ed.text(), "code/app");
+
+        assert!(!press(&mut ed, KeyCode::Char('e'), KeyModifiers::CONTROL));
+        press(&mut ed, KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(ed.text(), "code/apps");
+        press(&mut ed, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut ed, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(ed.text(), "");
+    }
+}
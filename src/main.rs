@@ -1,28 +1,141 @@
+mod archive;
+mod batch;
+mod codex_config;
+mod codex_history;
 mod config;
+mod devcontainer;
+mod exit;
+mod export;
+mod fzf;
+mod github;
+mod icons;
+mod ignore;
+mod install;
+mod launch;
+mod line_edit;
+mod output;
 mod pathfmt;
+mod plain_ui;
+mod procs;
 mod projects;
 mod quick;
+mod remote;
+mod repoinfo;
+mod search;
+mod session_cache;
 mod sessions;
+mod state;
+mod sync;
+mod target_cache;
 mod timefmt;
+mod tmux;
 mod tui;
 mod ui;
+mod verify;
+mod workspace;
 
+use std::fs;
 use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
-use crate::config::Config;
+use crate::config::{Config, PrioritizeCwd};
 use crate::projects::ProjectTarget;
 use crate::sessions::SessionItem;
 
+const HELP_EXTRA: &str = "\
+Examples:
+  codex-launch                       Project picker (Projects / Sessions tabs)
+  codex-launch chatkit               Start Codex in the best-matching project
+  codex-launch api web --tmux        Start Codex in both projects, one tmux window each
+  codex-launch --resume ethea        Resume the best-matching recent session
+  codex-launch .                     Start Codex right here (--here --resume: continue)
+  codex-launch --again               Repeat the previous quick launch/resume query (or `!!`)
+  codex-launch --recent --no-ui      Print recent sessions as TSV
+  codex-launch last --here           Resume the current repo's newest session (or --last)
+  codex-launch last --format json    The newest session's id, cwd and rollout path
+  codex-launch search flaky test     Sessions whose messages mention both words
+  codex-launch grep -i 'ENOENT' --since 7d
+                                     Matching rollout lines from the last week
+  codex-launch export --project api --since 30d --out api-history/
+                                     One Markdown transcript per matching session
+  codex-launch gh billing            Clone one of your GitHub repos and start Codex
+  codex-launch open api --with files Open a project without Codex (editor/files/shell)
+  codex-launch add-root ~/Code       Scan a folder's git repos for targets
+  codex-launch add-path ssh://devbox/~/api
+                                     Add a folder on another machine (run over ssh)
+  codex-launch pin ~/work/api        Keep a project (or a session id) at the top of the picker
+  codex-launch --print-cmd=json api  Print the launch command for a wrapper
+
+Exit codes: 0 launched, 1 error, 2 no match, 130 cancelled (--error-format json for details).
+
+Config (~/.codex-launch/config.toml):
+  codex.bin, codex.args              Codex executable and default arguments
+  codex.pick_profile                 Ask for a Codex profile before new sessions
+  codex.launch_options, codex.models Ask for sandbox/approval/model before new sessions
+  codex.remote_bin                   Codex executable on ssh:// targets / in containers
+  codex.resume_style                 Resume args for your codex version ([\"resume\", \"{id}\"])
+  [agents.NAME] bin, args, providers Other agent CLIs (claude, aider) to launch instead of codex
+  launch.agent, projects.agents      Default agent for new sessions, overall and per target
+  projects.roots, projects.paths     Folders to scan one level deep / explicit targets
+  projects.from_sessions             Infer targets from recent session cwds
+  projects.prioritize_cwd            always|inside_targets|never: put the cwd first
+  projects.cwd_exclude               Folders never added as the current directory
+  projects.exclude                   Globs (or re:regexes) of folders to hide from discovery
+  projects.sessions_limit            Sessions scanned to infer targets
+  projects.root_scan_limit           Repos per root before scanning stops with a warning
+  projects.session_limits           Per-target session list sizes
+  projects.sub_targets               List monorepo packages as repo/package targets
+  projects.submodules                List initialized git submodules under their repo
+  sessions.codex_home, sessions.limit
+                                     Where Codex keeps sessions/, how many to show
+  sessions.path_rewrites             Map foreign session cwd prefixes to local paths
+  sessions.history_only              Also list sessions only left in history.jsonl
+  launch.mode                        exec (default), tmux (a session per project),
+                                     kitty or wezterm (a new terminal tab), zellij
+  launch.zellij                      pane (default) or tab: where zellij mode opens codex
+  launch.env, launch.hooks           Environment and pre-launch commands for codex
+  launch.title                       Terminal title while codex runs (\"codex: {label}\")
+  launch.pick_subdir                 Ask for a monorepo package/subfolder to start in
+  launch.shell                       Run codex through e.g. \"zsh -ic\" for rc-file PATHs
+  launch.direnv, launch.env_file     Load .envrc (direnv exec) / a dotenv file for codex
+  projects.env                       Per-target direnv / env_file overrides
+  launch.devcontainer                ask|always|never: run inside .devcontainer/
+  projects.containers                Per-target running container to `exec` codex in
+  [[projects.overrides]] path, args, profile, model, env
+                                     Codex settings per folder or glob (~/work/*)
+  launch.container_runtime           docker (default) or podman
+  sync.remote, sync.branch           Git remote for `config sync`
+  open.with                          editor (default), files or shell for `open`
+  [[actions]] name, command         Extra project action-menu entries (tab)
+  search.index                       Keep a full-text index for `search`/`--resume`
+  display.badge_colors               Colors for provider/source/model badges
+  display.icons                      Nerd Font icons in project/session rows
+  display.preview                    README/last-commit preview under projects
+  display.last_commit                HEAD commit age/subject column for projects
+  display.git_status                 Branch column for projects, marked * when dirty
+  display.dates                      calendar (Today 14:02) or age (3d Jan17 10:11)
+  display.launches                   launch count/last launch column for projects
+  display.paths                      compact (~/…, head elided) or full paths in rows
+  display.vim_keys                   gg/G and ctrl+d/u/f/b list motions in the picker
+  display.plain_ui                   Numbered prompts instead of the full-screen picker
+  display.picker                     tui (built-in) or fzf (external fuzzy finder)
+  display.fzf_command                Finder command for the fzf picker (default: fzf)
+  display.sort                       Projects tab order: recent, label, path, sessions
+
+A root may contain a .codexlaunchignore file (gitignore-style globs) to hide children.";
+
 #[derive(Debug, Parser)]
 #[command(
     name = "codex-launch",
     version,
-    about = "Interactive launcher for Codex CLI"
+    about = "Interactive launcher for Codex CLI",
+    long_about = "Interactive launcher for Codex CLI: pick a folder and start a new Codex session there, \
+or pick a recent session from ~/.codex/sessions and resume it.",
+    after_long_help = HELP_EXTRA
 )]
 struct Cli {
     /// Path to config TOML (default: ~/.codex-launch/config.toml)
@@ -33,14 +146,46 @@ struct Cli {
     #[arg(long)]
     dry_run: bool,
 
+    /// Print the resolved command to stdout (shell syntax or JSON) instead of running it
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "shell"
+    )]
+    print_cmd: Option<ui::CommandFormat>,
+
     /// Disable interactive prompts; print lists instead (useful in non-TTY)
     #[arg(long)]
     no_ui: bool,
 
+    /// Numbered lists and typed answers instead of the full-screen picker (for screen
+    /// readers; also on with `TERM=dumb`)
+    #[arg(long)]
+    plain_ui: bool,
+
+    /// Picker for the no-args run (default: `display.picker`); `fzf` pipes the lists into
+    /// `display.fzf_command`
+    #[arg(long, value_enum)]
+    picker: Option<config::Picker>,
+
+    /// How to print a failure on stderr (`json` for wrappers); see also the exit codes
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = exit::ErrorFormat::Text)]
+    error_format: exit::ErrorFormat,
+
+    /// Rescan project roots and reread session files instead of using the caches
+    #[arg(long)]
+    refresh: bool,
+
     /// Shortcut for `recent` interactive picker
     #[arg(long)]
     recent: bool,
 
+    /// Resume the most recent session (same as `last`; with --here, the current repo's)
+    #[arg(long, conflicts_with_all = ["project", "resume", "again", "recent"])]
+    last: bool,
+
     /// With `--recent`, include sessions outside configured targets
     #[arg(long)]
     all_sessions: bool,
@@ -49,13 +194,42 @@ struct Cli {
     #[arg(long)]
     limit: Option<usize>,
 
-    /// Quick resume by searching recent sessions (matches id/cwd/summary)
-    #[arg(long, value_name = "QUERY")]
+    /// Codex profile to launch with (must exist in Codex's config.toml)
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Launch this agent from `[agents]` (or `codex`) instead of the configured one
+    #[arg(long, value_name = "NAME")]
+    agent: Option<String>,
+
+    /// Quick resume by searching recent sessions (matches id/cwd/summary); with --here, no query
+    #[arg(long, value_name = "QUERY", num_args = 0..=1, default_missing_value = "")]
     resume: Option<String>,
 
-    /// Quick launch by searching projects (positional query)
+    /// With --resume: also match the first few assistant replies of each session
+    #[arg(long, requires = "resume")]
+    deep: bool,
+
+    /// Quick launch by searching projects (positional query; several need `--tmux`)
     #[arg(value_name = "PROJECT")]
-    project: Option<String>,
+    project: Vec<String>,
+
+    /// Open each quick-launched project in its own tmux window
+    #[arg(long)]
+    tmux: bool,
+
+    /// Don't ask to confirm a quick launch whose fuzzy match looks off
+    #[arg(short = 'y', long)]
+    yes: bool,
+
+    /// Repeat the previous quick launch or `--resume` query (same as `!!`)
+    #[arg(long, conflicts_with_all = ["project", "resume", "here"])]
+    again: bool,
+
+    /// Start a new session in the current directory (same as `.`); with --resume, continue
+    /// its repo's latest session
+    #[arg(long, conflicts_with = "project")]
+    here: bool,
 
     #[command(subcommand)]
     cmd: Option<Cmd>,
@@ -67,54 +241,436 @@ enum Cmd {
     Pick,
 
     /// List discovered targets
-    List,
+    List {
+        /// Only targets found this way: under a root, an explicit path, or from session history
+        #[arg(long)]
+        kind: Option<ListKind>,
+
+        /// Only projects with a session carrying this tag (added in the picker)
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only targets inside this folder
+        #[arg(long)]
+        root: Option<PathBuf>,
+
+        /// Only targets with at least one recorded session
+        #[arg(long)]
+        with_sessions_only: bool,
+
+        /// `table`: aligned columns with a header; `plain`: bare paths
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
 
     /// Add a root folder (one-level scan for git repos)
-    AddRoot { path: PathBuf },
+    AddRoot {
+        path: PathBuf,
+
+        /// Search below PATH for folders holding several git repos and pick which to add
+        #[arg(long)]
+        scan: bool,
+
+        /// How many levels below PATH `--scan` looks
+        #[arg(long, default_value_t = 3, requires = "scan")]
+        depth: usize,
+    },
 
     /// Add an explicit folder target (git or non-git)
-    AddPath { path: PathBuf },
+    AddPath {
+        path: PathBuf,
+
+        /// Show the target under this name instead of the folder name
+        #[arg(long)]
+        label: Option<String>,
+    },
+
+    /// Find one of your GitHub repos (via the `gh` CLI), clone it under a root and start Codex
+    Gh {
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+
+    /// Open a project (fuzzy match, like quick launch) in your editor, file manager or a shell
+    /// instead of Codex
+    Open {
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// What to open it in (default: `open.with`, itself defaulting to the editor)
+        #[arg(long, value_enum)]
+        with: Option<config::OpenWith>,
+    },
+
+    /// Change the label of a configured path (an empty label restores the folder name)
+    Edit {
+        path: PathBuf,
+
+        #[arg(long)]
+        label: String,
+    },
+
+    /// Remove a configured root/path (exact match); without a path, pick entries to remove
+    Rm { path: Option<PathBuf> },
+
+    /// Keep a project folder or a session id at the top of the picker; without one, list pins
+    Pin { target: Option<String> },
 
-    /// Remove a configured root/path (exact match)
-    Rm { path: PathBuf },
+    /// Stop pinning a project folder or session id
+    Unpin { target: String },
 
     /// Show recent sessions and resume one
     Recent {
         /// Show only sessions whose cwd is under configured roots/paths
-        #[arg(long)]
+        #[arg(long, conflicts_with_all = ["cwd", "here", "project"])]
         scoped: bool,
 
+        /// Show only sessions started in this folder or below it
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["here", "project"])]
+        cwd: Option<PathBuf>,
+
+        /// Show only sessions of the current git repo (or folder, outside a repo)
+        #[arg(long, conflicts_with = "project")]
+        here: bool,
+
+        /// Show only sessions of the project matching this query (fuzzy, like quick launch)
+        #[arg(long, value_name = "QUERY")]
+        project: Option<String>,
+
         /// How many sessions to show (default from config)
         #[arg(long)]
         limit: Option<usize>,
+
+        /// Print sessions clustered by project (repo root) with per-project counts
+        #[arg(long, value_name = "KEY")]
+        group_by: Option<GroupBy>,
+
+        /// Print JSON instead of opening the picker
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Resume the most recent session; with --format or --no-ui, print it (id, cwd, rollout
+    /// path) instead. Scripts use `last --format text`
+    Last {
+        /// Only sessions of the project matching this query (fuzzy, like quick launch)
+        #[arg(long, value_name = "QUERY")]
+        project: Option<String>,
+
+        /// Only sessions of the current git repo (or folder, outside a repo)
+        #[arg(long, conflicts_with = "project")]
+        here: bool,
+
+        /// Print instead of resuming. `text`: id, cwd and rollout path separated by tabs;
+        /// `json`: the full session record
+        #[arg(long, value_enum)]
+        format: Option<LastFormat>,
     },
 
     /// Resume a specific session id (exact)
     ResumeId { id: String },
 
+    /// Check a session's rollout file line by line: missing metadata, malformed lines, and a
+    /// truncated tail (common after a crash)
+    VerifySession {
+        id: String,
+
+        /// Trim the truncated tail so codex can resume the session again (keeps a .bak copy)
+        #[arg(long)]
+        repair: bool,
+    },
+
     /// Print resolved config path and exit
     WhereConfig,
+
+    /// Print where sessions are read from and what the store holds (dates, counts in scope)
+    WhereSessions,
+
+    /// Print a man page (roff) to stdout, or write one per subcommand into a directory
+    Man {
+        /// Directory to write `codex-launch.1`, `codex-launch-recent.1`, … into
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Manage the launcher's config directory
+    Config {
+        #[command(subcommand)]
+        cmd: ConfigCmd,
+    },
+
+    /// List profiles defined in Codex's config.toml
+    Profiles,
+
+    /// Bundle session rollouts (plus a metadata manifest) into a .tar.zst archive
+    Backup {
+        /// Archive to write (`.tar.zst`; a plain `.tar` skips compression)
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Only sessions newer than this age (e.g. `30d`, `12h`, `2w`)
+        #[arg(long, value_parser = since_arg)]
+        since: Option<time::Duration>,
+
+        /// Only sessions of the project matching this query (fuzzy, like quick launch)
+        #[arg(long, value_name = "QUERY")]
+        project: Option<String>,
+    },
+
+    /// Unpack a `backup` archive into the sessions store (existing sessions are skipped)
+    Restore { archive: PathBuf },
+
+    /// Write session transcripts as Markdown files named by date and title
+    Export {
+        /// Directory to write the `.md` files to
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Only sessions newer than this age (e.g. `30d`, `12h`, `2w`)
+        #[arg(long, value_parser = since_arg)]
+        since: Option<time::Duration>,
+
+        /// Only sessions of the project matching this query (fuzzy, like quick launch)
+        #[arg(long, value_name = "QUERY")]
+        project: Option<String>,
+    },
+
+    /// Find sessions whose messages contain all the given words (prefixes match);
+    /// `file:<path>` matches sessions that edited that file
+    Search {
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// How many matches to show (default from config)
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Print JSON instead of opening the picker
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print rollout lines matching a regex, as `id<TAB>timestamp<TAB>line`
+    Grep {
+        pattern: String,
+
+        /// Case-insensitive match
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+
+        /// Only sessions of the project matching this query (fuzzy, like quick launch)
+        #[arg(long, value_name = "QUERY")]
+        project: Option<String>,
+
+        /// Only sessions newer than this age (e.g. `30d`, `12h`, `2w`)
+        #[arg(long, value_parser = since_arg)]
+        since: Option<time::Duration>,
+    },
+
+    /// Build or refresh the full-text session index (`search.index`)
+    Index {
+        /// Discard the existing index and re-read every rollout
+        #[arg(long)]
+        rebuild: bool,
+    },
+
+    /// Print earlier quick queries for shell completion (bash: `complete -C`)
+    #[command(hide = true)]
+    CompleteQueries {
+        /// As passed by `complete -C`: command name, word being completed, previous word
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum GroupBy {
+    Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum LastFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum ListFormat {
+    Table,
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ListKind {
+    Root,
+    Path,
+    Session,
+    Remote,
+}
+
+impl ListKind {
+    fn matches(self, kind: &projects::TargetKind) -> bool {
+        use projects::TargetKind;
+        matches!(
+            (self, kind),
+            (ListKind::Root, TargetKind::RootChildGitRepo)
+                | (ListKind::Path, TargetKind::ExplicitPath)
+                | (ListKind::Session, TargetKind::SessionHistory)
+                | (ListKind::Remote, TargetKind::Remote)
+        )
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCmd {
+    /// Commit the config directory to git and pull/push it via `sync.remote`
+    Sync {
+        /// Set (and save) the git remote URL before syncing
+        #[arg(long)]
+        remote: Option<String>,
+    },
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn since_arg(s: &str) -> std::result::Result<time::Duration, String> {
+    timefmt::parse_since(s)
+        .ok_or_else(|| format!("invalid age `{s}` (expected e.g. 45m, 12h, 30d, 2w)"))
+}
+
+fn main() -> std::process::ExitCode {
+    timefmt::local_offset();
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            // Usage errors exit 1 rather than clap's 2, which means "no match" here.
+            let _ = e.print();
+            return if e.use_stderr() {
+                exit::ERROR.into()
+            } else {
+                std::process::ExitCode::SUCCESS
+            };
+        }
+    };
+    let format = cli.error_format;
+    match run_cli(cli) {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(e) => exit::report(&e, format),
+    }
+}
+
+fn run_cli(mut cli: Cli) -> Result<()> {
     let config_path = config::resolve_config_path(cli.config.as_deref())?;
     let mut cfg = Config::load_or_init(&config_path)?;
+    if cli.refresh
+        && let Some(dir) = cfg.dir.as_deref()
+    {
+        target_cache::clear(dir)?;
+        session_cache::clear(dir)?;
+    }
+    let run = RunOpts {
+        dry_run: cli.dry_run,
+        print_cmd: cli.print_cmd,
+    };
+
+    if let Some(agent) = cli.agent.take() {
+        if agent != "codex" && !cfg.agents.contains_key(&agent) {
+            let known = std::iter::once("codex")
+                .chain(cfg.agents.keys().map(String::as_str))
+                .collect::<Vec<_>>();
+            anyhow::bail!("unknown agent: {agent} (available: {})", known.join(", "));
+        }
+        cfg.agent_override = Some(agent);
+    }
+
+    if let Some(profile) = cli.profile.as_deref() {
+        let codex_cfg = codex_config::load(&cfg.sessions.codex_home).unwrap_or_default();
+        if !codex_cfg.profiles.contains_key(profile) {
+            let known = codex_cfg.profiles.keys().cloned().collect::<Vec<_>>();
+            anyhow::bail!(
+                "unknown Codex profile: {profile} (available: {})",
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            );
+        }
+        cfg.profile_override = Some(profile.to_string());
+    }
+
+    let state_path = state::state_path(&config_path);
+    if cli.cmd.is_none() && (cli.again || cli.project == ["!!"]) {
+        let state = state::State::load(&state_path);
+        let Some(last) = state.quick_history.first() else {
+            anyhow::bail!("no earlier quick query to repeat");
+        };
+        if last.resume {
+            ui::print_info(&format!("Repeating: --resume {}", last.query));
+            cli.project.clear();
+            cli.resume = Some(last.query.clone());
+        } else {
+            ui::print_info(&format!("Repeating: {}", last.query));
+            cli.project = vec![last.query.clone()];
+        }
+    }
+
+    if cli.cmd.is_none() && cli.last {
+        cli.cmd = Some(Cmd::Last {
+            project: None,
+            here: cli.here,
+            format: None,
+        });
+    }
+
+    let here = cli.here || cli.project == ["."];
+    if cli.cmd.is_none() && here {
+        let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+        let dir = fs::canonicalize(&cwd).unwrap_or(cwd);
+        return match cli.resume.as_deref() {
+            Some("") => quick::resume_latest_in(&cfg, &dir, run),
+            Some(_) => anyhow::bail!("--resume takes no query with --here"),
+            None => run_codex_new(&cfg, &dir_target(&cfg, dir), run),
+        };
+    }
 
     if cli.cmd.is_none() && cli.resume.is_some() {
+        remember_quick_query(
+            &state_path,
+            true,
+            cli.resume.as_deref().unwrap_or_default(),
+            run,
+        );
         return quick::resume_by_query(
             &cfg,
+            &search::index_dir(&config_path),
             cli.resume.as_deref().unwrap_or_default(),
-            cli.dry_run,
+            cli.deep,
+            run,
         );
     }
 
-    if cli.cmd.is_none() && cli.project.is_some() {
-        return quick::launch_by_query(
-            &cfg,
-            cli.project.as_deref().unwrap_or_default(),
-            cli.dry_run,
-        );
+    if cli.cmd.is_none() && !cli.project.is_empty() {
+        if !cli.tmux {
+            let [query] = cli.project.as_slice() else {
+                anyhow::bail!("several projects open in tmux windows: add --tmux");
+            };
+            remember_quick_query(&state_path, false, query, run);
+            if let Some(dir) = existing_dir(query) {
+                if !cli.no_ui && std::io::stdin().is_terminal() && !is_configured(&cfg, &dir) {
+                    offer_to_add(&mut cfg, &config_path, &dir, run)?;
+                }
+                return run_codex_new(&cfg, &dir_target(&cfg, dir), run);
+            }
+            return quick::launch_by_query(&cfg, query, cli.yes, run);
+        }
+        let targets = cli
+            .project
+            .iter()
+            .map(|q| match existing_dir(q) {
+                Some(dir) => Ok(dir_target(&cfg, dir)),
+                None => quick::resolve_project(&cfg, q),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return tmux::launch_all(&cfg, &targets, run);
     }
 
     if cli.cmd.is_none() && cli.recent {
@@ -134,23 +690,14 @@ fn main() -> Result<()> {
         };
         let items = sessions::list_recent_sessions(&cfg, query)?;
         if items.is_empty() {
-            println!("No sessions found.");
-            return Ok(());
+            return Err(exit::Exit::NoMatch("no sessions found".to_string()).into());
         }
         if cli.no_ui {
-            for s in items {
-                println!(
-                    "{}\t{}\t{}\t{}",
-                    s.id,
-                    s.created_at.as_deref().unwrap_or(""),
-                    s.cwd.display(),
-                    s.summary.as_deref().unwrap_or("")
-                );
-            }
+            output::print_sessions_tsv(&items);
             return Ok(());
         } else {
             let picked = ui::pick_session(&items)?;
-            return run_codex_resume(&cfg, &picked, cli.dry_run);
+            return run_codex_resume(&cfg, &picked, run);
         }
     }
 
@@ -159,121 +706,678 @@ fn main() -> Result<()> {
             println!("{}", config_path.display());
             Ok(())
         }
-        Cmd::AddRoot { path } => {
-            cfg.add_root(path)?;
+        Cmd::WhereSessions => print_where_sessions(&cfg),
+        Cmd::Profiles => {
+            let codex_cfg = codex_config::load(&cfg.sessions.codex_home).unwrap_or_default();
+            for name in codex_cfg.profiles.keys() {
+                println!("{name}\t{}", codex_cfg.describe_profile(name));
+            }
+            Ok(())
+        }
+        Cmd::Backup {
+            out,
+            since,
+            project,
+        } => {
+            let items = quick::sessions_matching(&cfg, project.as_deref(), since)?;
+            archive::backup(&cfg, items, &out)
+        }
+        Cmd::Search { query, limit, json } => {
+            let print_only = cli.no_ui || json;
+            if !print_only && (!std::io::stdin().is_terminal() || !std::io::stdout().is_terminal())
+            {
+                anyhow::bail!(
+                    "The input device is not a TTY. Re-run with `--no-ui` to print lists without prompts."
+                );
+            }
+            let mut items =
+                search::search(&cfg, &search::index_dir(&config_path), &query.join(" "))?;
+            items.truncate(limit.unwrap_or(cfg.sessions.limit));
+            if json {
+                return output::print_sessions_json(&items);
+            }
+            if items.is_empty() {
+                return Err(exit::Exit::NoMatch("no sessions found".to_string()).into());
+            }
+            if cli.no_ui {
+                output::print_sessions_tsv(&items);
+                return Ok(());
+            }
+            let picked = ui::pick_session(&items)?;
+            run_codex_resume(&cfg, &picked, run)
+        }
+        Cmd::Grep {
+            pattern,
+            ignore_case,
+            project,
+            since,
+        } => {
+            let re = regex::RegexBuilder::new(&pattern)
+                .case_insensitive(ignore_case)
+                .build()
+                .with_context(|| format!("invalid regex: {pattern}"))?;
+            let items = quick::sessions_matching(&cfg, project.as_deref(), since)?;
+            search::grep(&items, &re, &mut std::io::stdout().lock())?;
+            Ok(())
+        }
+        Cmd::Index { rebuild } => {
+            let dir = search::index_dir(&config_path);
+            let mut index = search::FullTextIndex::load(&dir);
+            if rebuild {
+                index.clear();
+            }
+            let stats = index.update(&cfg)?;
+            index.save()?;
+            for note in stats.skipped.iter() {
+                ui::print_warn(note);
+            }
+            ui::print_info(&format!(
+                "Indexed {} session(s), dropped {}, {} total in {}",
+                stats.indexed,
+                stats.removed,
+                stats.total,
+                dir.display()
+            ));
+            if !cfg.search.index {
+                ui::print_info(
+                    "Set `search.index = true` to use it for `search` and `--resume` and keep it fresh.",
+                );
+            }
+            Ok(())
+        }
+        Cmd::CompleteQueries { words } => {
+            let current = words.get(1).map(String::as_str).unwrap_or_default();
+            let resume = words.get(2).is_some_and(|w| w == "--resume");
+            let state = state::State::load(&state_path);
+            for q in state.quick_history.iter() {
+                if q.resume == resume && q.query.starts_with(current) {
+                    println!("{}", q.query);
+                }
+            }
+            Ok(())
+        }
+        Cmd::Man { out } => {
+            let cmd = Cli::command();
+            match out {
+                Some(dir) => {
+                    std::fs::create_dir_all(&dir)
+                        .with_context(|| format!("failed to create {}", dir.display()))?;
+                    clap_mangen::generate_to(cmd, &dir)
+                        .with_context(|| format!("failed to write man pages to {}", dir.display()))
+                }
+                None => Ok(clap_mangen::Man::new(cmd).render(&mut std::io::stdout())?),
+            }
+        }
+        Cmd::Config {
+            cmd: ConfigCmd::Sync { remote },
+        } => {
+            if let Some(remote) = remote {
+                cfg.sync.remote = Some(remote);
+                cfg.save(&config_path)?;
+            }
+            let dir = config_path
+                .parent()
+                .context("config path has no parent directory")?;
+            sync::sync(dir, &cfg.sync, cli.dry_run)
+        }
+        Cmd::Restore { archive } => archive::restore(&cfg, &archive, cli.dry_run),
+        Cmd::Export {
+            out,
+            since,
+            project,
+        } => {
+            let items = quick::sessions_matching(&cfg, project.as_deref(), since)?;
+            if cli.dry_run {
+                ui::print_info(&format!(
+                    "DRY RUN: would export {} session(s) to {}",
+                    items.len(),
+                    out.display()
+                ));
+                return Ok(());
+            }
+            let written = export::export_markdown(&items, &out)?;
+            ui::print_info(&format!(
+                "Exported {} session(s) to {}",
+                written.len(),
+                out.display()
+            ));
+            Ok(())
+        }
+        Cmd::AddRoot { path, scan, depth } => {
+            if !scan {
+                cfg.add_root(path)?;
+                cfg.save(&config_path)?;
+                return Ok(());
+            }
+            let start = std::path::absolute(config::normalize(path)?)?;
+            let candidates = projects::find_root_candidates(&start, depth)
+                .into_iter()
+                .filter(|(p, _)| !cfg.projects.roots.contains(p))
+                .collect::<Vec<_>>();
+            if candidates.is_empty() {
+                println!("No folders with several git repos found.");
+                return Ok(());
+            }
+            if cli.no_ui || !std::io::stdin().is_terminal() {
+                for (p, repos) in candidates {
+                    println!("{}\t{repos}", p.display());
+                }
+                return Ok(());
+            }
+            let chosen = ui::pick_roots(&candidates)?;
+            if cli.dry_run {
+                for p in chosen {
+                    ui::print_info(&format!("DRY RUN: would add root {}", p.display()));
+                }
+                return Ok(());
+            }
+            for p in chosen {
+                ui::print_info(&format!("Added root {}", p.display()));
+                cfg.add_root(p)?;
+            }
             cfg.save(&config_path)?;
             Ok(())
         }
-        Cmd::AddPath { path } => {
-            cfg.add_path(path)?;
+        Cmd::AddPath { path, label } => {
+            cfg.add_path(path, label)?;
+            cfg.save(&config_path)?;
+            Ok(())
+        }
+        Cmd::Open { query, with } => {
+            let target = quick::resolve_project(&cfg, &query.join(" "))?;
+            match with.unwrap_or(cfg.open.with) {
+                config::OpenWith::Editor => open_editor(&target, run),
+                config::OpenWith::Shell => open_shell(&target, run),
+                config::OpenWith::Files => {
+                    if remote::is_remote(&target.path) {
+                        anyhow::bail!("{} is on another machine", target.path.display());
+                    }
+                    ui::print_info(&format!("Opening {}", target.path.display()));
+                    run_command(system_open(&target.path), run)
+                }
+            }
+        }
+        Cmd::Gh { query } => {
+            let repos = github::matching(github::list_repos()?, &query.join(" "));
+            if repos.is_empty() {
+                return Err(exit::Exit::NoMatch("no repos found".to_string()).into());
+            }
+            if cli.no_ui || !std::io::stdin().is_terminal() {
+                for r in repos {
+                    println!(
+                        "{}\t{}\t{}",
+                        r.name_with_owner,
+                        r.url,
+                        r.description.as_deref().unwrap_or("")
+                    );
+                }
+                return Ok(());
+            }
+            let repo = ui::pick_repo(&repos)?;
+            let dest = pick_parent_root(&cfg)?.join(&repo.name);
+            if dest.exists() {
+                ui::print_info(&format!("{} already exists; not cloning", dest.display()));
+            } else {
+                ui::print_info(&format!(
+                    "Cloning {} into {}",
+                    repo.name_with_owner,
+                    dest.display()
+                ));
+                run_command(github::clone_command(&repo, &dest), run)?;
+            }
+            let target = ProjectTarget::new(dest, projects::TargetKind::RootChildGitRepo);
+            run_codex_new(&cfg, &target, run)
+        }
+        Cmd::Edit { path, label } => {
+            cfg.set_label(path, label)?;
             cfg.save(&config_path)?;
             Ok(())
         }
-        Cmd::Rm { path } => {
+        Cmd::Rm { path: Some(path) } => {
             cfg.remove_path_or_root(path)?;
             cfg.save(&config_path)?;
             Ok(())
         }
-        Cmd::List => {
-            let targets = projects::gather_targets(&cfg)?;
-            for t in targets {
-                println!("{t}");
+        Cmd::Rm { path: None } => {
+            if cli.no_ui || !std::io::stdin().is_terminal() {
+                anyhow::bail!("`rm` needs a path when there's no terminal to pick from");
+            }
+            let entries = cfg
+                .projects
+                .roots
+                .iter()
+                .map(|p| ("root", p.clone()))
+                .chain(cfg.projects.paths.iter().map(|p| ("path", p.clone())))
+                .collect::<Vec<_>>();
+            if entries.is_empty() {
+                println!("No roots or paths configured.");
+                return Ok(());
+            }
+            let chosen = ui::pick_config_entries(&entries)?;
+            for p in chosen {
+                if cli.dry_run {
+                    ui::print_info(&format!("DRY RUN: would remove {}", p.display()));
+                    continue;
+                }
+                cfg.remove_path_or_root(p.clone())?;
+                ui::print_info(&format!("Removed {}", p.display()));
+            }
+            if !cli.dry_run {
+                cfg.save(&config_path)?;
             }
             Ok(())
         }
+        Cmd::List {
+            kind,
+            tag,
+            root,
+            with_sessions_only,
+            format,
+        } => {
+            let mut targets =
+                projects::gather_targets(&cfg, &mut sessions::SessionIndex::default())?;
+            if let Some(kind) = kind {
+                targets.retain(|t| kind.matches(&t.kind));
+            }
+            if let Some(root) = root {
+                let root = std::path::absolute(config::normalize(root)?)?;
+                targets.retain(|t| t.path.starts_with(&root));
+            }
+            if with_sessions_only {
+                targets.retain(|t| t.last_session_at.is_some());
+            }
+            if let Some(tag) = tag {
+                let state = state::State::load(&state_path);
+                let dirs = tagged_session_dirs(&cfg, &state, &tag)?;
+                targets.retain(|t| dirs.iter().any(|d| d.starts_with(&t.path)));
+            }
+            match format {
+                ListFormat::Table => output::print_targets_table(&targets),
+                ListFormat::Plain => {
+                    for t in targets {
+                        println!("{}", t.path.display());
+                    }
+                }
+            }
+            Ok(())
+        }
+        Cmd::Pin { target: None } => {
+            let state = state::State::load(&state_path);
+            for dir in &state.pins.projects {
+                println!("{}", dir.display());
+            }
+            for id in &state.pins.sessions {
+                println!("{id}");
+            }
+            Ok(())
+        }
+        Cmd::Pin {
+            target: Some(target),
+        } => {
+            let mut state = state::State::load_for_update(&state_path)?;
+            let dir = pin_path(&target)?;
+            if dir.is_dir() {
+                state.pins.projects.insert(dir);
+            } else if let Some(session) = sessions::find_session_by_id(&cfg, &target)? {
+                state.pins.sessions.insert(session.id);
+            } else {
+                anyhow::bail!(exit::Exit::NoMatch(format!(
+                    "no such folder or session id: {target}"
+                )));
+            }
+            state.save(&state_path)
+        }
+        Cmd::Unpin { target } => {
+            let mut state = state::State::load_for_update(&state_path)?;
+            if !state.pins.projects.remove(&pin_path(&target)?)
+                && !state.pins.sessions.remove(&target)
+            {
+                anyhow::bail!(exit::Exit::NoMatch(format!("not pinned: {target}")));
+            }
+            state.save(&state_path)
+        }
         Cmd::ResumeId { id } => {
             if let Some(item) = sessions::find_session_by_id(&cfg, &id)? {
-                run_codex_resume(&cfg, &item, cli.dry_run)
+                run_codex_resume(&cfg, &item, run)
             } else {
-                anyhow::bail!("session id not found: {id}");
+                anyhow::bail!(exit::Exit::NoMatch(format!("session id not found: {id}")));
             }
         }
-        Cmd::Recent { scoped, limit } => {
-            if !cli.no_ui && (!std::io::stdin().is_terminal() || !std::io::stdout().is_terminal()) {
+        Cmd::VerifySession { id, repair } => match sessions::find_rollout_by_id(&cfg, &id)? {
+            Some(path) => verify::verify(&path, repair, cli.dry_run),
+            None => anyhow::bail!(exit::Exit::NoMatch(format!("session id not found: {id}"))),
+        },
+        Cmd::Last {
+            project,
+            here,
+            format,
+        } => {
+            let query = if let Some(q) = project {
+                let target = quick::resolve_project(&cfg, &q)?;
+                sessions::query_for_target(&target.path, 1)
+            } else if here {
+                let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+                sessions::query_for_target(&fs::canonicalize(&cwd).unwrap_or(cwd), 1)
+            } else {
+                sessions::SessionQuery::All { limit: 1 }
+            };
+            let Some(last) = sessions::list_recent_sessions(&cfg, query)?.pop() else {
+                anyhow::bail!(exit::Exit::NoMatch("no sessions found".to_string()));
+            };
+            match format {
+                None if cli.no_ui => output::print_session_ref(&last),
+                None => return run_codex_resume(&cfg, &last, run),
+                Some(LastFormat::Text) => output::print_session_ref(&last),
+                Some(LastFormat::Json) => println!("{}", serde_json::to_string_pretty(&last)?),
+            }
+            Ok(())
+        }
+        Cmd::Recent {
+            scoped,
+            cwd,
+            here,
+            project,
+            limit,
+            group_by,
+            json,
+        } => {
+            let print_only = cli.no_ui || json || group_by.is_some();
+            if !print_only && (!std::io::stdin().is_terminal() || !std::io::stdout().is_terminal())
+            {
                 anyhow::bail!(
                     "The input device is not a TTY. Re-run with `--no-ui` to print lists without prompts."
                 );
             }
+            let limit = limit.unwrap_or(cfg.sessions.limit);
             let query = if scoped {
-                sessions::SessionQuery::Scoped {
-                    limit: limit.unwrap_or(cfg.sessions.limit),
-                }
+                sessions::SessionQuery::Scoped { limit }
+            } else if let Some(cwd) = cwd {
+                let cwd = std::path::absolute(config::normalize(cwd)?)?;
+                sessions::SessionQuery::ForCwd { cwd, limit }
+            } else if let Some(q) = project {
+                let target = quick::resolve_project(&cfg, &q)?;
+                sessions::query_for_target(&target.path, limit)
+            } else if here {
+                let cwd = std::env::current_dir().context("failed to read current directory")?;
+                sessions::query_for_target(&cwd, limit)
             } else {
-                sessions::SessionQuery::All {
-                    limit: limit.unwrap_or(cfg.sessions.limit),
-                }
+                sessions::SessionQuery::All { limit }
             };
             let items = sessions::list_recent_sessions(&cfg, query)?;
-            if items.is_empty() {
-                println!("No sessions found.");
-                return Ok(());
+            if items.is_empty() && !json {
+                return Err(exit::Exit::NoMatch("no sessions found".to_string()).into());
             }
-            if cli.no_ui {
-                for s in items {
-                    println!(
-                        "{}\t{}\t{}\t{}",
-                        s.id,
-                        s.created_at.as_deref().unwrap_or(""),
-                        s.cwd.display(),
-                        s.summary.as_deref().unwrap_or("")
-                    );
+            match group_by {
+                Some(GroupBy::Project) => output::print_grouped_by_project(&items, json),
+                None if json => output::print_sessions_json(&items),
+                None if cli.no_ui => {
+                    output::print_sessions_tsv(&items);
+                    Ok(())
+                }
+                None => {
+                    let picked = ui::pick_session(&items)?;
+                    run_codex_resume(&cfg, &picked, run)
                 }
-                Ok(())
-            } else {
-                let picked = ui::pick_session(&items)?;
-                run_codex_resume(&cfg, &picked, cli.dry_run)
             }
         }
         Cmd::Pick => {
-            let mut targets = projects::gather_targets(&cfg)?;
-            if targets.is_empty() {
-                anyhow::bail!(
-                    "No targets configured. Add a root with `codex-launch add-root <path>` or an explicit folder with `codex-launch add-path <path>`."
-                );
+            if cfg.search.index && !cli.no_ui {
+                spawn_index_refresh(cfg.clone(), search::index_dir(&config_path));
             }
-            prioritize_current_target(&cfg, &mut targets)?;
-            let sessions_index = sessions::list_recent_sessions(
-                &cfg,
-                sessions::SessionQuery::All {
-                    limit: cfg.projects.sessions_limit.max(cfg.sessions.limit),
-                },
-            )?;
-            let sessions_scoped = sessions_index
-                .iter()
-                .filter(|s| cfg.is_scoped_target(&s.cwd))
-                .cloned()
-                .collect::<Vec<_>>();
+            // Batch actions reopen the picker (with fresh lists) instead of exiting.
+            let mut status = None;
+            loop {
+                let mut index = sessions::SessionIndex::default();
+                let mut warnings = Vec::new();
+                let mut targets =
+                    projects::gather_targets_reporting(&cfg, &mut index, &mut warnings)?;
+                if targets.is_empty() {
+                    anyhow::bail!(
+                        "No targets configured. Add a root with `codex-launch add-root <path>` or an explicit folder with `codex-launch add-path <path>`."
+                    );
+                }
+                let first_page = cfg.projects.sessions_limit.max(cfg.sessions.limit);
+                index.recent(&cfg, first_page)?;
+                warnings.extend(index.take_skipped());
+                if cli.no_ui {
+                    for w in warnings.iter() {
+                        ui::print_warn(w);
+                    }
+                } else if status.is_none()
+                    && let Some(first) = warnings.first()
+                {
+                    status = Some(match warnings.len() {
+                        1 => first.clone(),
+                        n => format!("{first} (+{} more)", n - 1),
+                    });
+                }
+                prioritize_current_target(&cfg, &mut index, &mut targets)?;
+                let state = state::State::load(&state_path);
+                targets.sort_by_key(|t| !state.pins.projects.contains(&t.path));
+                let scope = cfg.scope();
+                let mut pages = tui::SessionPages::new(
+                    first_page,
+                    cfg.sessions.limit,
+                    Box::new(|limit| {
+                        let all = index.recent(&cfg, limit)?.to_vec();
+                        let scoped = all
+                            .iter()
+                            .filter(|s| scope.contains(&s.cwd))
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        Ok((scoped, all))
+                    }),
+                )?
+                .with_pinned(pinned_sessions(&cfg, &state.pins), |s| {
+                    scope.contains(&s.cwd)
+                });
 
-            if cli.no_ui {
-                for t in targets {
-                    println!("{}", t.path.display());
+                if cli.no_ui {
+                    for t in targets {
+                        println!("{}", t.path.display());
+                    }
+                    return Ok(());
                 }
-                Ok(())
-            } else {
                 if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
                     anyhow::bail!(
                         "The input device is not a TTY. Re-run with `--no-ui` to print lists without prompts."
                     );
                 }
-                match tui::pick_project(
-                    &targets,
-                    &sessions_scoped,
-                    &sessions_index,
-                    cfg.sessions.limit,
-                )? {
-                    tui::ProjectPick::New(target) => run_codex_new(&cfg, &target, cli.dry_run),
-                    tui::ProjectPick::Resume(session) => {
-                        run_codex_resume(&cfg, &session, cli.dry_run)
+                let mut history = state.filter_history.clone();
+                let mut pins = state.pins.clone();
+                let mut sort = state.project_sort(cfg.display.sort);
+                let ctx = tui::PickerContext {
+                    project_limit: &|path| cfg.project_session_limit(path),
+                    display: &cfg.display,
+                    state: &state,
+                    actions: &cfg.actions,
+                    status: status.take(),
+                };
+                // Screen readers and dumb terminals get the plain picker unless fzf is asked for.
+                let plain = plain_ui::wanted(cli.plain_ui, cfg.display.plain_ui);
+                let picker = cli.picker.unwrap_or(if plain {
+                    config::Picker::Tui
+                } else {
+                    cfg.display.picker
+                });
+                let picked = match picker {
+                    config::Picker::Fzf => {
+                        let command = cfg.display.fzf_command.as_deref().unwrap_or("fzf");
+                        fzf::pick_project(command, &targets, &mut pages, &ctx, sort)?
+                    }
+                    config::Picker::Tui if plain => {
+                        plain_ui::pick_project(&targets, &mut pages, &ctx, &mut pins, sort)?
+                    }
+                    config::Picker::Tui => tui::pick_project(
+                        &mut targets,
+                        &mut pages,
+                        &ctx,
+                        &mut history,
+                        &mut pins,
+                        &mut sort,
+                    )?,
+                };
+                drop(pages);
+                let quit = matches!(picked, tui::ProjectPick::Quit);
+                let sorted = sort != state.project_sort(cfg.display.sort) && !run.dry_run;
+                if !quit || pins != state.pins || sorted {
+                    // Reloaded: other invocations may have written launches, tags or pins while
+                    // the picker was open.
+                    let saved = state::State::load_for_update(&state_path).and_then(|mut fresh| {
+                        if !quit {
+                            fresh.filter_history = history;
+                        }
+                        fresh.pins.merge_changes(&state.pins, &pins);
+                        if sorted {
+                            fresh.sort = Some(sort);
+                            fresh.sort_over = Some(cfg.display.sort);
+                        }
+                        fresh.save(&state_path)
+                    });
+                    if let Err(e) = saved {
+                        ui::print_warn(&format!("{e:#}"));
                     }
-                    tui::ProjectPick::OpenConfig => open_config(&config_path, cli.dry_run),
-                    tui::ProjectPick::Quit => Ok(()),
                 }
+                return match picked {
+                    tui::ProjectPick::New(target) => run_codex_new(&cfg, &target, run),
+                    tui::ProjectPick::NewWithOptions(target) => {
+                        cfg.codex.launch_options = true;
+                        run_codex_new(&cfg, &target, run)
+                    }
+                    tui::ProjectPick::NewInSubdir(target) => {
+                        cfg.launch.pick_subdir = true;
+                        run_codex_new(&cfg, &target, run)
+                    }
+                    tui::ProjectPick::Resume(session) => run_codex_resume(&cfg, &session, run),
+                    tui::ProjectPick::Batch { action, sessions } => {
+                        status = Some(
+                            batch::apply(&cfg, &state_path, action, &sessions, run)
+                                .unwrap_or_else(|e| format!("error: {e:#}")),
+                        );
+                        continue;
+                    }
+                    tui::ProjectPick::CreateProject => create_project(&mut cfg, &config_path, run),
+                    tui::ProjectPick::Rename(target) => {
+                        status = rename_target(&mut cfg, &config_path, &target, run)
+                            .unwrap_or_else(|e| Some(format!("error: {e:#}")));
+                        continue;
+                    }
+                    tui::ProjectPick::Shell(target) => open_shell(&target, run),
+                    tui::ProjectPick::Editor(target) => open_editor(&target, run),
+                    tui::ProjectPick::Action { target, command } => {
+                        let cmd = match remote::RemoteTarget::parse(&target.path) {
+                            Some(remote) => remote.ssh_command(&command),
+                            None => {
+                                let mut cmd = launch::shell_command(&command);
+                                cmd.current_dir(&target.path);
+                                cmd
+                            }
+                        };
+                        ui::print_info(&format!(
+                            "Running `{command}` in {}",
+                            target.path.display()
+                        ));
+                        run_command(cmd, run)
+                    }
+                    tui::ProjectPick::LaunchAll(targets) => tmux::launch_all(&cfg, &targets, run),
+                    tui::ProjectPick::OpenConfig => open_config(&config_path, run),
+                    tui::ProjectPick::Quit => Err(exit::Exit::Cancelled.into()),
+                };
             }
         }
     }
 }
 
-fn prioritize_current_target(cfg: &Config, targets: &mut Vec<ProjectTarget>) -> Result<()> {
+/// Record a quick-mode query for `--again` / `!!` and completion (not in dry runs; best effort).
+fn remember_quick_query(state_path: &std::path::Path, resume: bool, query: &str, run: RunOpts) {
+    let query = query.trim();
+    if run.dry_run || query.is_empty() {
+        return;
+    }
+    let Ok(mut state) = state::State::load_for_update(state_path) else {
+        return;
+    };
+    state.remember_quick(state::QuickQuery {
+        resume,
+        query: query.to_string(),
+    });
+    let _ = state.save(state_path);
+}
+
+/// Count a launch for `dir`'s project in the state file (not in dry runs; best effort).
+pub(crate) fn record_launch(cfg: &Config, dir: &std::path::Path, run: RunOpts) {
+    if run.dry_run || run.print_cmd.is_some() || dir.as_os_str().is_empty() {
+        return;
+    }
+    let Some(config_dir) = cfg.dir.as_deref() else {
+        return;
+    };
+    let state_path = state::state_path_in(config_dir);
+    let Ok(mut state) = state::State::load_for_update(&state_path) else {
+        return;
+    };
+    state.record_launch(dir, time::OffsetDateTime::now_utc().unix_timestamp());
+    let _ = state.save(&state_path);
+}
+
+/// Working directories of the sessions tagged `tag`.
+fn tagged_session_dirs(cfg: &Config, state: &state::State, tag: &str) -> Result<Vec<PathBuf>> {
+    let ids: Vec<&String> = state
+        .session_tags
+        .iter()
+        .filter(|(_, tags)| tags.contains(tag))
+        .map(|(id, _)| id)
+        .collect();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut dirs = Vec::new();
+    for path in sessions::all_rollout_files(cfg)? {
+        // Rollout file names end with the session id, so only tagged sessions are opened.
+        let name = path.file_stem().unwrap_or_default().as_encoded_bytes();
+        if !ids.iter().any(|id| name.ends_with(id.as_bytes())) {
+            continue;
+        }
+        if let Some(s) = sessions::session_from_rollout(cfg, &path)? {
+            dirs.push(s.cwd);
+        }
+    }
+    Ok(dirs)
+}
+
+/// Folders that aren't projects of their own: `/`, home, the configured roots,
+/// `projects.cwd_exclude` and whatever `projects.exclude` matches.
+fn is_excluded_cwd(cfg: &Config, path: &std::path::Path) -> bool {
+    if path.parent().is_none() || dirs::home_dir().is_some_and(|h| h == path) {
+        return true;
+    }
+    let same = |p: &PathBuf| config::normalize(p.clone()).is_ok_and(|p| p == path);
+    cfg.projects.roots.iter().any(same)
+        || cfg.projects.cwd_exclude.iter().any(same)
+        || ignore::Excludes::new(&cfg.projects.exclude).is_ok_and(|e| e.is_excluded(path))
+}
+
+/// `pin`/`unpin`'s argument as the absolute folder it would name.
+fn pin_path(target: &str) -> Result<PathBuf> {
+    let path = config::normalize(PathBuf::from(target))?;
+    std::path::absolute(&path).with_context(|| format!("failed to resolve {}", path.display()))
+}
+
+/// The pinned sessions that still exist, newest first.
+fn pinned_sessions(cfg: &Config, pins: &state::Pins) -> Vec<sessions::SessionItem> {
+    let mut pinned = sessions::find_sessions_by_ids(cfg, &pins.sessions).unwrap_or_default();
+    pinned.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    pinned
+}
+
+fn prioritize_current_target(
+    cfg: &Config,
+    index: &mut sessions::SessionIndex,
+    targets: &mut Vec<ProjectTarget>,
+) -> Result<()> {
+    if cfg.projects.prioritize_cwd == PrioritizeCwd::Never {
+        return Ok(());
+    }
     let Ok(cwd) = std::env::current_dir() else {
         return Ok(());
     };
@@ -281,6 +1385,21 @@ fn prioritize_current_target(cfg: &Config, targets: &mut Vec<ProjectTarget>) ->
         return Ok(());
     }
 
+    if cfg.projects.prioritize_cwd == PrioritizeCwd::InsideTargets {
+        // The deepest target containing the cwd (a package sub-target over its repo).
+        let pos = targets
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| cwd.starts_with(&t.path))
+            .max_by_key(|(_, t)| t.path.components().count())
+            .map(|(i, _)| i);
+        if let Some(pos) = pos {
+            let t = targets.remove(pos);
+            targets.insert(0, t);
+        }
+        return Ok(());
+    }
+
     // Prefer the git repo root when inside a repo; otherwise just use the cwd.
     let cur_path = sessions::git_root_for_path(&cwd).unwrap_or(cwd);
 
@@ -290,8 +1409,7 @@ fn prioritize_current_target(cfg: &Config, targets: &mut Vec<ProjectTarget>) ->
         return Ok(());
     }
 
-    // Only insert if it looks like a meaningful directory (avoid adding "/").
-    if cur_path.parent().is_none() {
+    if is_excluded_cwd(cfg, &cur_path) {
         return Ok(());
     }
 
@@ -301,73 +1419,510 @@ fn prioritize_current_target(cfg: &Config, targets: &mut Vec<ProjectTarget>) ->
         label: crate::pathfmt::basename(&cur_path),
         last_session_at: None,
         last_session_summary: None,
+        session_count: 0,
+        agents_md: projects::detect_agents_md(&cur_path),
+        codex_trust: codex_config::load(&cfg.sessions.codex_home)
+            .unwrap_or_default()
+            .trust_for(&cur_path),
+        parent: None,
+        git: None,
     };
 
-    // Best-effort: populate last-session metadata for display.
-    let meta_query = sessions::git_root_for_path(&cur_path)
-        .map(|repo_root| sessions::SessionQuery::ForRepoRoot {
-            repo_root,
-            limit: 1,
-        })
-        .unwrap_or_else(|| sessions::SessionQuery::ForCwd {
-            cwd: cur_path.clone(),
-            limit: 1,
-        });
-    if let Ok(mut items) = sessions::list_recent_sessions(cfg, meta_query) {
-        if let Some(s) = items.pop() {
-            t.last_session_at = s.created_at;
-            t.last_session_summary = s.summary;
-        }
+    // Best-effort: populate last-session metadata for display, scanning past the loaded page
+    // only when none of its sessions is in this folder.
+    let meta_query = sessions::query_for_target(&cur_path, 1);
+    if let Ok(Some(s)) = index.newest_matching(cfg, meta_query) {
+        t.last_session_at = s.created_at;
+        t.last_session_summary = s.summary;
     }
 
     targets.insert(0, t);
     Ok(())
 }
 
-fn open_config(config_path: &std::path::Path, dry_run: bool) -> Result<()> {
-    let cmd = if cfg!(target_os = "macos") {
+/// Refresh the full-text index while the picker (and then codex) runs; the index is written
+/// atomically, so being cut short when the process exits is harmless.
+fn spawn_index_refresh(cfg: Config, dir: PathBuf) {
+    std::thread::spawn(move || {
+        let mut index = search::FullTextIndex::load(&dir);
+        if let Ok(stats) = index.update(&cfg)
+            && (stats.indexed > 0 || stats.removed > 0)
+        {
+            let _ = index.save();
+        }
+    });
+}
+
+fn open_config(config_path: &std::path::Path, run: RunOpts) -> Result<()> {
+    ui::print_info(&format!("Opening config {}", config_path.display()));
+    run_command(system_open(config_path), run)
+}
+
+/// Open a file or folder with the platform's default handler.
+fn system_open(path: &std::path::Path) -> Command {
+    if cfg!(target_os = "macos") {
         let mut c = Command::new("open");
-        c.arg(config_path);
+        c.arg(path);
         c
     } else if cfg!(target_os = "windows") {
         let mut c = Command::new("cmd");
-        c.args(["/C", "start"]).arg(config_path);
+        c.args(["/C", "start"]).arg(path);
         c
     } else {
         let mut c = Command::new("xdg-open");
-        c.arg(config_path);
+        c.arg(path);
         c
+    }
+}
+
+/// A quick-launch argument that is written as a path (contains `/`, or starts with `.` or `~`)
+/// to an existing folder, which is launched as is instead of fuzzy-matched. Bare words are
+/// always queries, even when a folder of that name is in the current directory.
+fn existing_dir(arg: &str) -> Option<PathBuf> {
+    if !(arg.contains('/') || arg.starts_with('.') || arg.starts_with('~')) {
+        return None;
+    }
+    let path = config::normalize(PathBuf::from(arg)).ok()?;
+    if !path.is_dir() {
+        return None;
+    }
+    fs::canonicalize(path).ok()
+}
+
+fn dir_target(cfg: &Config, dir: PathBuf) -> ProjectTarget {
+    let mut target = ProjectTarget::new(dir, projects::TargetKind::ExplicitPath);
+    if let Some(label) = cfg.label_for(&target.path) {
+        target.label = label.to_string();
+    }
+    target
+}
+
+/// Whether `dir` is already a target: a configured path or a child of a root.
+fn is_configured(cfg: &Config, dir: &std::path::Path) -> bool {
+    let normalized = |p: &PathBuf| config::normalize(p.clone()).unwrap_or_else(|_| p.clone());
+    cfg.projects.paths.iter().map(normalized).any(|p| p == dir)
+        || cfg
+            .projects
+            .roots
+            .iter()
+            .map(normalized)
+            .any(|r| dir.parent() == Some(r.as_path()))
+}
+
+fn offer_to_add(
+    cfg: &mut Config,
+    config_path: &std::path::Path,
+    dir: &std::path::Path,
+    run: RunOpts,
+) -> Result<()> {
+    if !ui::confirm(&format!("Add {} as a target?", dir.display()))? {
+        return Ok(());
+    }
+    if run.dry_run {
+        ui::print_info(&format!("DRY RUN: add path {}", dir.display()));
+        return Ok(());
+    }
+    cfg.add_path(dir.to_path_buf(), None)?;
+    cfg.save(config_path)
+}
+
+/// The root a new project goes under: the only one configured, or the user's pick.
+fn pick_parent_root(cfg: &Config) -> Result<PathBuf> {
+    let roots = cfg
+        .projects
+        .roots
+        .iter()
+        .filter(|r| r.is_dir())
+        .cloned()
+        .collect::<Vec<_>>();
+    match roots.as_slice() {
+        [] => anyhow::bail!(
+            "No roots configured to put the project in. Add one with `codex-launch add-root <path>`."
+        ),
+        [only] => Ok(only.clone()),
+        _ => ui::pick_root(&roots),
+    }
+}
+
+/// Prompt for a new label for `target` and save it, adding the folder to `projects.paths` when
+/// it was only found by a root scan or from sessions. Returns the picker's status line.
+fn rename_target(
+    cfg: &mut Config,
+    config_path: &std::path::Path,
+    target: &ProjectTarget,
+    run: RunOpts,
+) -> Result<Option<String>> {
+    let prompt = format!("Label for {}:", target.path.display());
+    let Some(label) = ui::prompt_text(&prompt, Some(&target.label))? else {
+        return Ok(None);
     };
-    ui::print_info(&format!("Opening config {}", config_path.display()));
-    run_command(cmd, dry_run)
+    if label == target.label {
+        return Ok(None);
+    }
+    if run.dry_run {
+        return Ok(Some(format!(
+            "DRY RUN: would label {} \"{label}\"",
+            target.path.display()
+        )));
+    }
+    cfg.add_path(target.path.clone(), Some(label.clone()))?;
+    cfg.save(config_path)?;
+    Ok(Some(format!("Renamed {} to {label}", target.label)))
+}
+
+/// Prompt for a root and a name, create the folder (optionally as a git repo), make it a target
+/// and start Codex in it.
+fn create_project(cfg: &mut Config, config_path: &std::path::Path, run: RunOpts) -> Result<()> {
+    let parent = pick_parent_root(cfg)?;
+    let Some(name) = ui::prompt_text("Project name:", None)? else {
+        return Ok(());
+    };
+    if name.contains(['/', '\\']) || name == "." || name == ".." {
+        anyhow::bail!("not a folder name: {name}");
+    }
+    let dir = parent.join(&name);
+    if dir.exists() {
+        anyhow::bail!("already exists: {}", dir.display());
+    }
+    let git_init = ui::confirm("Initialize a git repository?")?;
+    if run.dry_run {
+        ui::print_info(&format!("DRY RUN: mkdir {}", dir.display()));
+        if git_init {
+            ui::print_info("DRY RUN: git init");
+        }
+    } else {
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        if git_init {
+            let status = Command::new("git")
+                .arg("init")
+                .arg("--quiet")
+                .current_dir(&dir)
+                .status()
+                .context("failed to run git init")?;
+            if !status.success() {
+                anyhow::bail!("git init failed ({status})");
+            }
+        } else {
+            // Only git repos are discovered under roots, so a plain folder needs an explicit entry.
+            cfg.add_path(dir.clone(), None)?;
+            cfg.save(config_path)?;
+        }
+    }
+    let kind = if git_init {
+        projects::TargetKind::RootChildGitRepo
+    } else {
+        projects::TargetKind::ExplicitPath
+    };
+    run_codex_new(cfg, &ProjectTarget::new(dir, kind), run)
+}
+
+/// Interactive `$SHELL` (or `cmd` on Windows) in the project folder.
+fn open_shell(target: &ProjectTarget, run: RunOpts) -> Result<()> {
+    let shell = if cfg!(target_os = "windows") {
+        "cmd".to_string()
+    } else {
+        std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string())
+    };
+    let cmd = match remote::RemoteTarget::parse(&target.path) {
+        Some(remote) => remote.ssh_command("exec \"$SHELL\" -l"),
+        None => {
+            let mut cmd = Command::new(shell);
+            cmd.current_dir(&target.path);
+            cmd
+        }
+    };
+    ui::print_info(&format!("Opening a shell in {}", target.path.display()));
+    run_command(cmd, run)
+}
+
+/// `$VISUAL` / `$EDITOR` (falling back to `vi`) on the project folder.
+fn open_editor(target: &ProjectTarget, run: RunOpts) -> Result<()> {
+    if remote::is_remote(&target.path) {
+        anyhow::bail!(
+            "{} is on another machine; open a shell there instead",
+            target.path.display()
+        );
+    }
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Editors are often configured with flags, e.g. `code --wait`.
+    let mut parts = editor.split_whitespace();
+    let mut cmd = Command::new(parts.next().unwrap_or("vi"));
+    cmd.args(parts).arg(".").current_dir(&target.path);
+    ui::print_info(&format!("Opening {} in {editor}", target.path.display()));
+    run_command(cmd, run)
+}
+
+pub(crate) fn run_codex_new(cfg: &Config, target: &ProjectTarget, run: RunOpts) -> Result<()> {
+    let dir = if cfg.launch.pick_subdir
+        && !remote::is_remote(&target.path)
+        && std::io::stdin().is_terminal()
+    {
+        let packages = projects::package_dirs(&target.path);
+        match ui::pick_subdir(&target.path, &packages)? {
+            Some(sub) => target.path.join(sub),
+            None => target.path.clone(),
+        }
+    } else {
+        target.path.clone()
+    };
+    let plan = codex_new_plan(cfg, target, &dir, run)?;
+    record_launch(cfg, &target.path, run);
+    plan.execute(run)
 }
 
-pub(crate) fn run_codex_new(cfg: &Config, target: &ProjectTarget, dry_run: bool) -> Result<()> {
+/// How a new session in `target` is started, with codex running in `dir` (the target folder or
+/// one below it), asking for profile/launch options as configured.
+pub(crate) fn codex_new_plan(
+    cfg: &Config,
+    target: &ProjectTarget,
+    dir: &std::path::Path,
+    run: RunOpts,
+) -> Result<launch::LaunchPlan> {
+    let overridden;
+    let cfg = match cfg.with_overrides(dir)? {
+        Some(c) => {
+            overridden = c;
+            &overridden
+        }
+        None => cfg,
+    };
+    let agent = cfg.agent_for(dir);
+    let agent_cfg;
+    let cfg = match agent {
+        Some(name) => {
+            agent_cfg = cfg.with_agent(name)?;
+            &agent_cfg
+        }
+        None => cfg,
+    };
+    let remote = remote::RemoteTarget::parse(&target.path);
     let mut cmd = Command::new(&cfg.codex.bin);
-    cmd.current_dir(&target.path);
-    cmd.args(cfg.codex.args.iter());
+    if remote.is_none() {
+        cmd.current_dir(dir);
+    }
+    let args = cfg.codex_args();
+    cmd.args(args.iter());
+    if (cfg.codex.pick_profile || cfg.codex.launch_options) && std::io::stdin().is_terminal() {
+        let codex_cfg = codex_config::load(&cfg.sessions.codex_home).unwrap_or_default();
+        if cfg.codex.pick_profile
+            && !has_profile_arg(&args)
+            && !codex_cfg.profiles.is_empty()
+            && let Some(profile) = ui::pick_profile(&codex_cfg)?
+        {
+            cmd.args(["--profile", &profile]);
+        }
+        if cfg.codex.launch_options {
+            let mut models = codex_cfg.known_models();
+            for m in cfg.codex.models.iter() {
+                if !models.contains(m) {
+                    models.push(m.clone());
+                }
+            }
+            cmd.args(ui::pick_launch_options(&codex_cfg, &models)?);
+        }
+    }
 
-    ui::print_info(&format!("Launching Codex in {}", target.path.display()));
-    run_command(cmd, dry_run)
+    let container = cfg.container_for(&target.path);
+    let devcontainer = match remote {
+        None if container.is_none() => use_devcontainer(cfg, &target.path)?,
+        _ => None,
+    };
+    let mut where_ = String::new();
+    if let Some(remote) = &remote {
+        cmd = remote.codex_command(&cfg.codex.remote_bin, &cfg.launch.env, cmd.get_args());
+    } else if let Some(name) = container {
+        cmd = launch::container_exec(cfg, name, dir, cmd.get_args());
+        where_ = format!(" (container {name})");
+    } else if let Some(dc) = &devcontainer {
+        cmd = dc.exec_command(&cfg.codex.remote_bin, &cfg.launch.env, cmd.get_args());
+        where_ = " (devcontainer)".to_string();
+    } else if agent.is_none() && !run.dry_run && run.print_cmd.is_none() {
+        warn_if_untrusted(cfg, dir);
+    }
+    ui::print_info(&format!(
+        "Launching {} in {}{where_}",
+        agent.unwrap_or("Codex"),
+        dir.display()
+    ));
+    let local = remote.is_none() && container.is_none() && devcontainer.is_none();
+    let mut plan = launch::LaunchPlan::new(cfg, cmd).titled(cfg, &target.label, dir);
+    plan.runs_codex &= local && agent.is_none();
+    if local {
+        plan = plan.with_project_env(cfg, dir);
+    }
+    if let Some(dc) = devcontainer {
+        plan.hooks.push(dc.up_command());
+    }
+    Ok(plan)
+}
+
+/// The target's devcontainer when `launch.devcontainer` says to run inside it.
+fn use_devcontainer(
+    cfg: &Config,
+    dir: &std::path::Path,
+) -> Result<Option<devcontainer::Devcontainer>> {
+    let use_it = match cfg.launch.devcontainer {
+        config::DevcontainerMode::Never => false,
+        config::DevcontainerMode::Always => true,
+        config::DevcontainerMode::Ask => {
+            devcontainer::config_path(dir).is_some()
+                && std::io::stdin().is_terminal()
+                && ui::confirm("Start Codex inside the devcontainer?")?
+        }
+    };
+    if !use_it {
+        return Ok(None);
+    }
+    devcontainer::load(dir)
 }
 
-pub(crate) fn run_codex_resume(cfg: &Config, session: &SessionItem, dry_run: bool) -> Result<()> {
+pub(crate) fn run_codex_resume(cfg: &Config, session: &SessionItem, run: RunOpts) -> Result<()> {
+    // Sessions known only from history.jsonl have no rollout to check.
+    if let Some(rollout) = session.path.as_deref()
+        && let Err(e) = sessions::check_rollout(rollout)
+    {
+        return match pick_instead_of_broken(cfg, session, e)? {
+            Some(other) => run_codex_resume(cfg, &other, run),
+            None => Err(exit::Exit::Cancelled.into()),
+        };
+    }
+    let overridden;
+    let cfg = match cfg.with_overrides(&session.cwd)? {
+        Some(c) => {
+            overridden = c;
+            &overridden
+        }
+        None => cfg,
+    };
+    let agent = cfg.agent_for_provider(session.model_provider.as_deref());
+    let agent_cfg;
+    let cfg = match agent {
+        Some(name) => {
+            agent_cfg = cfg.with_agent(name)?;
+            &agent_cfg
+        }
+        None => cfg,
+    };
     let mut cmd = Command::new(&cfg.codex.bin);
-    cmd.current_dir(&session.cwd);
-    cmd.args(cfg.codex.args.iter());
-    cmd.arg("resume");
-    cmd.arg(&session.id);
+    // Sessions known only from history.jsonl have no cwd; codex then resumes where we are.
+    if !session.cwd.as_os_str().is_empty() {
+        cmd.current_dir(&session.cwd);
+    }
+    cmd.args(cfg.codex_args());
+    cmd.args(cfg.codex.resume_args(&session.id)?);
 
     ui::print_info(&format!(
         "Resuming {} in {}",
         session.id,
         session.cwd.display()
     ));
-    run_command(cmd, dry_run)
+    // Named after the repo rather than a subfolder, so tmux mode finds the project's session.
+    let project = sessions::git_root_for_path(&session.cwd).unwrap_or(session.cwd.clone());
+    let mut plan = launch::LaunchPlan::new(cfg, cmd)
+        .titled(cfg, &pathfmt::basename(&project), &session.cwd)
+        .with_project_env(cfg, &session.cwd);
+    plan.reuse_session = false;
+    plan.runs_codex &= agent.is_none();
+    record_launch(cfg, &project, run);
+    plan.execute(run)
+}
+
+fn print_where_sessions(cfg: &Config) -> Result<()> {
+    let sessions_dir = cfg.sessions.codex_home.join("sessions");
+    println!("codex_home: {}", cfg.sessions.codex_home.display());
+    if !sessions_dir.is_dir() {
+        println!("sessions:   {} (missing)", sessions_dir.display());
+        return Ok(());
+    }
+    println!("sessions:   {}", sessions_dir.display());
+    let summary = sessions::store_summary(cfg)?;
+    for note in summary.skipped.iter() {
+        ui::print_warn(note);
+    }
+    println!(
+        "rollouts:   {} ({} with session metadata)",
+        summary.rollouts, summary.sessions
+    );
+    if let (Some(oldest), Some(newest)) = (summary.oldest, summary.newest) {
+        println!("dates:      {} to {}", oldest.date(), newest.date());
+    }
+    println!(
+        "in scope:   {} (under configured targets; the rest show in Sessions (all))",
+        summary.scoped
+    );
+    Ok(())
+}
+
+/// Report a session codex can't resume and, when interactive, offer another session of the
+/// same project. Without a terminal (or other sessions) the error is returned as-is.
+fn pick_instead_of_broken(
+    cfg: &Config,
+    broken: &SessionItem,
+    err: anyhow::Error,
+) -> Result<Option<SessionItem>> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(err);
+    }
+    let query = if broken.cwd.as_os_str().is_empty() {
+        sessions::SessionQuery::All {
+            limit: cfg.sessions.limit + 1,
+        }
+    } else {
+        sessions::query_for_target(&broken.cwd, cfg.sessions.limit + 1)
+    };
+    let others: Vec<SessionItem> = sessions::list_recent_sessions(cfg, query)?
+        .into_iter()
+        .filter(|s| s.id != broken.id)
+        .collect();
+    if others.is_empty() {
+        return Err(err);
+    }
+    ui::print_warn(&format!("{err:#}"));
+    if !ui::confirm("Pick another session?")? {
+        return Ok(None);
+    }
+    ui::pick_session(&others).map(Some)
 }
 
-fn run_command(mut cmd: Command, dry_run: bool) -> Result<()> {
-    if dry_run {
+fn has_profile_arg(args: &[String]) -> bool {
+    args.iter()
+        .any(|a| a == "--profile" || a == "-p" || a.starts_with("--profile="))
+}
+
+/// A folder marked untrusted in Codex's config means approval prompts; say so before launching.
+fn warn_if_untrusted(cfg: &Config, dir: &std::path::Path) {
+    let Some(codex_cfg) = codex_config::load(&cfg.sessions.codex_home) else {
+        return;
+    };
+    let policy = codex_cfg
+        .approval_policy
+        .as_deref()
+        .map(|p| format!(" (approval_policy = {p})"))
+        .unwrap_or_default();
+    // `Unknown` is every folder nobody has trusted yet, so it isn't worth a warning.
+    if codex_cfg.trust_for(dir) == codex_config::Trust::Untrusted {
+        ui::print_warn(&format!(
+            "{} is marked untrusted in Codex's config; expect approval prompts{policy}",
+            dir.display()
+        ));
+    }
+}
+
+/// How the final command should be handled: executed, described, or printed for a wrapper.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RunOpts {
+    pub dry_run: bool,
+    pub print_cmd: Option<ui::CommandFormat>,
+}
+
+pub(crate) fn run_command(mut cmd: Command, run: RunOpts) -> Result<()> {
+    if let Some(format) = run.print_cmd {
+        println!("{}", ui::render_command(&cmd, &[], format));
+        return Ok(());
+    }
+    if run.dry_run {
         ui::print_info(&format!("DRY RUN: {}", ui::format_command(&cmd)));
         return Ok(());
     }
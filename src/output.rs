@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::projects::ProjectTarget;
+use crate::sessions::{self, SessionItem};
+use crate::timefmt;
+
+/// `id<TAB>created_at<TAB>cwd<TAB>summary`, one session per line.
+pub fn print_sessions_tsv(items: &[SessionItem]) {
+    for s in items {
+        println!("{}", session_tsv_line(s));
+    }
+}
+
+fn session_tsv_line(s: &SessionItem) -> String {
+    format!(
+        "{}\t{}\t{}\t{}",
+        s.id,
+        s.created_at.as_deref().unwrap_or(""),
+        s.cwd.display(),
+        s.summary.as_deref().unwrap_or("")
+    )
+}
+
+/// `id<TAB>cwd<TAB>rollout path` of one session (`-` for the path when it has no rollout).
+pub fn print_session_ref(s: &SessionItem) {
+    let path = s
+        .path
+        .as_deref()
+        .map_or_else(|| "-".to_string(), |p| p.display().to_string());
+    println!("{}\t{}\t{path}", s.id, s.cwd.display());
+}
+
+/// Targets as aligned columns under a header row.
+pub fn print_targets_table(targets: &[ProjectTarget]) {
+    let mut rows = vec![[
+        "LABEL".to_string(),
+        "PATH".to_string(),
+        "KIND".to_string(),
+        "LAST SESSION".to_string(),
+        "SESSIONS".to_string(),
+    ]];
+    for t in targets {
+        let last = t
+            .last_session_at
+            .as_deref()
+            .and_then(timefmt::parse_rfc3339)
+            .map(|dt| format!("{} {}", timefmt::format_age(dt), timefmt::format_short(dt)))
+            .unwrap_or_else(|| "-".to_string());
+        rows.push([
+            t.label.clone(),
+            t.path.display().to_string(),
+            t.kind.name().to_string(),
+            last,
+            t.session_count.to_string(),
+        ]);
+    }
+    let mut widths = [0; 5];
+    for row in rows.iter() {
+        for (w, cell) in widths.iter_mut().zip(row.iter()) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    for row in rows.iter() {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+pub fn print_sessions_json(items: &[SessionItem]) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(items)?);
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectGroup<'a> {
+    project: PathBuf,
+    count: usize,
+    sessions: Vec<&'a SessionItem>,
+}
+
+/// Cluster sessions under their git repo root (or cwd outside a repo), most recent group first.
+fn group_by_project(items: &[SessionItem]) -> Vec<ProjectGroup<'_>> {
+    let mut groups: Vec<ProjectGroup> = Vec::new();
+    for s in items {
+        let project = sessions::git_root_for_path(&s.cwd).unwrap_or_else(|| s.cwd.clone());
+        match groups.iter_mut().find(|g| g.project == project) {
+            Some(g) => {
+                g.count += 1;
+                g.sessions.push(s);
+            }
+            None => groups.push(ProjectGroup {
+                project,
+                count: 1,
+                sessions: vec![s],
+            }),
+        }
+    }
+    groups
+}
+
+pub fn print_grouped_by_project(items: &[SessionItem], json: bool) -> Result<()> {
+    let groups = group_by_project(items);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&groups)?);
+        return Ok(());
+    }
+    for (i, g) in groups.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let noun = if g.count == 1 { "session" } else { "sessions" };
+        println!("{} ({} {noun})", g.project.display(), g.count);
+        for s in g.sessions.iter() {
+            println!("  {}", session_tsv_line(s));
+        }
+    }
+    Ok(())
+}
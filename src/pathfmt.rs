@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use crate::config::PathStyle;
+
 pub fn compact_path(p: &Path, max_chars: usize) -> String {
     let mut s = p.display().to_string();
     if let Some(home) = dirs::home_dir() {
@@ -24,6 +26,14 @@ pub fn compact_path(p: &Path, max_chars: usize) -> String {
     format!("…{tail}")
 }
 
+/// `p` for a list row: compacted to `max_chars`, or in full.
+pub fn row_path(p: &Path, max_chars: usize, style: PathStyle) -> String {
+    match style {
+        PathStyle::Compact => compact_path(p, max_chars),
+        PathStyle::Full => p.display().to_string(),
+    }
+}
+
 pub fn basename(p: &Path) -> String {
     p.file_name()
         .map(|s| s.to_string_lossy().to_string())
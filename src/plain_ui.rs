@@ -0,0 +1,417 @@
+use std::io::{self, BufRead, Write};
+
+use anyhow::Result;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::batch::BatchAction;
+use crate::config::ProjectSort;
+use crate::projects::ProjectTarget;
+use crate::sessions::{Outcome, SessionItem};
+use crate::state::Pins;
+use crate::timefmt;
+use crate::tui::{self, MenuItem, PickerContext, ProjectPick, SessionPages};
+
+/// How many rows a list prints before asking for a filter instead.
+const PLAIN_ROWS: usize = 20;
+
+/// `--plain-ui` (or `display.plain_ui`), or a terminal that can't redraw (`TERM=dumb`).
+pub fn wanted(flag: bool, configured: bool) -> bool {
+    flag || configured || std::env::var("TERM").is_ok_and(|t| t == "dumb")
+}
+
+/// The picker as numbered lists and typed answers, for screen readers and dumb terminals:
+/// everything the full-screen picker does, printed line by line with nothing redrawn.
+pub fn pick_project(
+    targets: &[ProjectTarget],
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    pins: &mut Pins,
+    sort: ProjectSort,
+) -> Result<ProjectPick> {
+    if let Some(status) = &ctx.status {
+        println!("{status}");
+    }
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut filter = String::new();
+    loop {
+        let shown = tui::shown_targets(targets, &matcher, &filter, pins, sort);
+        println!();
+        let printed = print_list(
+            &format!("Projects ({})", shown.len()),
+            shown.iter().map(|i| project_line(&targets[*i], ctx, pins)),
+        );
+        println!(
+            "Type a number to open that project (or pin it from its menu), /text to filter, s for sessions in your projects, a for all sessions, L and numbers to launch several in tmux, c to create a project, o for the config, or q to quit."
+        );
+        let Some(answer) = ask()? else {
+            return Ok(ProjectPick::Quit);
+        };
+        match answer.as_str() {
+            "q" => return Ok(ProjectPick::Quit),
+            "o" => return Ok(ProjectPick::OpenConfig),
+            "c" => return Ok(ProjectPick::CreateProject),
+            "s" | "a" => {
+                if let Some(pick) = pick_tab_session(pages, ctx, pins, answer == "s")? {
+                    return Ok(pick);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(text) = answer.strip_prefix('/') {
+            filter = text.trim().to_string();
+        } else if let Some(rest) = answer.strip_prefix("L ") {
+            match numbers(rest, printed) {
+                Some(picked) => {
+                    let picked = picked.iter().map(|n| targets[shown[*n]].clone());
+                    return Ok(ProjectPick::LaunchAll(picked.collect()));
+                }
+                None => println!("No such projects: {rest}"),
+            }
+        } else if let Some(n) = number(&answer, printed) {
+            if let Some(pick) = project_menu(&targets[shown[n]], pages, ctx, pins)? {
+                return Ok(pick);
+            }
+        } else {
+            println!("Not understood: {answer}");
+        }
+    }
+}
+
+/// A line of the project menu.
+#[derive(Clone, Copy)]
+enum Entry {
+    Menu(MenuItem),
+    NewWithOptions,
+    Rename,
+    TogglePin,
+}
+
+/// What can be done with one project; `None` goes back to the project list.
+fn project_menu(
+    target: &ProjectTarget,
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    pins: &mut Pins,
+) -> Result<Option<ProjectPick>> {
+    // The full-screen picker's `N`, `R` and `p` keys, as menu entries.
+    let mut items: Vec<(String, Entry)> = tui::menu_items(ctx.actions)
+        .into_iter()
+        .map(|(label, item)| (label, Entry::Menu(item)))
+        .collect();
+    items.insert(
+        1,
+        (
+            "New session with options…".to_string(),
+            Entry::NewWithOptions,
+        ),
+    );
+    items.push(("Rename…".to_string(), Entry::Rename));
+    items.push((String::new(), Entry::TogglePin));
+    loop {
+        let pinned = pins.projects.contains(&target.path);
+        println!();
+        let printed = print_list(
+            &format!("{} ({})", target.label, target.path.display()),
+            items.iter().map(|(label, entry)| match entry {
+                Entry::TogglePin if pinned => "Unpin".to_string(),
+                Entry::TogglePin => "Pin to the top".to_string(),
+                _ => label.clone(),
+            }),
+        );
+        println!("Type a number to choose, b to go back, or q to quit.");
+        let Some(answer) = ask()? else {
+            return Ok(Some(ProjectPick::Quit));
+        };
+        match answer.as_str() {
+            "q" => return Ok(Some(ProjectPick::Quit)),
+            "b" => return Ok(None),
+            _ => {}
+        }
+        let Some(n) = number(&answer, printed) else {
+            println!("Not understood: {answer}");
+            continue;
+        };
+        let target = target.clone();
+        let pick = match items[n].1 {
+            Entry::TogglePin => {
+                let verb = tui::pin_verb(pins.toggle_project(&target.path));
+                println!("{verb} {}", target.label);
+                continue;
+            }
+            Entry::NewWithOptions => ProjectPick::NewWithOptions(target),
+            Entry::Rename => ProjectPick::Rename(target),
+            Entry::Menu(MenuItem::New) => ProjectPick::New(target),
+            Entry::Menu(MenuItem::NewInSubdir) => ProjectPick::NewInSubdir(target),
+            Entry::Menu(MenuItem::ResumeLatest) => match tui::latest_session(&target, pages)? {
+                Some(s) => ProjectPick::Resume(s),
+                None => {
+                    println!("No sessions in {} yet.", target.label);
+                    continue;
+                }
+            },
+            Entry::Menu(MenuItem::Browse) => {
+                let limit = (ctx.project_limit)(&target.path);
+                match pick_project_session(&target, pages, ctx, pins, limit)? {
+                    Some(pick) => pick,
+                    None => continue,
+                }
+            }
+            Entry::Menu(MenuItem::Shell) => ProjectPick::Shell(target),
+            Entry::Menu(MenuItem::Editor) => ProjectPick::Editor(target),
+            Entry::Menu(MenuItem::Custom(i)) => ProjectPick::Action {
+                target,
+                command: ctx.actions[i].command.clone(),
+            },
+        };
+        return Ok(Some(pick));
+    }
+}
+
+/// The Sessions tabs: sessions in configured projects (`scoped`) or all of them.
+fn pick_tab_session(
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    pins: &mut Pins,
+    scoped: bool,
+) -> Result<Option<ProjectPick>> {
+    let title = if scoped {
+        "Sessions in your projects"
+    } else {
+        "All sessions"
+    };
+    pick_session_from(title, ctx, pages, pins, |pages, more| {
+        if more {
+            pages.load_more()?;
+        }
+        Ok(if scoped {
+            pages.scoped.clone()
+        } else {
+            pages.all.clone()
+        })
+    })
+}
+
+/// A project's sessions, growing by a page on `m`.
+fn pick_project_session(
+    target: &ProjectTarget,
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    pins: &mut Pins,
+    mut limit: usize,
+) -> Result<Option<ProjectPick>> {
+    let title = format!("Sessions in {}", target.label);
+    pick_session_from(&title, ctx, pages, pins, |pages, more| {
+        if more {
+            limit += pages.page_size;
+        }
+        tui::load_project_sessions(target, pages, limit)
+    })
+}
+
+/// List sessions from `load` (asked again with `true` for another page) and act on them by
+/// number: resume one, pin some, or apply a batch action to several.
+fn pick_session_from(
+    title: &str,
+    ctx: &PickerContext,
+    pages: &mut SessionPages,
+    pins: &mut Pins,
+    mut load: impl FnMut(&mut SessionPages, bool) -> Result<Vec<SessionItem>>,
+) -> Result<Option<ProjectPick>> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut sessions = load(pages, false)?;
+    let mut filter = String::new();
+    loop {
+        let shown = tui::shown_sessions(&sessions, &matcher, &filter, pins);
+        println!();
+        let printed = print_list(
+            &format!("{title} ({})", shown.len()),
+            shown.iter().map(|i| session_line(&sessions[*i], ctx, pins)),
+        );
+        println!(
+            "Type a number to resume that session, /text to filter, m for more, or a letter and numbers to act on sessions: p pin or unpin, d delete, a archive, t tag, e export, k signal (for example: a 2 3). b goes back, q quits."
+        );
+        let Some(answer) = ask()? else {
+            return Ok(Some(ProjectPick::Quit));
+        };
+        match answer.as_str() {
+            "q" => return Ok(Some(ProjectPick::Quit)),
+            "b" => return Ok(None),
+            "m" => {
+                sessions = load(pages, true)?;
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(text) = answer.strip_prefix('/') {
+            filter = text.trim().to_string();
+            continue;
+        }
+        if let Some(n) = number(&answer, printed) {
+            return Ok(Some(ProjectPick::Resume(sessions[shown[n]].clone())));
+        }
+        let (letter, rest) = answer.split_once(' ').unwrap_or((&answer, ""));
+        if letter == "p" {
+            match numbers(rest, printed) {
+                Some(picked) => {
+                    for n in picked {
+                        println!("{}", tui::toggle_pin(pins, &sessions[shown[n]]));
+                    }
+                }
+                None => println!("Which sessions? For example: p 1 3"),
+            }
+            continue;
+        }
+        let action = match letter {
+            "d" => BatchAction::Delete,
+            "a" => BatchAction::Archive,
+            "t" => BatchAction::Tag,
+            "e" => BatchAction::Export,
+            "k" => BatchAction::Signal,
+            _ => {
+                println!("Not understood: {answer}");
+                continue;
+            }
+        };
+        match numbers(rest, printed) {
+            Some(picked) => {
+                let sessions = picked.iter().map(|n| sessions[shown[*n]].clone());
+                return Ok(Some(ProjectPick::Batch {
+                    action,
+                    sessions: sessions.collect(),
+                }));
+            }
+            None => println!("Which sessions? For example: {letter} 1 3"),
+        }
+    }
+}
+
+/// Print the first `PLAIN_ROWS` lines numbered from 1; returns how many were printed, which
+/// is as far as typed numbers reach.
+fn print_list(title: &str, lines: impl ExactSizeIterator<Item = String>) -> usize {
+    let total = lines.len();
+    println!("{title}:");
+    for (i, line) in lines.take(PLAIN_ROWS).enumerate() {
+        println!("{}. {line}", i + 1);
+    }
+    if total > PLAIN_ROWS {
+        println!(
+            "{} more not shown; type /text to narrow the list.",
+            total - PLAIN_ROWS
+        );
+    }
+    total.min(PLAIN_ROWS)
+}
+
+/// A project as a sentence rather than padded columns.
+fn project_line(t: &ProjectTarget, ctx: &PickerContext, pins: &Pins) -> String {
+    let mut line = format!("{}, {}", t.label, t.path.display());
+    if pins.projects.contains(&t.path) {
+        line.push_str(", pinned");
+    }
+    if let Some(when) = t
+        .last_session_at
+        .as_deref()
+        .and_then(timefmt::parse_rfc3339)
+    {
+        line.push_str(&format!(
+            ", last session {}",
+            timefmt::format_when(when, ctx.display.dates)
+        ));
+    }
+    if let Some(summary) = t.last_session_summary.as_deref() {
+        line.push_str(&format!(": {}", one_line(summary)));
+    }
+    line
+}
+
+fn session_line(s: &SessionItem, ctx: &PickerContext, pins: &Pins) -> String {
+    let when = s
+        .created_at
+        .as_deref()
+        .and_then(timefmt::parse_rfc3339)
+        .map(|dt| timefmt::format_when(dt, ctx.display.dates))
+        .unwrap_or_else(|| "unknown time".to_string());
+    let what = s
+        .summary
+        .as_deref()
+        .map(one_line)
+        .unwrap_or_else(|| format!("session {}", s.id));
+    let mut line = format!("{when}, {what}, in {}", s.cwd.display());
+    match s.outcome {
+        Outcome::Failed => line.push_str(", failed"),
+        Outcome::Running => line.push_str(", still running"),
+        Outcome::Completed | Outcome::Unknown => {}
+    }
+    if pins.sessions.contains(&s.id) {
+        line.push_str(", pinned");
+    }
+    line
+}
+
+pub(crate) fn one_line(s: &str) -> String {
+    let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    match s.char_indices().nth(100) {
+        Some((i, _)) => format!("{}…", &s[..i]),
+        None => s,
+    }
+}
+
+/// Read one answer; `None` at end of input.
+fn ask() -> Result<Option<String>> {
+    print!("> ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// A 1-based number as a 0-based index below `len`.
+fn number(s: &str, len: usize) -> Option<usize> {
+    s.parse::<usize>()
+        .ok()
+        .filter(|n| (1..=len).contains(n))
+        .map(|n| n - 1)
+}
+
+/// 1-based numbers separated by spaces or commas, as 0-based indices below `len`.
+fn numbers(s: &str, len: usize) -> Option<Vec<usize>> {
+    let picked = s
+        .split([' ', ','])
+        .filter(|w| !w.is_empty())
+        .map(|w| number(w, len))
+        .collect::<Option<Vec<_>>>()?;
+    (!picked.is_empty()).then_some(picked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_accepts_only_listed_rows() {
+        assert_eq!(number("1", 3), Some(0));
+        assert_eq!(number("3", 3), Some(2));
+        assert_eq!(number("0", 3), None);
+        assert_eq!(number("4", 3), None);
+        assert_eq!(number("-1", 3), None);
+        assert_eq!(number("two", 3), None);
+        assert_eq!(number("1", 0), None);
+    }
+
+    #[test]
+    fn numbers_splits_on_spaces_and_commas() {
+        assert_eq!(numbers("1 3", 3), Some(vec![0, 2]));
+        assert_eq!(numbers("2,3", 3), Some(vec![1, 2]));
+        assert_eq!(numbers(" 1,  2 ", 3), Some(vec![0, 1]));
+        assert_eq!(
+            numbers("1 4", 3),
+            None,
+            "one number past the list spoils the lot"
+        );
+        assert_eq!(numbers("", 3), None);
+        assert_eq!(numbers(" , ", 3), None);
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// A running codex process and the folder it runs in.
+#[derive(Debug, Clone)]
+pub struct CodexProcess {
+    pub pid: u32,
+    pub command: String,
+    pub cwd: PathBuf,
+}
+
+/// Codex processes from the process table (`ps`), with their working directories.
+pub fn codex_processes() -> Result<Vec<CodexProcess>> {
+    let out = Command::new("ps")
+        .args(["-axo", "pid=,args="])
+        .output()
+        .context("failed to run ps")?;
+    let me = std::process::id();
+    let mut procs = Vec::new();
+    for line in String::from_utf8_lossy(&out.stdout).lines() {
+        let Some((pid, command)) = line.trim_start().split_once(' ') else {
+            continue;
+        };
+        let Ok(pid) = pid.parse::<u32>() else {
+            continue;
+        };
+        if pid == me || !is_codex(command) {
+            continue;
+        }
+        if let Some(cwd) = process_cwd(pid) {
+            procs.push(CodexProcess {
+                pid,
+                command: command.trim().to_string(),
+                cwd,
+            });
+        }
+    }
+    Ok(procs)
+}
+
+/// `codex …` itself, or the npm wrapper run by node (`node …/bin/codex.js …`, or `node
+/// …/bin/codex …` through its shebang). Only the program and node's script count, so an editor
+/// or pager with a `codex` argument (`vim codex`) is left alone.
+fn is_codex(command: &str) -> bool {
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    match program.rsplit('/').next().unwrap_or(program) {
+        "codex" => true,
+        "node" | "nodejs" => words
+            .next()
+            .is_some_and(|script| script.ends_with("/codex.js") || script.ends_with("/codex")),
+        _ => false,
+    }
+}
+
+fn process_cwd(pid: u32) -> Option<PathBuf> {
+    if let Ok(p) = std::fs::read_link(format!("/proc/{pid}/cwd")) {
+        return Some(p);
+    }
+    // No procfs (macOS): `lsof` prints the cwd as an `n<path>` field.
+    let out = Command::new("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-d", "cwd", "-Fn"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .find_map(|l| l.strip_prefix('n').map(PathBuf::from))
+}
+
+/// `kill -s <signal> <pid>`.
+pub fn signal(pid: u32, signal: &str) -> Result<()> {
+    let status = Command::new("kill")
+        .args(["-s", signal, &pid.to_string()])
+        .status()
+        .context("failed to run kill")?;
+    if !status.success() {
+        anyhow::bail!("kill -s {signal} {pid} failed ({status})");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_codex_and_its_node_wrapper_only() {
+        assert!(is_codex("codex"));
+        assert!(is_codex("/opt/homebrew/bin/codex resume 0199a2"));
+        assert!(is_codex(
+            "node /usr/lib/node_modules/@openai/codex/bin/codex.js --model o3"
+        ));
+        assert!(is_codex("/usr/bin/node /usr/local/bin/codex"));
+
+        assert!(!is_codex("vim codex"));
+        assert!(!is_codex("less /tmp/codex"));
+        assert!(!is_codex("node server.js codex"));
+        assert!(!is_codex("codex-launch last"));
+        assert!(!is_codex("node ./codex.json"));
+    }
+}
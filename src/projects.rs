@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -6,10 +6,17 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::config::Config;
+use crate::codex_config::{self, CodexCliConfig, Trust};
+use crate::config::{Config, DateStyle, PathStyle};
+use crate::ignore::{Excludes, IGNORE_FILE_NAME, IgnoreRules};
 use crate::pathfmt;
-use crate::sessions;
+use crate::remote::RemoteTarget;
+use crate::repoinfo::GitStatus;
+use crate::sessions::{SessionIndex, SessionItem};
+use crate::target_cache::{self, TargetCache};
 use crate::timefmt;
+use crate::ui;
+use crate::workspace;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TargetKind {
@@ -17,6 +24,41 @@ pub enum TargetKind {
     RootChildGitRepo,
     ExplicitPath,
     SessionHistory,
+    /// `ssh://host/path` in `projects.paths`.
+    Remote,
+}
+
+impl TargetKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetKind::CurrentWorkingDir => "cwd",
+            TargetKind::RootChildGitRepo => "root",
+            TargetKind::ExplicitPath => "path",
+            TargetKind::SessionHistory => "session",
+            TargetKind::Remote => "ssh",
+        }
+    }
+}
+
+/// Where (if anywhere) a target carries Codex agent instructions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgentsMd {
+    #[default]
+    None,
+    /// `AGENTS.md` (or `AGENTS.override.md`) at the target root.
+    Root,
+    /// Only in a direct subdirectory (e.g. `crates/foo/AGENTS.md`).
+    Nested,
+}
+
+impl AgentsMd {
+    pub fn marker(self) -> char {
+        match self {
+            AgentsMd::None => ' ',
+            AgentsMd::Root => 'A',
+            AgentsMd::Nested => 'a',
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,16 +68,65 @@ pub struct ProjectTarget {
     pub label: String,
     pub last_session_at: Option<String>,
     pub last_session_summary: Option<String>,
+    /// Sessions in this target among the last `projects.sessions_limit` scanned.
+    #[serde(default)]
+    pub session_count: usize,
+    pub agents_md: AgentsMd,
+    pub codex_trust: Trust,
+    /// For a sub-target (a package inside a monorepo), the target it was found in.
+    #[serde(default)]
+    pub parent: Option<PathBuf>,
+    /// Branch and uncommitted changes, when read (`display.git_status`); never cached.
+    #[serde(skip)]
+    pub git: Option<GitStatus>,
 }
 
 impl fmt::Display for ProjectTarget {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let path = pathfmt::compact_path(&self.path, 52);
+        f.write_str(&self.row_text(
+            self.git.is_some(),
+            None,
+            None,
+            DateStyle::default(),
+            PathStyle::default(),
+        ))
+    }
+}
+
+impl ProjectTarget {
+    /// A target with no session history yet.
+    pub fn new(path: PathBuf, kind: TargetKind) -> Self {
+        Self {
+            label: display_name(&path),
+            agents_md: detect_agents_md(&path),
+            path,
+            kind,
+            last_session_at: None,
+            last_session_summary: None,
+            session_count: 0,
+            codex_trust: Trust::Unknown,
+            parent: None,
+            git: None,
+        }
+    }
+
+    /// The picker row; `git` (a branch column, empty until `self.git` is read), `head` (HEAD's
+    /// age and subject, empty outside repos) and `launched` (launch count and time) add columns
+    /// before the last session's summary.
+    pub fn row_text(
+        &self,
+        git: bool,
+        head: Option<&str>,
+        launched: Option<&str>,
+        dates: DateStyle,
+        paths: PathStyle,
+    ) -> String {
+        let path = pathfmt::row_path(&self.path, 52, paths);
         let last = self
             .last_session_at
             .as_deref()
             .and_then(timefmt::parse_rfc3339)
-            .map(|dt| format!("{} {}", timefmt::format_age(dt), timefmt::format_short(dt)))
+            .map(|dt| timefmt::format_when(dt, dates))
             .unwrap_or_else(|| "-".to_string());
         let summary = self
             .last_session_summary
@@ -43,51 +134,94 @@ impl fmt::Display for ProjectTarget {
             .map(|s| truncate_one_line(s, 64))
             .unwrap_or_default();
 
-        if summary.is_empty() {
-            write!(f, "{:<22}  {:<52}  {}", self.label, path, last)
-        } else {
-            write!(f, "{:<22}  {:<52}  {}  {}", self.label, path, last, summary)
+        let marker = format!("{}{}", self.agents_md.marker(), self.codex_trust.marker());
+        let mut row = format!("{marker} {:<22}  {:<52}", self.label, path);
+        if git {
+            let branch = self.git.as_ref().map(GitStatus::label).unwrap_or_default();
+            row.push_str(&format!("  {:<20}", truncate_one_line(&branch, 20)));
+        }
+        row.push_str(&format!("  {last:<15}"));
+        if let Some(head) = head {
+            row.push_str(&format!("  {:<32}", truncate_one_line(head, 32)));
+        }
+        if let Some(launched) = launched {
+            row.push_str(&format!("  {launched:<30}"));
         }
+        if !summary.is_empty() {
+            row.push_str(&format!("  {summary}"));
+        }
+        row
+    }
+}
+
+pub fn gather_targets(cfg: &Config, index: &mut SessionIndex) -> Result<Vec<ProjectTarget>> {
+    let mut warnings = Vec::new();
+    let targets = gather_targets_reporting(cfg, index, &mut warnings)?;
+    for w in warnings.iter() {
+        ui::print_warn(w);
     }
+    Ok(targets)
 }
 
-pub fn gather_targets(cfg: &Config) -> Result<Vec<ProjectTarget>> {
+/// [`gather_targets`], handing scan warnings (roots cut off at `projects.root_scan_limit`,
+/// unreadable roots and session folders) to the caller instead of printing them.
+pub fn gather_targets_reporting(
+    cfg: &Config,
+    index: &mut SessionIndex,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<ProjectTarget>> {
     let mut map: BTreeMap<PathBuf, ProjectTarget> = BTreeMap::new();
+    let excludes = Excludes::new(&cfg.projects.exclude)?;
 
     for p in cfg.projects.paths.iter() {
         let p = p.clone();
-        if !p.exists() {
-            continue;
-        }
-        if !p.is_dir() {
-            continue;
-        }
-        let label = display_name(&p);
+        let (kind, label) = match RemoteTarget::parse(&p) {
+            Some(remote) => (TargetKind::Remote, remote.label()),
+            None if p.is_dir() => (TargetKind::ExplicitPath, display_name(&p)),
+            None => continue,
+        };
         map.entry(p.clone()).or_insert(ProjectTarget {
             path: p,
-            kind: TargetKind::ExplicitPath,
+            kind,
             label,
             last_session_at: None,
             last_session_summary: None,
+            session_count: 0,
+            agents_md: AgentsMd::None,
+            codex_trust: Trust::Unknown,
+            parent: None,
+            git: None,
         });
     }
 
-    for root in cfg.projects.roots.iter() {
-        if !root.exists() || !root.is_dir() {
-            continue;
-        }
-        let entries =
-            fs::read_dir(root).with_context(|| format!("failed to read dir {}", root.display()))?;
-        for ent in entries.flatten() {
-            let path = ent.path();
-            let Ok(ft) = ent.file_type() else { continue };
-            if !ft.is_dir() {
-                continue;
-            }
-            if is_hidden_or_noise(&path) {
+    let (roots, limit) = (&cfg.projects.roots, cfg.projects.root_scan_limit);
+    let cache_path = cfg.dir.as_deref().map(target_cache::cache_path);
+    let mut cache = cache_path
+        .as_deref()
+        .map(TargetCache::load)
+        .unwrap_or_default();
+    let scans = scan_roots(roots, limit, &mut cache);
+    // Best effort: without a writable config folder, roots are just scanned every time.
+    if let Some(path) = cache_path.as_deref() {
+        let _ = cache.save(path);
+    }
+    for (root, scan) in roots.iter().zip(scans) {
+        let scan = match scan {
+            Ok(Some(scan)) => scan,
+            Ok(None) => continue,
+            Err(e) => {
+                warnings.push(format!("skipped root: {e:#}"));
                 continue;
             }
-            if !is_git_repo_root(&path) {
+        };
+        if scan.truncated {
+            warnings.push(format!(
+                "root {} produced more than {limit} targets; stopped scanning it. Consider narrower roots or a {IGNORE_FILE_NAME} there",
+                pathfmt::compact_path(root, 60),
+            ));
+        }
+        for path in scan.repos {
+            if excludes.is_excluded(&path) {
                 continue;
             }
             let label = path
@@ -100,27 +234,27 @@ pub fn gather_targets(cfg: &Config) -> Result<Vec<ProjectTarget>> {
                 label,
                 last_session_at: None,
                 last_session_summary: None,
+                session_count: 0,
+                agents_md: AgentsMd::None,
+                codex_trust: Trust::Unknown,
+                parent: None,
+                git: None,
             });
         }
     }
 
     if cfg.projects.from_sessions {
-        let sessions = sessions::list_recent_sessions(
-            cfg,
-            sessions::SessionQuery::All {
-                limit: cfg.projects.sessions_limit,
-            },
-        )?;
-        for s in sessions {
+        let sessions = index.recent(cfg, cfg.projects.sessions_limit)?;
+        for s in sessions.iter() {
             let inferred = infer_target_path_from_session_cwd(&s.cwd);
-            if inferred.is_none() {
+            let Some(inferred) = inferred.filter(|p| !excludes.is_excluded(p)) else {
                 continue;
-            }
-            let inferred = inferred.unwrap();
+            };
             let label = display_name(&inferred);
 
             match map.get_mut(&inferred) {
                 Some(existing) => {
+                    existing.session_count += 1;
                     // Only upgrade metadata if this session is newer than what we already have.
                     let replace = match (&existing.last_session_at, &s.created_at) {
                         (None, Some(_)) => true,
@@ -142,6 +276,11 @@ pub fn gather_targets(cfg: &Config) -> Result<Vec<ProjectTarget>> {
                             label,
                             last_session_at: s.created_at.clone(),
                             last_session_summary: s.summary.clone(),
+                            session_count: 1,
+                            agents_md: AgentsMd::None,
+                            codex_trust: Trust::Unknown,
+                            parent: None,
+                            git: None,
                         },
                     );
                 }
@@ -149,7 +288,18 @@ pub fn gather_targets(cfg: &Config) -> Result<Vec<ProjectTarget>> {
         }
     }
 
+    let codex_cfg = codex_config::load(&cfg.sessions.codex_home).unwrap_or_default();
     let mut items: Vec<ProjectTarget> = map.into_values().collect();
+    for t in items.iter_mut() {
+        if let Some(label) = cfg.label_for(&t.path) {
+            t.label = label.to_string();
+        }
+        if t.kind == TargetKind::Remote {
+            continue;
+        }
+        t.agents_md = detect_agents_md(&t.path);
+        t.codex_trust = codex_cfg.trust_for(&t.path);
+    }
     // Prefer targets you used recently, then alphabetical.
     items.sort_by(|a, b| match (&a.last_session_at, &b.last_session_at) {
         (Some(ta), Some(tb)) if ta != tb => tb.cmp(ta),
@@ -157,9 +307,282 @@ pub fn gather_targets(cfg: &Config) -> Result<Vec<ProjectTarget>> {
         (None, Some(_)) => std::cmp::Ordering::Greater,
         _ => a.label.to_lowercase().cmp(&b.label.to_lowercase()),
     });
+    if cfg.projects.sub_targets || cfg.projects.submodules {
+        let sessions = index.recent(cfg, cfg.projects.sessions_limit)?;
+        items = with_sub_targets(items, sessions, &codex_cfg, |root| {
+            let mut subs = Vec::new();
+            if cfg.projects.sub_targets {
+                subs.extend(sub_target_dirs(root));
+            }
+            if cfg.projects.submodules {
+                subs.extend(submodule_dirs(root));
+            }
+            subs
+        });
+    }
+    warnings.extend(index.take_skipped());
     Ok(items)
 }
 
+/// Roots scanned at once; roots on network filesystems mostly wait on I/O.
+const SCAN_THREADS: usize = 8;
+
+pub struct RootScan {
+    /// Git repos directly under the root, by name.
+    pub repos: Vec<PathBuf>,
+    /// More than `projects.root_scan_limit` repos were found; the rest were skipped.
+    pub truncated: bool,
+}
+
+/// [`scan_root`] for each root not in `cache`, a few at a time in parallel; results in `roots`
+/// order.
+fn scan_roots(
+    roots: &[PathBuf],
+    limit: usize,
+    cache: &mut TargetCache,
+) -> Vec<Result<Option<RootScan>>> {
+    let mut out: Vec<Option<Result<Option<RootScan>>>> = roots
+        .iter()
+        .map(|root| cache.get(root, limit).map(|scan| Ok(Some(scan))))
+        .collect();
+    let misses: Vec<usize> = (0..roots.len()).filter(|&i| out[i].is_none()).collect();
+    for chunk in misses.chunks(SCAN_THREADS) {
+        std::thread::scope(|s| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|&i| (i, s.spawn(move || scan_root(&roots[i], limit))))
+                .collect();
+            for (i, h) in handles {
+                let res = h
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("root scan panicked")));
+                if let Ok(Some(scan)) = &res {
+                    cache.insert(&roots[i], limit, scan);
+                }
+                out[i] = Some(res);
+            }
+        });
+    }
+    out.into_iter().flatten().collect()
+}
+
+/// `None` when `root` is not a directory.
+fn scan_root(root: &Path, limit: usize) -> Result<Option<RootScan>> {
+    if !root.exists() || !root.is_dir() {
+        return Ok(None);
+    }
+    let ignore = IgnoreRules::load(root);
+    let mut entries: Vec<_> = fs::read_dir(root)
+        .with_context(|| format!("failed to read dir {}", root.display()))?
+        .flatten()
+        .collect();
+    entries.sort_by_key(|e| e.file_name());
+    let mut scan = RootScan {
+        repos: Vec::new(),
+        truncated: false,
+    };
+    for ent in entries {
+        let path = ent.path();
+        let Ok(ft) = ent.file_type() else { continue };
+        if !ft.is_dir() {
+            continue;
+        }
+        if is_hidden_or_noise(&path) {
+            continue;
+        }
+        if ignore.is_ignored(Path::new(&ent.file_name())) {
+            continue;
+        }
+        if !is_git_repo_root(&path) {
+            continue;
+        }
+        if scan.repos.len() == limit {
+            scan.truncated = true;
+            break;
+        }
+        scan.repos.push(path);
+    }
+    Ok(Some(scan))
+}
+
+/// `items` with each target's sub-targets (`subs_of(path)`, e.g. [`sub_target_dirs`]) listed
+/// right after it, labeled `parent/name` and carrying their own session counts (sessions started
+/// inside them; for a nested git repo such as a submodule, sessions whose git root it is).
+fn with_sub_targets(
+    items: Vec<ProjectTarget>,
+    sessions: &[SessionItem],
+    codex_cfg: &CodexCliConfig,
+    subs_of: impl Fn(&Path) -> Vec<(PathBuf, String)>,
+) -> Vec<ProjectTarget> {
+    let subs: Vec<Vec<(PathBuf, String)>> = items
+        .iter()
+        .map(|t| match t.kind {
+            TargetKind::Remote => Vec::new(),
+            _ => subs_of(&t.path),
+        })
+        .collect();
+    let sub_paths: BTreeSet<PathBuf> = items
+        .iter()
+        .zip(subs.iter())
+        .flat_map(|(t, subs)| subs.iter().map(|(rel, _)| t.path.join(rel)))
+        .collect();
+    // A submodule with sessions of its own was inferred as a separate target; it moves under its repo.
+    let (items, subs): (Vec<_>, Vec<_>) = items
+        .into_iter()
+        .zip(subs)
+        .filter(|(t, _)| !(t.kind == TargetKind::SessionHistory && sub_paths.contains(&t.path)))
+        .unzip();
+    let mut known: BTreeSet<PathBuf> = items.iter().map(|t| t.path.clone()).collect();
+    let mut out = Vec::with_capacity(items.len());
+    for (t, subs) in items.into_iter().zip(subs) {
+        let (parent_path, parent_label, kind) = (t.path.clone(), t.label.clone(), t.kind.clone());
+        out.push(t);
+        for (rel, name) in subs {
+            let path = parent_path.join(rel);
+            if !known.insert(path.clone()) {
+                continue;
+            }
+            let mut sub = ProjectTarget::new(path, kind.clone());
+            sub.label = format!("{parent_label}/{name}");
+            sub.parent = Some(parent_path.clone());
+            sub.codex_trust = codex_cfg.trust_for(&sub.path);
+            let own_repo = is_git_repo_root(&sub.path);
+            let inside = |s: &&SessionItem| {
+                if own_repo {
+                    find_git_root(&s.cwd).as_ref() == Some(&sub.path)
+                } else {
+                    s.cwd.starts_with(&sub.path)
+                }
+            };
+            // Newest first, so the first match is the latest session.
+            for s in sessions.iter().filter(inside) {
+                if sub.session_count == 0 {
+                    sub.last_session_at = s.created_at.clone();
+                    sub.last_session_summary = s.summary.clone();
+                }
+                sub.session_count += 1;
+            }
+            out.push(sub);
+        }
+    }
+    out
+}
+
+/// Sub-targets of a monorepo at `root`: Cargo and pnpm/npm/yarn workspace members (by package
+/// name), then the packages [`package_dirs`] finds, as relative folders and display names.
+pub fn sub_target_dirs(root: &Path) -> Vec<(PathBuf, String)> {
+    let mut out = workspace::cargo_members(root);
+    for (rel, name) in workspace::js_members(root) {
+        if !out.iter().any(|(r, _)| *r == rel) {
+            out.push((rel, name));
+        }
+    }
+    for rel in package_dirs(root) {
+        if !out.iter().any(|(r, _)| *r == rel) {
+            let name = pathfmt::basename(&rel);
+            out.push((rel, name));
+        }
+    }
+    out
+}
+
+/// Initialized submodules of the repo at `root` (the `path`s in `.gitmodules` that have been
+/// checked out), named by their folder.
+pub fn submodule_dirs(root: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(s) = fs::read_to_string(root.join(".gitmodules")) else {
+        return Vec::new();
+    };
+    s.lines()
+        .filter_map(|l| {
+            let (key, value) = l.split_once('=')?;
+            (key.trim() == "path").then(|| PathBuf::from(value.trim()))
+        })
+        .filter(|rel| is_git_repo_root(&root.join(rel)))
+        .map(|rel| {
+            let name = pathfmt::basename(&rel);
+            (rel, name)
+        })
+        .collect()
+}
+
+/// Folders monorepos keep their packages in, and the manifests that make a child a package.
+const PACKAGE_PARENTS: &[&str] = &["apps", "packages", "crates", "services", "libs", "modules"];
+const PACKAGE_MANIFESTS: &[&str] = &["package.json", "Cargo.toml", "go.mod", "pyproject.toml"];
+
+/// Package folders of a monorepo at `root` (relative, e.g. `packages/api`): children of the
+/// usual parent folders that hold a manifest.
+pub fn package_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    for parent in PACKAGE_PARENTS {
+        let Ok(entries) = fs::read_dir(root.join(parent)) else {
+            continue;
+        };
+        for ent in entries.flatten() {
+            let path = ent.path();
+            if path.is_dir() && PACKAGE_MANIFESTS.iter().any(|m| path.join(m).is_file()) {
+                out.push(Path::new(parent).join(ent.file_name()));
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+const AGENTS_MD_NAMES: &[&str] = &["AGENTS.md", "AGENTS.override.md"];
+
+/// Look for agent instructions at the target root, then one level down (skipping noise dirs).
+pub fn detect_agents_md(dir: &Path) -> AgentsMd {
+    if AGENTS_MD_NAMES.iter().any(|n| dir.join(n).is_file()) {
+        return AgentsMd::Root;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return AgentsMd::None;
+    };
+    for ent in entries.flatten() {
+        let Ok(ft) = ent.file_type() else { continue };
+        if !ft.is_dir() {
+            continue;
+        }
+        let path = ent.path();
+        if is_hidden_or_noise(&path) {
+            continue;
+        }
+        if AGENTS_MD_NAMES.iter().any(|n| path.join(n).is_file()) {
+            return AgentsMd::Nested;
+        }
+    }
+    AgentsMd::None
+}
+
+/// Folders up to `max_depth` levels below `start` (including it) with at least two git repos as
+/// direct children, i.e. good `projects.roots`, with their repo counts. Repos aren't descended.
+pub fn find_root_candidates(start: &Path, max_depth: usize) -> Vec<(PathBuf, usize)> {
+    let mut out = Vec::new();
+    let mut queue = vec![(start.to_path_buf(), 0)];
+    while let Some((dir, depth)) = queue.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut repos = 0;
+        for ent in entries.flatten() {
+            let path = ent.path();
+            if !ent.file_type().is_ok_and(|ft| ft.is_dir()) || is_hidden_or_noise(&path) {
+                continue;
+            }
+            if is_git_repo_root(&path) {
+                repos += 1;
+            } else if depth < max_depth {
+                queue.push((path, depth + 1));
+            }
+        }
+        if repos >= 2 {
+            out.push((dir, repos));
+        }
+    }
+    out.sort();
+    out
+}
+
 fn is_git_repo_root(p: &Path) -> bool {
     let dotgit = p.join(".git");
     dotgit.is_dir() || dotgit.is_file()
@@ -187,14 +610,16 @@ fn find_git_root(start: &Path) -> Option<PathBuf> {
     None
 }
 
-fn is_hidden_or_noise(p: &Path) -> bool {
-    let Some(name) = p.file_name().and_then(|s| s.to_str()) else {
+pub(crate) fn is_hidden_or_noise(p: &Path) -> bool {
+    let Some(name) = p.file_name() else {
         return false;
     };
-    if name.starts_with('.') {
+    if name.as_encoded_bytes().starts_with(b".") {
         return true;
     }
-    matches!(name, "node_modules" | "target" | "dist" | "build")
+    ["node_modules", "target", "dist", "build"]
+        .iter()
+        .any(|n| name == *n)
 }
 
 fn display_name(p: &Path) -> String {
@@ -1,19 +1,55 @@
+use std::cmp::Reverse;
+use std::io::IsTerminal;
+use std::path::Path;
+
 use anyhow::Result;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
+use time::Duration;
 
+use crate::RunOpts;
 use crate::config::Config;
+use crate::exit::Exit;
+use crate::pathfmt;
 use crate::projects::{self, ProjectTarget};
-use crate::sessions::{self, SessionItem};
+use crate::search;
+use crate::sessions::{self, SessionIndex, SessionItem};
+use crate::timefmt;
 use crate::ui;
 
-pub fn launch_by_query(cfg: &Config, query: &str, dry_run: bool) -> Result<()> {
+const CONTENT_MATCH_BONUS: i64 = 20;
+
+/// How far the best fuzzy match must be ahead of the runner-up to be picked without asking.
+const AUTO_PICK_MARGIN: i64 = 25;
+
+/// Quick launch. An auto-picked project that may not be what was meant (a narrow lead, or a
+/// label not containing the query) is confirmed first unless `yes`.
+pub fn launch_by_query(cfg: &Config, query: &str, yes: bool, run: RunOpts) -> Result<()> {
+    let (chosen, expected) = resolve_project_checked(cfg, query)?;
+    if !expected && !yes && std::io::stdin().is_terminal() {
+        let prompt = format!("Launch in {}?", pathfmt::compact_path(&chosen.path, 60));
+        if !ui::confirm_with_default(&prompt, true)? {
+            anyhow::bail!(Exit::Cancelled);
+        }
+    }
+    crate::run_codex_new(cfg, &chosen, run)
+}
+
+/// Fuzzy-resolve a project the way quick launch does (auto-pick a clear winner, else prompt;
+/// without a terminal to prompt on, the best match wins).
+pub fn resolve_project(cfg: &Config, query: &str) -> Result<ProjectTarget> {
+    Ok(resolve_project_checked(cfg, query)?.0)
+}
+
+/// [`resolve_project`], plus whether the pick is unsurprising: chosen in the prompt, or
+/// auto-picked by a wide lead with the query in its label.
+fn resolve_project_checked(cfg: &Config, query: &str) -> Result<(ProjectTarget, bool)> {
     let query = query.trim();
     if query.is_empty() {
         anyhow::bail!("empty project query");
     }
 
-    let targets = projects::gather_targets(cfg)?;
+    let targets = projects::gather_targets(cfg, &mut SessionIndex::default())?;
     let matcher = SkimMatcherV2::default().ignore_case();
     let mut scored = targets
         .into_iter()
@@ -22,20 +58,60 @@ pub fn launch_by_query(cfg: &Config, query: &str, dry_run: bool) -> Result<()> {
             matcher.fuzzy_match(&hay, query).map(|score| (score, t))
         })
         .collect::<Vec<_>>();
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.sort_by_key(|(score, _)| Reverse(*score));
 
     if scored.is_empty() {
-        anyhow::bail!("no project matches for: {query}");
+        anyhow::bail!(Exit::NoMatch(format!("no project matches for: {query}")));
     }
 
-    let chosen = choose_target(scored)?;
-    crate::run_codex_new(cfg, &chosen, dry_run)
+    choose_target(scored, query)
+}
+
+/// All sessions (newest first), optionally limited to a fuzzy-resolved project and a max age.
+pub fn sessions_matching(
+    cfg: &Config,
+    project: Option<&str>,
+    since: Option<Duration>,
+) -> Result<Vec<SessionItem>> {
+    let query = match project {
+        Some(q) => {
+            let target = resolve_project(cfg, q)?;
+            sessions::query_for_target(&target.path, usize::MAX)
+        }
+        None => sessions::SessionQuery::All { limit: usize::MAX },
+    };
+    let mut items = sessions::list_recent_sessions(cfg, query)?;
+    if let Some(age) = since {
+        items.retain(|s| timefmt::is_within(s.created_at.as_deref(), age));
+    }
+    Ok(items)
 }
 
-pub fn resume_by_query(cfg: &Config, query: &str, dry_run: bool) -> Result<()> {
+/// Resume the newest session of `dir`'s repo (or of the folder, outside a repo).
+pub fn resume_latest_in(cfg: &Config, dir: &Path, run: RunOpts) -> Result<()> {
+    let latest = sessions::list_recent_sessions(cfg, sessions::query_for_target(dir, 1))?;
+    let Some(session) = latest.into_iter().next() else {
+        anyhow::bail!(Exit::NoMatch(format!(
+            "no sessions for {} yet; start one with `codex-launch .`",
+            dir.display()
+        )));
+    };
+    crate::run_codex_resume(cfg, &session, run)
+}
+
+/// Resume the best session for `query`: fuzzy over id/cwd/summary, plus message content when
+/// the full-text index is enabled (content hits rank just above fuzzy-only matches). With `deep`,
+/// the first few assistant replies of each session are fuzzy-matched too.
+pub fn resume_by_query(
+    cfg: &Config,
+    index_dir: &Path,
+    query: &str,
+    deep: bool,
+    run: RunOpts,
+) -> Result<()> {
     let query = query.trim();
     if query.is_empty() {
-        anyhow::bail!("empty resume query");
+        anyhow::bail!("empty resume query (`--here --resume` continues the latest session here)");
     }
 
     let items = sessions::list_recent_sessions(
@@ -44,6 +120,11 @@ pub fn resume_by_query(cfg: &Config, query: &str, dry_run: bool) -> Result<()> {
             limit: cfg.projects.sessions_limit.max(cfg.sessions.limit),
         },
     )?;
+    let index = if cfg.search.index {
+        Some(search::open(cfg, index_dir)?)
+    } else {
+        None
+    };
 
     let matcher = SkimMatcherV2::default().ignore_case();
     let mut scored = items
@@ -55,34 +136,78 @@ pub fn resume_by_query(cfg: &Config, query: &str, dry_run: bool) -> Result<()> {
                 s.cwd.display(),
                 s.summary.as_deref().unwrap_or("")
             );
-            matcher.fuzzy_match(&hay, query).map(|score| (score, s))
+            let mut best = matcher.fuzzy_match(&hay, query);
+            if deep {
+                let deep_score = s
+                    .path
+                    .as_deref()
+                    .map(|p| assistant_excerpts(index.as_ref(), p))
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|text| matcher.fuzzy_match(text, query))
+                    .max();
+                best = best.max(deep_score);
+            }
+            best.map(|score| (score, s))
         })
         .collect::<Vec<_>>();
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+    if let Some(index) = index.as_ref() {
+        for hit in index.search(query) {
+            match scored.iter_mut().find(|(_, s)| s.id == hit.id) {
+                Some((score, _)) => *score += CONTENT_MATCH_BONUS,
+                None => scored.push((CONTENT_MATCH_BONUS, hit)),
+            }
+        }
+    }
+    scored.sort_by_key(|(score, _)| Reverse(*score));
 
     if scored.is_empty() {
-        anyhow::bail!("no session matches for: {query}");
+        anyhow::bail!(Exit::NoMatch(format!("no session matches for: {query}")));
     }
 
     let chosen = choose_session(scored)?;
-    crate::run_codex_resume(cfg, &chosen, dry_run)
+    crate::run_codex_resume(cfg, &chosen, run)
+}
+
+/// Leading assistant replies from the index when available, else read from the rollout.
+fn assistant_excerpts(index: Option<&search::FullTextIndex>, rollout: &Path) -> Vec<String> {
+    if let Some(excerpts) = index.and_then(|i| i.assistant_excerpts(rollout)) {
+        return excerpts.to_vec();
+    }
+    sessions::read_rollout(rollout)
+        .map(|c| {
+            sessions::assistant_excerpts(
+                &c.messages,
+                search::DEEP_MESSAGES,
+                search::DEEP_MESSAGE_CHARS,
+            )
+        })
+        .unwrap_or_default()
 }
 
-fn choose_target(mut scored: Vec<(i64, ProjectTarget)>) -> Result<ProjectTarget> {
+fn choose_target(
+    mut scored: Vec<(i64, ProjectTarget)>,
+    query: &str,
+) -> Result<(ProjectTarget, bool)> {
+    let in_label = |t: &ProjectTarget| t.label.to_lowercase().contains(&query.to_lowercase());
     if scored.len() == 1 {
-        return Ok(scored.remove(0).1);
+        let top = scored.remove(0).1;
+        let expected = in_label(&top);
+        return Ok((top, expected));
     }
     let (top_score, top) = scored[0].clone();
     let (second_score, _) = scored[1].clone();
-    if top_score >= second_score + 25 {
-        return Ok(top);
+    if top_score >= second_score + AUTO_PICK_MARGIN || !std::io::stdin().is_terminal() {
+        let expected = top_score >= second_score + 2 * AUTO_PICK_MARGIN && in_label(&top);
+        return Ok((top, expected));
     }
     let options = scored
         .into_iter()
         .take(12)
         .map(|(_, t)| t)
         .collect::<Vec<_>>();
-    ui::pick_target(&options)
+    Ok((ui::pick_target(&options)?, true))
 }
 
 fn choose_session(mut scored: Vec<(i64, SessionItem)>) -> Result<SessionItem> {
@@ -91,7 +216,7 @@ fn choose_session(mut scored: Vec<(i64, SessionItem)>) -> Result<SessionItem> {
     }
     let (top_score, top) = scored[0].clone();
     let (second_score, _) = scored[1].clone();
-    if top_score >= second_score + 25 {
+    if top_score >= second_score + AUTO_PICK_MARGIN {
         return Ok(top);
     }
     let options = scored
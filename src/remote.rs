@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+use crate::ui;
+
+/// A target on another machine, written `ssh://host/path` in `projects.paths`
+/// (`ssh://host/~/code/api` for a path under the remote home).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTarget {
+    pub host: String,
+    pub path: String,
+}
+
+impl RemoteTarget {
+    pub fn parse(path: &Path) -> Option<Self> {
+        let rest = path.to_str()?.strip_prefix("ssh://")?;
+        let (host, path) = rest.split_once('/').unwrap_or((rest, "~"));
+        if host.is_empty() {
+            return None;
+        }
+        let path = if path.starts_with('~') {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+        Some(Self {
+            host: host.to_string(),
+            path,
+        })
+    }
+
+    /// `host:name`, the picker label.
+    pub fn label(&self) -> String {
+        let name = self
+            .path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .unwrap_or(&self.path);
+        format!("{}:{name}", self.host)
+    }
+
+    /// `ssh -t host 'cd <path> && <script>'`.
+    pub fn ssh_command(&self, script: &str) -> Command {
+        let mut cmd = Command::new("ssh");
+        cmd.arg("-t")
+            .arg(&self.host)
+            .arg(format!("cd {} && {script}", self.cd_arg()));
+        cmd
+    }
+
+    /// Runs `bin` with `args` in the remote folder; `env` is set on the remote side.
+    pub fn codex_command<'a>(
+        &self,
+        bin: &str,
+        env: &BTreeMap<String, String>,
+        args: impl IntoIterator<Item = &'a OsStr>,
+    ) -> Command {
+        let mut words: Vec<String> = Vec::new();
+        if !env.is_empty() {
+            words.push("env".to_string());
+            for (k, v) in env.iter() {
+                words.push(ui::shell_escape(OsStr::new(&format!("{k}={v}"))));
+            }
+        }
+        words.push(bin.to_string());
+        words.extend(args.into_iter().map(ui::shell_escape));
+        self.ssh_command(&words.join(" "))
+    }
+
+    /// The path quoted for the remote shell, leaving a leading `~` unquoted so it still expands.
+    fn cd_arg(&self) -> String {
+        match self.path.strip_prefix("~/") {
+            Some(rest) => format!("~/{}", ui::shell_escape(OsStr::new(rest))),
+            None if self.path == "~" => "~".to_string(),
+            None => ui::shell_escape(OsStr::new(&self.path)),
+        }
+    }
+}
+
+pub fn is_remote(path: &Path) -> bool {
+    RemoteTarget::parse(path).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_quotes_remote_targets() {
+        let t = RemoteTarget::parse(Path::new("ssh://devbox/~/code/my api")).unwrap();
+        assert_eq!(t.host, "devbox");
+        assert_eq!(t.label(), "devbox:my api");
+        let cmd = t.codex_command(
+            "codex",
+            &BTreeMap::new(),
+            [OsStr::new("--model"), OsStr::new("o3")],
+        );
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(
+            args,
+            ["-t", "devbox", "cd ~/'code/my api' && codex --model o3"]
+        );
+
+        let t = RemoteTarget::parse(Path::new("ssh://devbox/srv/app")).unwrap();
+        assert_eq!(t.path, "/srv/app");
+        assert!(RemoteTarget::parse(Path::new("/srv/app")).is_none());
+    }
+}
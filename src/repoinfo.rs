@@ -0,0 +1,165 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use time::OffsetDateTime;
+
+use crate::timefmt;
+
+/// README names tried (in order) for the project preview.
+const README_NAMES: &[&str] = &[
+    "README.md",
+    "README",
+    "README.markdown",
+    "README.rst",
+    "README.txt",
+    "readme.md",
+];
+
+#[derive(Debug, Clone)]
+pub struct Commit {
+    pub subject: String,
+    pub time: OffsetDateTime,
+}
+
+impl Commit {
+    /// `3d Fix the login redirect`: relative age, then subject.
+    pub fn summary(&self) -> String {
+        format!("{} {}", timefmt::format_age(self.time), self.subject)
+    }
+}
+
+/// HEAD's subject and commit time, via `git log -1` (None outside a repo or without git).
+pub fn last_commit(dir: &Path) -> Option<Commit> {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", "-1", "--format=%ct%x09%s"])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let line = String::from_utf8_lossy(&out.stdout);
+    let (ts, subject) = line.trim_end().split_once('\t')?;
+    Some(Commit {
+        subject: subject.to_string(),
+        time: OffsetDateTime::from_unix_timestamp(ts.parse().ok()?).ok()?,
+    })
+}
+
+/// A repo's checked-out branch and whether it has uncommitted changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Branch name, or `(abc1234)` for a detached HEAD.
+    pub branch: String,
+    pub dirty: bool,
+}
+
+impl GitStatus {
+    /// `main`, or `main*` with uncommitted changes.
+    pub fn label(&self) -> String {
+        let dirty = if self.dirty { "*" } else { "" };
+        format!("{}{dirty}", self.branch)
+    }
+}
+
+/// Branch from `.git/HEAD` and dirtiness from `git status` (None when `dir` isn't a repo root).
+pub fn git_status(dir: &Path) -> Option<GitStatus> {
+    let branch = head_branch(dir)?;
+    // `--no-optional-locks` so a status running alongside the user's own git never blocks it.
+    let dirty = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["--no-optional-locks", "status", "--porcelain"])
+        .output()
+        .is_ok_and(|out| out.status.success() && !out.stdout.is_empty());
+    Some(GitStatus { branch, dirty })
+}
+
+/// [`git_status`] of each of `dirs` in turn on another thread; results arrive as they are read,
+/// and the thread stops once the receiver is dropped.
+pub fn git_status_in_background(dirs: Vec<PathBuf>) -> Receiver<(PathBuf, Option<GitStatus>)> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for dir in dirs {
+            let status = git_status(&dir);
+            if tx.send((dir, status)).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// The branch `HEAD` points at, read without running git. `.git` may be a `gitdir:` file
+/// (worktrees and submodules).
+fn head_branch(dir: &Path) -> Option<String> {
+    let dot_git = dir.join(".git");
+    let git_dir = if dot_git.is_file() {
+        let link = fs::read_to_string(&dot_git).ok()?;
+        dir.join(link.strip_prefix("gitdir:")?.trim())
+    } else {
+        dot_git
+    };
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref:") {
+        Some(r) => {
+            let r = r.trim();
+            Some(r.strip_prefix("refs/heads/").unwrap_or(r).to_string())
+        }
+        None => Some(format!("({})", head.get(..7)?)),
+    }
+}
+
+/// The first `max_lines` lines of the project's README, if it has one.
+pub fn readme_head(dir: &Path, max_lines: usize) -> Option<Vec<String>> {
+    let file = README_NAMES
+        .iter()
+        .find_map(|name| File::open(dir.join(name)).ok())?;
+    Some(
+        BufReader::new(file)
+            .lines()
+            .map_while(|l| l.ok())
+            .take(max_lines)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_branch_from_head_and_gitdir_links() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let repo = dir.join("repo");
+        let worktree = dir.join("worktree");
+        let linked = dir.join("repo/.git/worktrees/wt");
+        fs::create_dir_all(&linked).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(repo.join(".git/HEAD"), "ref: refs/heads/feature/x\n").unwrap();
+        fs::write(
+            linked.join("HEAD"),
+            "0123456789abcdef0123456789abcdef01234567\n",
+        )
+        .unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", linked.display()),
+        )
+        .unwrap();
+
+        let branch = head_branch(&repo);
+        let detached = head_branch(&worktree);
+        let plain = head_branch(&dir);
+        assert_eq!(branch.as_deref(), Some("feature/x"));
+        assert_eq!(detached.as_deref(), Some("(0123456)"));
+        assert_eq!(plain, None);
+    }
+}
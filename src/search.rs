@@ -0,0 +1,407 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::sessions::{self, SessionItem};
+use crate::ui;
+
+const INDEX_FILE_NAME: &str = "fulltext.json";
+const INDEX_VERSION: u32 = 4;
+
+/// How many leading assistant replies `--resume --deep` matches against, and how much of each.
+pub const DEEP_MESSAGES: usize = 5;
+pub const DEEP_MESSAGE_CHARS: usize = 2000;
+
+/// Tokens longer than this are almost always hashes or base64 blobs; skip them.
+const MAX_TERM_LEN: usize = 48;
+
+/// Where the full-text index lives: `index/` next to the launcher config.
+pub fn index_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("index")
+}
+
+/// Lowercased alphanumeric words (plus `_`) of at least two characters, deduplicated.
+pub fn terms(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| w.chars().count() >= 2 && w.len() <= MAX_TERM_LEN)
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// A search query: plain words, matched as prefixes of message words, and `file:<path>`
+/// filters, matched as case-insensitive substrings of the paths a session edited.
+#[derive(Debug, Default)]
+struct Query {
+    terms: BTreeSet<String>,
+    files: Vec<String>,
+}
+
+impl Query {
+    fn parse(q: &str) -> Self {
+        let mut out = Query::default();
+        let mut words = Vec::new();
+        for w in q.split_whitespace() {
+            match w.strip_prefix("file:") {
+                Some(f) if !f.is_empty() => out.files.push(f.to_lowercase()),
+                _ => words.push(w),
+            }
+        }
+        out.terms = terms(&words.join(" "));
+        out
+    }
+
+    fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.files.is_empty()
+    }
+
+    fn matches_files(&self, touched: &BTreeMap<String, usize>) -> bool {
+        self.files.iter().all(|f| {
+            touched
+                .keys()
+                .any(|p| p.to_lowercase().contains(f.as_str()))
+        })
+    }
+}
+
+/// Every query term must be a prefix of some document term.
+fn matches_all(doc_terms: &BTreeSet<String>, query: &BTreeSet<String>) -> bool {
+    query.iter().all(|q| {
+        doc_terms
+            .range::<String, _>(q.clone()..)
+            .next()
+            .is_some_and(|t| t.starts_with(q.as_str()))
+    })
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    docs: BTreeMap<PathBuf, Doc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Doc {
+    /// Rollout mtime (ms since epoch) when it was indexed; a different value means reindex.
+    mtime: u128,
+    session: SessionItem,
+    terms: BTreeSet<String>,
+    /// Leading assistant replies, for `--resume --deep`.
+    #[serde(default)]
+    assistant: Vec<String>,
+    /// Files the session edited, with patch counts.
+    #[serde(default)]
+    files: BTreeMap<String, usize>,
+}
+
+/// Inverted index over session message text, persisted as one JSON file and refreshed
+/// incrementally (only rollouts whose mtime changed are re-read).
+#[derive(Debug)]
+pub struct FullTextIndex {
+    dir: PathBuf,
+    file: IndexFile,
+    /// term -> rollout paths containing it; rebuilt in memory after load/update.
+    postings: BTreeMap<String, BTreeSet<PathBuf>>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct UpdateStats {
+    pub indexed: usize,
+    pub removed: usize,
+    pub total: usize,
+    /// Notes about rollouts that couldn't be read (deleted meanwhile, unreadable); left out.
+    pub skipped: Vec<String>,
+}
+
+impl FullTextIndex {
+    /// Load the index from `dir`; a missing, stale-format or corrupt file starts empty.
+    pub fn load(dir: &Path) -> Self {
+        let file = fs::read_to_string(dir.join(INDEX_FILE_NAME))
+            .ok()
+            .and_then(|s| serde_json::from_str::<IndexFile>(&s).ok())
+            .filter(|f| f.version == INDEX_VERSION)
+            .unwrap_or(IndexFile {
+                version: INDEX_VERSION,
+                docs: BTreeMap::new(),
+            });
+        let mut index = Self {
+            dir: dir.to_path_buf(),
+            file,
+            postings: BTreeMap::new(),
+        };
+        index.rebuild_postings();
+        index
+    }
+
+    /// Drop everything so the next [`update`](Self::update) re-reads every rollout.
+    pub fn clear(&mut self) {
+        self.file.docs.clear();
+        self.postings.clear();
+    }
+
+    /// Index new or changed rollouts and forget deleted ones.
+    pub fn update(&mut self, cfg: &Config) -> Result<UpdateStats> {
+        let mut stats = UpdateStats::default();
+        let files = sessions::all_rollout_files(cfg)?;
+        let present: BTreeSet<&PathBuf> = files.iter().collect();
+        let before = self.file.docs.len();
+        self.file.docs.retain(|p, _| present.contains(p));
+        stats.removed = before - self.file.docs.len();
+
+        for path in files.iter() {
+            let mtime = mtime_ms(path);
+            if self.file.docs.get(path).is_some_and(|d| d.mtime == mtime) {
+                continue;
+            }
+            let Some(session) = sessions::session_from_rollout(cfg, path).ok().flatten() else {
+                continue;
+            };
+            let content = match sessions::read_rollout(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    stats.skipped.push(format!("skipped: {e:#}"));
+                    continue;
+                }
+            };
+            let mut doc_terms = BTreeSet::new();
+            for m in content.messages.iter() {
+                doc_terms.extend(terms(&m.text));
+            }
+            self.file.docs.insert(
+                path.clone(),
+                Doc {
+                    mtime,
+                    session,
+                    terms: doc_terms,
+                    assistant: sessions::assistant_excerpts(
+                        &content.messages,
+                        DEEP_MESSAGES,
+                        DEEP_MESSAGE_CHARS,
+                    ),
+                    files: content.touched_files,
+                },
+            );
+            stats.indexed += 1;
+        }
+        stats.total = self.file.docs.len();
+        if stats.indexed > 0 || stats.removed > 0 {
+            self.rebuild_postings();
+        }
+        Ok(stats)
+    }
+
+    /// Write the index atomically (per-process temp file + rename) so a reader never sees half a
+    /// file and two concurrent indexers never write into the same temp file.
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let path = self.dir.join(INDEX_FILE_NAME);
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        let s = serde_json::to_string(&self.file)?;
+        fs::write(&tmp, s).with_context(|| format!("failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Sessions whose messages contain every query word (as a word prefix) and that edited
+    /// every `file:` path, newest first.
+    pub fn search(&self, query: &str) -> Vec<SessionItem> {
+        let query = Query::parse(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut hits: Option<BTreeSet<&PathBuf>> = None;
+        for q in query.terms.iter() {
+            let with_prefix: BTreeSet<&PathBuf> = self
+                .postings
+                .range::<String, _>(q.clone()..)
+                .take_while(|(t, _)| t.starts_with(q.as_str()))
+                .flat_map(|(_, paths)| paths.iter())
+                .collect();
+            hits = Some(match hits {
+                None => with_prefix,
+                Some(prev) => prev.intersection(&with_prefix).copied().collect(),
+            });
+        }
+        let candidates = hits.unwrap_or_else(|| self.file.docs.keys().collect());
+        let mut out = candidates
+            .into_iter()
+            .filter_map(|p| self.file.docs.get(p))
+            .filter(|d| query.matches_files(&d.files))
+            .map(|d| d.session.clone())
+            .collect::<Vec<_>>();
+        sort_newest_first(&mut out);
+        out
+    }
+
+    /// Indexed leading assistant replies of the session stored at `rollout`.
+    pub fn assistant_excerpts(&self, rollout: &Path) -> Option<&[String]> {
+        self.file.docs.get(rollout).map(|d| d.assistant.as_slice())
+    }
+
+    fn rebuild_postings(&mut self) {
+        self.postings.clear();
+        for (path, doc) in self.file.docs.iter() {
+            for t in doc.terms.iter() {
+                self.postings
+                    .entry(t.clone())
+                    .or_default()
+                    .insert(path.clone());
+            }
+        }
+    }
+}
+
+/// Content search without an index: reads every rollout. Same matching rules as the index.
+pub fn scan(cfg: &Config, query: &str) -> Result<Vec<SessionItem>> {
+    let query = Query::parse(query);
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for path in sessions::all_rollout_files(cfg)? {
+        let Some(session) = sessions::session_from_rollout(cfg, &path).ok().flatten() else {
+            continue;
+        };
+        let content = match sessions::read_rollout(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                ui::print_warn(&format!("skipped: {e:#}"));
+                continue;
+            }
+        };
+        let mut doc_terms = BTreeSet::new();
+        for m in content.messages.iter() {
+            doc_terms.extend(terms(&m.text));
+        }
+        if matches_all(&doc_terms, &query.terms) && query.matches_files(&content.touched_files) {
+            out.push(session);
+        }
+    }
+    Ok(out)
+}
+
+/// Load the index from `dir` and bring it up to date, saving it if anything changed.
+pub fn open(cfg: &Config, dir: &Path) -> Result<FullTextIndex> {
+    let mut index = FullTextIndex::load(dir);
+    let stats = index.update(cfg)?;
+    for note in stats.skipped.iter() {
+        ui::print_warn(note);
+    }
+    if stats.indexed > 0 || stats.removed > 0 {
+        index.save()?;
+    }
+    Ok(index)
+}
+
+/// Search with the persisted index when `search.index` is on (refreshing it first), else scan.
+pub fn search(cfg: &Config, dir: &Path, query: &str) -> Result<Vec<SessionItem>> {
+    if !cfg.search.index {
+        return scan(cfg, query);
+    }
+    Ok(open(cfg, dir)?.search(query))
+}
+
+/// Characters of context kept on each side of a `grep` match.
+const GREP_CONTEXT: usize = 80;
+
+/// Stream `id<TAB>timestamp<TAB>line` for every rollout line matching `re`. The line is cut
+/// down to the match plus some context, since rollout lines are whole JSON events.
+pub fn grep(items: &[SessionItem], re: &Regex, out: &mut impl Write) -> Result<usize> {
+    let mut count = 0;
+    for s in items {
+        let Some(Ok(file)) = s.path.as_deref().map(fs::File::open) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines() {
+            let Ok(line) = line else { break };
+            let Some(m) = re.find(&line) else { continue };
+            let ts = line_timestamp(&line)
+                .or_else(|| s.created_at.clone())
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "{}\t{}\t{}",
+                s.id,
+                ts,
+                snippet(&line, m.start(), m.end())
+            )?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn line_timestamp(line: &str) -> Option<String> {
+    let v: serde_json::Value = serde_json::from_str(line).ok()?;
+    v.get("timestamp")?.as_str().map(|s| s.to_string())
+}
+
+fn snippet(line: &str, start: usize, end: usize) -> String {
+    let from = line[..start]
+        .char_indices()
+        .rev()
+        .nth(GREP_CONTEXT.saturating_sub(1))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let to = line[end..]
+        .char_indices()
+        .nth(GREP_CONTEXT)
+        .map(|(i, _)| end + i)
+        .unwrap_or(line.len());
+    let mut out = String::new();
+    if from > 0 {
+        out.push('…');
+    }
+    out.push_str(&line[from..to].replace('\t', " "));
+    if to < line.len() {
+        out.push('…');
+    }
+    out
+}
+
+fn sort_newest_first(items: &mut [SessionItem]) {
+    items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+}
+
+fn mtime_ms(path: &Path) -> u128 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_terms_match_word_prefixes() {
+        let doc = terms("Fix the `LoginError` in auth_service.rs (see #42)");
+        assert!(doc.contains("loginerror"));
+        assert!(doc.contains("auth_service"));
+        assert!(matches_all(&doc, &terms("login auth")));
+        assert!(matches_all(&doc, &terms("42 RS")));
+        assert!(!matches_all(&doc, &terms("login logout")));
+    }
+
+    #[test]
+    fn grep_snippet_keeps_context_around_match() {
+        let line = format!("{}needle{}", "a".repeat(200), "b".repeat(200));
+        let s = snippet(&line, 200, 206);
+        assert!(s.starts_with('…') && s.ends_with('…'));
+        assert!(s.contains(&format!("{}needle{}", "a".repeat(80), "b".repeat(80))));
+        assert_eq!(snippet("short needle", 6, 12), "short needle");
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::sessions::SessionItem;
+
+/// Bumped when rollout parsing changes, so entries parsed the old way are dropped.
+const VERSION: u32 = 1;
+
+/// Rollout metadata parsed by earlier runs, kept next to the config as `cache.json` so
+/// listings only read new or changed rollouts. An entry is reused while its file's mtime and
+/// size are unchanged; `--refresh` drops the file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SessionCache {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    files: HashMap<PathBuf, CachedRollout>,
+    #[serde(skip)]
+    changed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedRollout {
+    #[serde(flatten)]
+    stamp: Stamp,
+    /// As parsed, before `sessions.path_rewrites`; `None` for a file without session metadata.
+    item: Option<SessionItem>,
+}
+
+/// What a rollout looked like on disk when it was parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamp {
+    mtime: SystemTime,
+    len: u64,
+}
+
+pub fn stamp(path: &Path) -> Option<Stamp> {
+    let meta = fs::metadata(path).ok()?;
+    Some(Stamp {
+        mtime: meta.modified().ok()?,
+        len: meta.len(),
+    })
+}
+
+pub fn cache_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("cache.json")
+}
+
+/// Forget all cached rollouts (`--refresh`).
+pub fn clear(config_dir: &Path) -> Result<()> {
+    let path = cache_path(config_dir);
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl SessionCache {
+    /// A missing, unreadable or outdated cache is an empty one.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<Self>(&s).ok())
+            .filter(|c| c.version == VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Write the cache back if a rollout was (re)parsed, atomically (a per-process temp file +
+    /// rename) so concurrent invocations never interleave their writes. Entries of deleted
+    /// rollouts stay until `--refresh`.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        self.version = VERSION;
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        let s = serde_json::to_string(self)?;
+        fs::write(&tmp, s).with_context(|| format!("failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// The cached metadata of the rollout at `path`, if it was parsed when it had `stamp`.
+    pub fn get(&self, path: &Path, stamp: Stamp) -> Option<Option<SessionItem>> {
+        let entry = self.files.get(path)?;
+        (entry.stamp == stamp).then(|| entry.item.clone())
+    }
+
+    /// Remember `item` as parsed from `path` with `stamp` (taken before reading it).
+    pub fn insert(&mut self, path: &Path, stamp: Stamp, item: Option<SessionItem>) {
+        self.changed = true;
+        self.files
+            .insert(path.to_path_buf(), CachedRollout { stamp, item });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_entries_until_the_rollout_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        fs::create_dir_all(&dir).unwrap();
+        let rollout = dir.join("rollout.jsonl");
+        fs::write(&rollout, "{}\n").unwrap();
+        let before = stamp(&rollout).unwrap();
+
+        let mut cache = SessionCache::default();
+        cache.insert(&rollout, before, None);
+        cache.save(&cache_path(&dir)).unwrap();
+        let cache = SessionCache::load(&cache_path(&dir));
+        assert!(matches!(cache.get(&rollout, before), Some(None)));
+
+        fs::write(&rollout, "{}\n{}\n").unwrap();
+        let after = stamp(&rollout).unwrap();
+        assert!(cache.get(&rollout, after).is_none());
+    }
+}
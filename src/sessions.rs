@@ -1,17 +1,27 @@
 use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ffi::OsStr;
 use std::fmt;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
 
-use crate::config::Config;
+use crate::codex_history;
+use crate::config::{Config, DateStyle, PathStyle, Scope};
 use crate::pathfmt;
+use crate::session_cache::{self, SessionCache};
 use crate::timefmt;
+use crate::ui;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionItem {
     pub id: String,
     pub created_at: Option<String>,
@@ -20,20 +30,81 @@ pub struct SessionItem {
     pub cli_version: Option<String>,
     pub model_provider: Option<String>,
     pub source: Option<String>,
-    pub path: PathBuf,
+    /// Model of the first turn (from `turn_context`).
+    #[serde(default)]
+    pub model: Option<String>,
+    /// The rollout file; `None` for a session only known from `history.jsonl`, which has none.
+    pub path: Option<PathBuf>,
+    /// How the last recorded turn ended.
+    #[serde(default)]
+    pub outcome: Outcome,
 }
 
-impl fmt::Display for SessionItem {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    #[default]
+    Unknown,
+    /// The last turn finished normally.
+    Completed,
+    /// The last turn was aborted or hit an error.
+    Failed,
+    /// A turn started but never finished (still running, or the process died mid-task).
+    Running,
+}
+
+impl Outcome {
+    pub fn glyph(self) -> char {
+        match self {
+            Outcome::Unknown => ' ',
+            Outcome::Completed => '✓',
+            Outcome::Failed => '✗',
+            Outcome::Running => '⋯',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeKind {
+    Provider,
+    Source,
+    Model,
+}
+
+impl BadgeKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            BadgeKind::Provider => "provider",
+            BadgeKind::Source => "source",
+            BadgeKind::Model => "model",
+        }
+    }
+}
+
+impl SessionItem {
+    /// Short labels shown next to a row: provider, source and model, when known.
+    pub fn badges(&self) -> Vec<(BadgeKind, &str)> {
+        [
+            (BadgeKind::Provider, self.model_provider.as_deref()),
+            (BadgeKind::Source, self.source.as_deref()),
+            (BadgeKind::Model, self.model.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(kind, v)| v.map(|v| (kind, v)))
+        .collect()
+    }
+
+    /// The row without its metadata: outcome, age, id, cwd and summary.
+    pub fn row_text(&self, dates: DateStyle, paths: PathStyle) -> String {
         let id_short = self.id.chars().take(8).collect::<String>();
         let when = self
             .created_at
             .as_deref()
             .and_then(timefmt::parse_rfc3339)
-            .map(|dt| format!("{} {}", timefmt::format_age(dt), timefmt::format_short(dt)))
+            .map(|dt| timefmt::format_when(dt, dates))
             .unwrap_or_else(|| "-".to_string());
 
-        let cwd = pathfmt::compact_path(&self.cwd, 56);
+        let cwd = pathfmt::row_path(&self.cwd, 56, paths);
 
         let summary = self
             .summary
@@ -41,32 +112,48 @@ impl fmt::Display for SessionItem {
             .map(|s| truncate_one_line(s, 90))
             .unwrap_or_default();
 
-        let mut meta = Vec::new();
-        if let Some(p) = self.model_provider.as_deref() {
-            meta.push(p);
-        }
-        if let Some(s) = self.source.as_deref() {
-            meta.push(s);
+        let glyph = self.outcome.glyph();
+        if summary.is_empty() {
+            format!("{glyph} {:<15}  {:<8}  {}", when, id_short, cwd)
+        } else {
+            format!(
+                "{glyph} {:<15}  {:<8}  {}  {}",
+                when, id_short, cwd, summary
+            )
         }
+    }
+}
+
+impl fmt::Display for SessionItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut meta = self
+            .badges()
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
         if let Some(v) = self.cli_version.as_deref() {
             meta.push(v);
         }
-        if let Some(name) = self.path.file_name().and_then(|s| s.to_str()) {
+        if let Some(name) = self
+            .path
+            .as_deref()
+            .and_then(Path::file_name)
+            .and_then(|s| s.to_str())
+        {
             meta.push(name);
         }
-        let meta = if meta.is_empty() {
-            String::new()
-        } else {
-            format!("  [{}]", meta.join(" "))
-        };
-
-        if summary.is_empty() {
-            write!(f, "{:<12}  {:<8}  {}{}", when, id_short, cwd, meta)
+        if meta.is_empty() {
+            write!(
+                f,
+                "{}",
+                self.row_text(DateStyle::default(), PathStyle::default())
+            )
         } else {
             write!(
                 f,
-                "{:<12}  {:<8}  {}  {}{}",
-                when, id_short, cwd, summary, meta
+                "{}  [{}]",
+                self.row_text(DateStyle::default(), PathStyle::default()),
+                meta.join(" ")
             )
         }
     }
@@ -80,76 +167,368 @@ pub enum SessionQuery {
     ForRepoRoot { repo_root: PathBuf, limit: usize },
 }
 
-pub fn list_recent_sessions(cfg: &Config, query: SessionQuery) -> Result<Vec<SessionItem>> {
-    let (limit, filter) = match query {
-        SessionQuery::All { limit } => (limit, Filter::All),
-        SessionQuery::Scoped { limit } => (limit, Filter::Scoped),
-        SessionQuery::ForCwd { cwd, limit } => (limit, Filter::ForCwd(cwd)),
-        SessionQuery::ForRepoRoot { repo_root, limit } => (limit, Filter::ForRepoRoot(repo_root)),
-    };
+/// Sessions belonging to a target folder: everything in the same git repo when the folder is a
+/// repo, otherwise everything under the folder.
+pub fn query_for_target(path: &Path, limit: usize) -> SessionQuery {
+    match find_git_root(path) {
+        Some(repo_root) => SessionQuery::ForRepoRoot { repo_root, limit },
+        None => SessionQuery::ForCwd {
+            cwd: path.to_path_buf(),
+            limit,
+        },
+    }
+}
 
-    let sessions_root = cfg.sessions.codex_home.join("sessions");
-    if !sessions_root.exists() {
-        return Ok(Vec::new());
+impl SessionQuery {
+    fn into_parts(self, cfg: &Config) -> (usize, Filter) {
+        match self {
+            SessionQuery::All { limit } => (limit, Filter::All),
+            SessionQuery::Scoped { limit } => (limit, Filter::Scoped(cfg.scope())),
+            SessionQuery::ForCwd { cwd, limit } => (limit, Filter::ForCwd(cwd)),
+            SessionQuery::ForRepoRoot { repo_root, limit } => {
+                (limit, Filter::ForRepoRoot(repo_root))
+            }
+        }
     }
+}
 
-    let mut items = Vec::new();
+/// Newest-first sessions shared by everything in one invocation, so the sessions tree is
+/// scanned once instead of once per consumer. Grows only when asked for more, continuing the
+/// scan from the last rollout it read.
+#[derive(Debug, Default)]
+pub struct SessionIndex {
+    /// `scanned` plus the sessions only known from `history.jsonl`.
+    items: Vec<SessionItem>,
+    /// Sessions from rollouts, in scan order.
+    scanned: Vec<SessionItem>,
+    /// The last rollout the scans got to; the next one starts below it.
+    resume_after: Option<PathBuf>,
+    limit: usize,
+    complete: bool,
+    /// Notes about unreadable folders the scans skipped.
+    skipped: Vec<String>,
+}
 
-    for year_path in collect_dirs_desc(&sessions_root)? {
-        for month_path in collect_dirs_desc(&year_path)? {
-            for day_path in collect_dirs_desc(&month_path)? {
-                for p in collect_rollout_files_desc(&day_path)? {
-                    if items.len() >= limit {
-                        return Ok(items);
-                    }
-                    let Some(session) = read_session_meta(&p).ok().flatten() else {
-                        continue;
-                    };
-                    if !matches_filter(cfg, &filter, &session.cwd) {
-                        continue;
-                    }
-                    items.push(session);
-                }
-            }
+impl SessionIndex {
+    /// The newest `limit` sessions.
+    pub fn recent(&mut self, cfg: &Config, limit: usize) -> Result<&[SessionItem]> {
+        if limit > self.limit && !self.complete {
+            self.skipped.clear();
+            let want = limit - self.scanned.len();
+            let (more, last) = scan_rollouts_after(
+                cfg,
+                want,
+                |_| true,
+                self.resume_after.as_deref(),
+                &mut self.skipped,
+            )?;
+            self.complete = more.len() < want;
+            self.resume_after = last.or(self.resume_after.take());
+            self.scanned.extend(more);
+            self.items = self.scanned.clone();
+            fill_from_history(cfg, &Filter::All, limit, &mut self.items);
+            self.limit = limit;
         }
+        Ok(&self.items[..limit.min(self.items.len())])
     }
 
+    /// The newest session matching `query` (its limit aside): a loaded one, else the first found
+    /// by scanning on from where the index stopped, without growing the index.
+    pub fn newest_matching(
+        &mut self,
+        cfg: &Config,
+        query: SessionQuery,
+    ) -> Result<Option<SessionItem>> {
+        let (_, filter) = query.into_parts(cfg);
+        let keep = |s: &SessionItem| matches_filter(&filter, &s.cwd);
+        if let Some(s) = self.items.iter().find(|s| keep(s)) {
+            return Ok(Some(s.clone()));
+        }
+        if self.complete {
+            return Ok(None);
+        }
+        let after = self.resume_after.as_deref();
+        let (mut found, _) = scan_rollouts_after(cfg, 1, keep, after, &mut self.skipped)?;
+        Ok(found.pop())
+    }
+
+    /// Notes about folders the last scan couldn't read (cleared by taking them).
+    pub fn take_skipped(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.skipped)
+    }
+}
+
+pub fn list_recent_sessions(cfg: &Config, query: SessionQuery) -> Result<Vec<SessionItem>> {
+    let mut skipped = Vec::new();
+    let items = scan_recent_sessions(cfg, query, &mut skipped)?;
+    for note in skipped.iter() {
+        ui::print_warn(note);
+    }
     Ok(items)
 }
 
-pub fn find_session_by_id(cfg: &Config, id: &str) -> Result<Option<SessionItem>> {
+/// [`list_recent_sessions`], noting unreadable folders in `skipped` instead of printing them.
+fn scan_recent_sessions(
+    cfg: &Config,
+    query: SessionQuery,
+    skipped: &mut Vec<String>,
+) -> Result<Vec<SessionItem>> {
+    let (limit, filter) = query.into_parts(cfg);
+    let mut items = scan_rollouts(cfg, limit, |s| matches_filter(&filter, &s.cwd), skipped)?;
+    fill_from_history(cfg, &filter, limit, &mut items);
+    Ok(items)
+}
+
+/// The newest `limit` sessions that `keep` accepts. The tree is walked on one thread while
+/// rollouts are parsed and filtered on others; results are put back in walk order (newest
+/// first) and everything stops once `limit` have been kept.
+fn scan_rollouts(
+    cfg: &Config,
+    limit: usize,
+    keep: impl Fn(&SessionItem) -> bool + Sync,
+    skipped: &mut Vec<String>,
+) -> Result<Vec<SessionItem>> {
+    Ok(scan_rollouts_after(cfg, limit, keep, None, skipped)?.0)
+}
+
+/// [`scan_rollouts`] over the rollouts older than `after` only (all of them for `None`), also
+/// returning the last rollout it read, where a later scan can go on.
+fn scan_rollouts_after(
+    cfg: &Config,
+    limit: usize,
+    keep: impl Fn(&SessionItem) -> bool + Sync,
+    after: Option<&Path>,
+    skipped: &mut Vec<String>,
+) -> Result<(Vec<SessionItem>, Option<PathBuf>)> {
     let sessions_root = cfg.sessions.codex_home.join("sessions");
-    if !sessions_root.exists() {
-        return Ok(None);
+    if !sessions_root.exists() || limit == 0 {
+        return Ok((Vec::new(), None));
+    }
+
+    let mut days = day_dirs_desc(&sessions_root, skipped);
+    // Walk order is descending path order, so older rollouts are the smaller paths.
+    if let Some(day) = after.and_then(Path::parent) {
+        days.retain(|d| d.as_path() <= day);
     }
-    for year_path in collect_dirs_desc(&sessions_root)? {
-        for month_path in collect_dirs_desc(&year_path)? {
-            for day_path in collect_dirs_desc(&month_path)? {
-                for p in collect_rollout_files_desc(&day_path)? {
-                    let Some(session) = read_session_meta(&p).ok().flatten() else {
-                        continue;
+    let cache_path = cfg.dir.as_deref().map(session_cache::cache_path);
+    let cache = Mutex::new(
+        cache_path
+            .as_deref()
+            .map(SessionCache::load)
+            .unwrap_or_default(),
+    );
+    let stop = AtomicBool::new(false);
+    let (path_tx, path_rx) = mpsc::sync_channel::<(usize, PathBuf)>(64);
+    let path_rx = Arc::new(Mutex::new(path_rx));
+    let (item_tx, item_rx) = mpsc::channel::<(usize, PathBuf, Option<SessionItem>)>();
+    let scanned = thread::scope(|s| {
+        let (stop, keep, cache) = (&stop, &keep, &cache);
+        let walker = s.spawn(|| walk_rollouts(&days, after, stop, path_tx));
+        for _ in 0..parse_threads() {
+            let (path_rx, item_tx) = (Arc::clone(&path_rx), item_tx.clone());
+            s.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let next = path_rx.lock().ok().and_then(|rx| rx.recv().ok());
+                    let Some((seq, path)) = next else {
+                        break;
                     };
-                    if session.id == id {
-                        return Ok(Some(session));
+                    let item = cached_session_meta(cfg, cache, &path).filter(keep);
+                    if item_tx.send((seq, path, item)).is_err() {
+                        break;
                     }
                 }
+            });
+        }
+        drop(path_rx);
+        drop(item_tx);
+
+        let mut items = Vec::new();
+        let mut last = None;
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        'collect: for (seq, path, item) in item_rx.iter() {
+            pending.insert(seq, (path, item));
+            while let Some((path, item)) = pending.remove(&next) {
+                next += 1;
+                last = Some(path);
+                items.extend(item);
+                if items.len() >= limit {
+                    stop.store(true, Ordering::Relaxed);
+                    break 'collect;
+                }
             }
         }
+        match walker.join() {
+            Ok(notes) => skipped.extend(notes),
+            Err(_) => anyhow::bail!("session scan panicked"),
+        }
+        Ok((items, last))
+    })?;
+    // Best effort: without a writable config folder, rollouts are just read every time.
+    if let Some(path) = cache_path.as_deref()
+        && let Ok(mut cache) = cache.into_inner()
+    {
+        let _ = cache.save(path);
+    }
+    Ok(scanned)
+}
+
+/// Sessions whose rollout has no usable prompt take their summary from Codex's `history.jsonl`.
+/// With `sessions.history_only`, unfiltered listings also get the sessions only known from
+/// there (their rollout is gone), without a cwd.
+fn fill_from_history(cfg: &Config, filter: &Filter, limit: usize, items: &mut Vec<SessionItem>) {
+    let merge = cfg.sessions.history_only && matches!(filter, Filter::All);
+    if !merge && items.iter().all(|s| s.summary.is_some()) {
+        return;
+    }
+    let history = codex_history::load(&cfg.sessions.codex_home);
+    for s in items.iter_mut().filter(|s| s.summary.is_none()) {
+        s.summary = history
+            .first_prompt(&s.id)
+            .map(|t| normalize_summary(t.to_string()));
+    }
+    if !merge {
+        return;
+    }
+    let created = |s: &SessionItem| s.created_at.as_deref().and_then(timefmt::parse_rfc3339);
+    // A full page only takes history sessions newer than its oldest entry.
+    let oldest = (items.len() >= limit)
+        .then(|| items.last().and_then(created))
+        .flatten();
+    let known: HashSet<String> = items.iter().map(|s| s.id.clone()).collect();
+    for (id, ts, text) in history.sessions() {
+        let Some(created_at) = timefmt::from_unix(ts) else {
+            continue;
+        };
+        if known.contains(id) || oldest.is_some_and(|o| created_at < o) {
+            continue;
+        }
+        items.push(SessionItem {
+            id: id.to_string(),
+            created_at: created_at.format(&Rfc3339).ok(),
+            cwd: PathBuf::new(),
+            summary: Some(normalize_summary(text.to_string())),
+            cli_version: None,
+            model_provider: None,
+            source: None,
+            model: None,
+            path: None,
+            outcome: Outcome::Unknown,
+        });
     }
-    Ok(None)
+    items.sort_by_key(|s| Reverse(created(s)));
+    items.truncate(limit);
+}
+
+/// Threads parsing rollouts in [`list_recent_sessions`].
+fn parse_threads() -> usize {
+    thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(8)
+}
+
+/// Send every rollout file in `days` older than `after`, newest first and numbered in that
+/// order, until `stop` is set or nobody is receiving anymore. Returns notes about day folders
+/// it couldn't read.
+fn walk_rollouts(
+    days: &[PathBuf],
+    after: Option<&Path>,
+    stop: &AtomicBool,
+    tx: mpsc::SyncSender<(usize, PathBuf)>,
+) -> Vec<String> {
+    let mut skipped = Vec::new();
+    let mut seq = 0;
+    for day_path in days.iter() {
+        for p in readable(collect_rollout_files_desc(day_path), &mut skipped) {
+            if after.is_some_and(|a| p.as_path() >= a) {
+                continue;
+            }
+            if stop.load(Ordering::Relaxed) || tx.send((seq, p)).is_err() {
+                return skipped;
+            }
+            seq += 1;
+        }
+    }
+    skipped
+}
+
+/// `sessions/YYYY/MM/DD` folders, newest first. A folder that can't be read (permissions, a
+/// disconnected mount) is skipped with a note in `skipped` instead of failing the listing.
+fn day_dirs_desc(sessions_root: &Path, skipped: &mut Vec<String>) -> Vec<PathBuf> {
+    let mut days = Vec::new();
+    for year_path in readable(collect_dirs_desc(sessions_root), skipped) {
+        for month_path in readable(collect_dirs_desc(&year_path), skipped) {
+            days.extend(readable(collect_dirs_desc(&month_path), skipped));
+        }
+    }
+    days
+}
+
+fn readable(listing: Result<Vec<PathBuf>>, skipped: &mut Vec<String>) -> Vec<PathBuf> {
+    listing.unwrap_or_else(|e| {
+        skipped.push(format!("skipped: {e:#}"));
+        Vec::new()
+    })
+}
+
+/// The sessions with one of `ids`, newest first, found in a single scan that stops once each
+/// id has turned up.
+pub fn find_sessions_by_ids(cfg: &Config, ids: &BTreeSet<String>) -> Result<Vec<SessionItem>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut skipped = Vec::new();
+    scan_rollouts(cfg, ids.len(), |s| ids.contains(&s.id), &mut skipped)
+}
+
+/// Session `id`: the rollout named after it if there is one, else the newest rollout whose
+/// metadata has that id.
+pub fn find_session_by_id(cfg: &Config, id: &str) -> Result<Option<SessionItem>> {
+    if let Some(path) = rollout_named(cfg, id)?
+        && let Some(session) = read_session_meta(cfg, &path).ok().flatten()
+        && session.id == id
+    {
+        return Ok(Some(session));
+    }
+    let mut skipped = Vec::new();
+    Ok(scan_rollouts(cfg, 1, |s| s.id == id, &mut skipped)?.pop())
+}
+
+/// The rollout of session `id`, even one whose metadata no longer parses.
+pub fn find_rollout_by_id(cfg: &Config, id: &str) -> Result<Option<PathBuf>> {
+    match rollout_named(cfg, id)? {
+        Some(path) => Ok(Some(path)),
+        None => {
+            let mut skipped = Vec::new();
+            Ok(scan_rollouts(cfg, 1, |s| s.id == id, &mut skipped)?
+                .pop()
+                .and_then(|s| s.path))
+        }
+    }
+}
+
+/// Codex names rollouts `rollout-<time>-<id>.jsonl`, so a session's file can usually be found
+/// by name alone, without reading any rollout.
+fn rollout_named(cfg: &Config, id: &str) -> Result<Option<PathBuf>> {
+    let suffix = format!("-{id}");
+    Ok(all_rollout_files(cfg)?.into_iter().find(|p| {
+        p.file_stem()
+            .and_then(OsStr::to_str)
+            .is_some_and(|stem| stem.ends_with(&suffix))
+    }))
 }
 
 enum Filter {
     All,
-    Scoped,
+    Scoped(Scope),
     ForCwd(PathBuf),
     ForRepoRoot(PathBuf),
 }
 
-fn matches_filter(cfg: &Config, filter: &Filter, cwd: &Path) -> bool {
+fn matches_filter(filter: &Filter, cwd: &Path) -> bool {
     match filter {
         Filter::All => true,
-        Filter::Scoped => cfg.is_scoped_target(cwd),
+        Filter::Scoped(scope) => scope.contains(cwd),
         Filter::ForCwd(root) => cwd.starts_with(root),
         Filter::ForRepoRoot(repo_root) => find_git_root(cwd).is_some_and(|r| r == *repo_root),
     }
@@ -173,6 +552,241 @@ pub fn git_root_for_path(start: &Path) -> Option<PathBuf> {
     find_git_root(start)
 }
 
+/// Every rollout file under `codex_home/sessions`, newest first (unreadable folders skipped).
+pub fn all_rollout_files(cfg: &Config) -> Result<Vec<PathBuf>> {
+    let sessions_root = cfg.sessions.codex_home.join("sessions");
+    let mut files = Vec::new();
+    if !sessions_root.exists() {
+        return Ok(files);
+    }
+    let mut skipped = Vec::new();
+    for day_path in day_dirs_desc(&sessions_root, &mut skipped) {
+        files.extend(readable(
+            collect_rollout_files_desc(&day_path),
+            &mut skipped,
+        ));
+    }
+    Ok(files)
+}
+
+/// What the session store holds, for `where-sessions`.
+#[derive(Debug, Default)]
+pub struct StoreSummary {
+    /// Rollout files found under `sessions/`.
+    pub rollouts: usize,
+    /// Those with a usable `session_meta` record.
+    pub sessions: usize,
+    /// Sessions whose cwd is under a configured target.
+    pub scoped: usize,
+    pub oldest: Option<time::OffsetDateTime>,
+    pub newest: Option<time::OffsetDateTime>,
+    /// Notes about folders that couldn't be read.
+    pub skipped: Vec<String>,
+}
+
+/// Read every rollout's metadata and count what the picker can see.
+pub fn store_summary(cfg: &Config) -> Result<StoreSummary> {
+    let mut summary = StoreSummary {
+        rollouts: all_rollout_files(cfg)?.len(),
+        ..StoreSummary::default()
+    };
+    let items = scan_recent_sessions(
+        cfg,
+        SessionQuery::All { limit: usize::MAX },
+        &mut summary.skipped,
+    )?;
+    let scope = cfg.scope();
+    for s in items.iter().filter(|s| s.path.is_some()) {
+        summary.sessions += 1;
+        if scope.contains(&s.cwd) {
+            summary.scoped += 1;
+        }
+        if let Some(dt) = s.created_at.as_deref().and_then(timefmt::parse_rfc3339) {
+            summary.oldest = Some(summary.oldest.map_or(dt, |o| o.min(dt)));
+            summary.newest = Some(summary.newest.map_or(dt, |n| n.max(dt)));
+        }
+    }
+    Ok(summary)
+}
+
+/// Metadata of a single rollout file (`None` when it has no usable `session_meta`).
+pub fn session_from_rollout(cfg: &Config, path: &Path) -> Result<Option<SessionItem>> {
+    read_session_meta(cfg, path)
+}
+
+/// Fail unless `path` is a rollout codex can resume: it exists, isn't empty, and its first line
+/// is the `session_meta` record.
+pub fn check_rollout(path: &Path) -> Result<()> {
+    let file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            anyhow::bail!("the session's rollout file is gone: {}", path.display())
+        }
+        Err(e) => return Err(e).with_context(|| format!("failed to open {}", path.display())),
+    };
+    let mut reader = BufReader::new(file);
+    let mut buf = Vec::new();
+    let n = read_line_capped(&mut reader, &mut buf, META_LINE_BYTES)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    if n == 0 {
+        anyhow::bail!("the session's rollout file is empty: {}", path.display());
+    }
+    let line = String::from_utf8_lossy(&buf);
+    let is_meta = match serde_json::from_str::<Value>(line.trim()) {
+        Ok(v) => v.get("type").and_then(Value::as_str) == Some("session_meta"),
+        // Longer than the cap: judge by the start of the line.
+        Err(_) => n > buf.len() && line.contains("session_meta"),
+    };
+    if !is_meta {
+        anyhow::bail!(
+            "the session's rollout file doesn't start with its session_meta record: {}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// One text block of a user or assistant message in a rollout.
+#[derive(Debug, Clone)]
+pub struct RolloutMessage {
+    pub role: String,
+    pub text: String,
+}
+
+/// What a full read of a rollout yields beyond its metadata.
+#[derive(Debug, Clone, Default)]
+pub struct RolloutContent {
+    /// Every message text block (user and assistant), in order.
+    pub messages: Vec<RolloutMessage>,
+    /// Files the session edited (via `apply_patch`), relative to its cwd when inside it, with
+    /// how many patches touched each.
+    pub touched_files: BTreeMap<String, usize>,
+}
+
+/// Read a whole rollout: messages plus files touched by patches.
+pub fn read_rollout(path: &Path) -> Result<RolloutContent> {
+    let file =
+        fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut out = RolloutContent::default();
+    let mut cwd: Option<PathBuf> = None;
+    // By bytes: a rollout cut short by a crash can end in the middle of a UTF-8 character.
+    for line in BufReader::new(file).split(b'\n') {
+        let line = line.with_context(|| format!("failed to read {}", path.display()))?;
+        let line = String::from_utf8_lossy(&line);
+        let Ok(v) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        let Some(payload) = v.get("payload") else {
+            continue;
+        };
+        match v.get("type").and_then(|t| t.as_str()) {
+            Some("session_meta") if cwd.is_none() => {
+                cwd = payload
+                    .get("cwd")
+                    .and_then(|x| x.as_str())
+                    .map(PathBuf::from);
+            }
+            Some("response_item") => {
+                if payload.get("type").and_then(|x| x.as_str()) == Some("message") {
+                    collect_message(payload, &mut out.messages);
+                } else {
+                    for p in patched_paths(payload) {
+                        let p = relative_to(&p, cwd.as_deref());
+                        *out.touched_files.entry(p).or_default() += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+fn collect_message(payload: &Value, out: &mut Vec<RolloutMessage>) {
+    let role = payload
+        .get("role")
+        .and_then(|r| r.as_str())
+        .unwrap_or_default();
+    let Some(content) = payload.get("content").and_then(|c| c.as_array()) else {
+        return;
+    };
+    for item in content {
+        if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
+            out.push(RolloutMessage {
+                role: role.to_string(),
+                text: text.to_string(),
+            });
+        }
+    }
+}
+
+/// Paths named by an `apply_patch` tool call. Codex records it either as a custom tool call
+/// with the patch in `input`, or as a function call whose JSON `arguments` carry the patch in
+/// `input` or as the last word of a `command` (`["apply_patch", "<patch>"]`).
+fn patched_paths(payload: &Value) -> Vec<String> {
+    let mut patches: Vec<String> = Vec::new();
+    match payload.get("type").and_then(|t| t.as_str()) {
+        Some("custom_tool_call") => {
+            if let Some(input) = payload.get("input").and_then(|x| x.as_str()) {
+                patches.push(input.to_string());
+            }
+        }
+        Some("function_call") => {
+            let Some(args) = payload
+                .get("arguments")
+                .and_then(|a| a.as_str())
+                .and_then(|a| serde_json::from_str::<Value>(a).ok())
+            else {
+                return Vec::new();
+            };
+            if let Some(input) = args.get("input").and_then(|x| x.as_str()) {
+                patches.push(input.to_string());
+            }
+            if let Some(cmd) = args.get("command").and_then(|c| c.as_array()) {
+                patches.extend(cmd.iter().filter_map(|x| x.as_str()).map(str::to_string));
+            }
+        }
+        _ => return Vec::new(),
+    }
+    patches
+        .iter()
+        .filter(|p| p.contains("*** Begin Patch"))
+        .flat_map(|p| paths_in_patch(p))
+        .collect()
+}
+
+fn paths_in_patch(patch: &str) -> Vec<String> {
+    const MARKERS: &[&str] = &[
+        "*** Add File: ",
+        "*** Update File: ",
+        "*** Delete File: ",
+        "*** Move to: ",
+    ];
+    patch
+        .lines()
+        .filter_map(|l| MARKERS.iter().find_map(|m| l.strip_prefix(m)))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+fn relative_to(p: &str, cwd: Option<&Path>) -> String {
+    match cwd.and_then(|c| Path::new(p).strip_prefix(c).ok()) {
+        Some(rel) => rel.to_string_lossy().to_string(),
+        None => p.to_string(),
+    }
+}
+
+/// The first `n` assistant message texts, each cut to `max_chars` (for `--resume --deep`).
+pub fn assistant_excerpts(messages: &[RolloutMessage], n: usize, max_chars: usize) -> Vec<String> {
+    messages
+        .iter()
+        .filter(|m| m.role == "assistant" && !m.text.trim().is_empty())
+        .take(n)
+        .map(|m| m.text.chars().take(max_chars).collect())
+        .collect()
+}
+
 fn collect_dirs_desc(parent: &Path) -> Result<Vec<PathBuf>> {
     let mut dirs = Vec::new();
     for ent in
@@ -194,14 +808,7 @@ fn collect_dirs_desc(parent: &Path) -> Result<Vec<PathBuf>> {
             dirs.push(path);
         }
     }
-    dirs.sort_by_key(|p| {
-        Reverse(
-            p.file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string(),
-        )
-    });
+    dirs.sort_by_key(|p| Reverse(p.file_name().map(OsStr::to_os_string)));
     Ok(dirs)
 }
 
@@ -224,38 +831,149 @@ fn collect_rollout_files_desc(day_path: &Path) -> Result<Vec<PathBuf>> {
         }
         files.push(path);
     }
-    files.sort_by_key(|p| {
-        Reverse(
-            p.file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string(),
-        )
-    });
+    files.sort_by_key(|p| Reverse(p.file_name().map(OsStr::to_os_string)));
     Ok(files)
 }
 
-fn read_session_meta(path: &Path) -> Result<Option<SessionItem>> {
-    let file =
+fn read_session_meta(cfg: &Config, path: &Path) -> Result<Option<SessionItem>> {
+    let mut item = read_rollout_meta(path)?;
+    if let Some(item) = item.as_mut() {
+        item.cwd = cfg.rewrite_session_path(&item.cwd);
+    }
+    Ok(item)
+}
+
+/// [`read_session_meta`] through `cache`: a rollout unchanged since it was cached isn't read.
+fn cached_session_meta(
+    cfg: &Config,
+    cache: &Mutex<SessionCache>,
+    path: &Path,
+) -> Option<SessionItem> {
+    let stamp = session_cache::stamp(path);
+    let cached = stamp.and_then(|stamp| cache.lock().ok()?.get(path, stamp));
+    let mut item = match cached {
+        Some(item) => item,
+        None => {
+            let item = read_rollout_meta(path).ok()?;
+            if let Some(stamp) = stamp
+                && let Ok(mut cache) = cache.lock()
+            {
+                cache.insert(path, stamp, item.clone());
+            }
+            item
+        }
+    };
+    if let Some(item) = item.as_mut() {
+        item.cwd = cfg.rewrite_session_path(&item.cwd);
+    }
+    item
+}
+
+/// A rollout's metadata and outcome, with its cwd as recorded.
+fn read_rollout_meta(path: &Path) -> Result<Option<SessionItem>> {
+    let mut file =
         fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
-    let reader = BufReader::new(file);
+    let mut item = parse_session_meta(BufReader::new(&file), path)?;
+    if let Some(item) = item.as_mut() {
+        item.outcome = read_outcome(&mut file);
+    }
+    Ok(item)
+}
+
+/// How much of a rollout's end is read to find how its last turn ended.
+const OUTCOME_TAIL_BYTES: u64 = 64 * 1024;
+
+/// How the rollout open as `file` ended, from its tail.
+fn read_outcome(file: &mut fs::File) -> Outcome {
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(OUTCOME_TAIL_BYTES);
+    let mut buf = Vec::new();
+    if file.seek(SeekFrom::Start(start)).is_err() || file.read_to_end(&mut buf).is_err() {
+        return Outcome::Unknown;
+    }
+    let tail = String::from_utf8_lossy(&buf);
+    let mut lines = tail.lines();
+    if start > 0 {
+        // Most likely cut mid-line.
+        lines.next();
+    }
+    outcome_from_lines(lines)
+}
 
+/// Turn lifecycle events (`task_started` / `task_complete` / `turn_aborted` / `error`) decide;
+/// rollouts without them count as completed when the last message is the assistant's.
+fn outcome_from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Outcome {
+    let mut from_events: Option<Outcome> = None;
+    let mut last_role_assistant: Option<bool> = None;
+    for line in lines {
+        let Ok(v) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        let Some(payload) = v.get("payload") else {
+            continue;
+        };
+        let payload_type = payload.get("type").and_then(|t| t.as_str());
+        match (v.get("type").and_then(|t| t.as_str()), payload_type) {
+            (Some("event_msg"), Some("task_started")) => from_events = Some(Outcome::Running),
+            (Some("event_msg"), Some("task_complete")) => from_events = Some(Outcome::Completed),
+            (Some("event_msg"), Some("turn_aborted" | "error")) => {
+                from_events = Some(Outcome::Failed)
+            }
+            (Some("response_item"), Some("message")) => {
+                last_role_assistant =
+                    Some(payload.get("role").and_then(|r| r.as_str()) == Some("assistant"));
+            }
+            _ => {}
+        }
+    }
+    match (from_events, last_role_assistant) {
+        (Some(o), _) => o,
+        (None, Some(true)) => Outcome::Completed,
+        _ => Outcome::Unknown,
+    }
+}
+
+/// How much of a rollout's head is read for its metadata: lines, bytes kept per line (the rest
+/// of a longer line is skipped), and bytes overall.
+const META_MAX_LINES: usize = 300;
+const META_LINE_BYTES: usize = 256 * 1024;
+const META_FILE_BYTES: u64 = 4 * 1024 * 1024;
+/// Lines read past the first prompt looking for the model's `turn_context`, which older CLIs
+/// and exec sessions never write.
+const MODEL_LOOKAHEAD_LINES: usize = 8;
+
+/// Parse session metadata from rollout contents; `path` is recorded as the rollout location.
+pub fn parse_session_meta(reader: impl BufRead, path: &Path) -> Result<Option<SessionItem>> {
     let mut created_at: Option<String> = None;
     let mut id: Option<String> = None;
     let mut cwd: Option<PathBuf> = None;
     let mut cli_version: Option<String> = None;
     let mut model_provider: Option<String> = None;
     let mut source: Option<String> = None;
+    let mut model: Option<String> = None;
     let mut first_user_text: Option<String> = None;
     let mut best_user_text: Option<String> = None;
+    let mut model_lines_left: Option<usize> = None;
 
-    for line_result in reader.lines().take(300) {
-        let line = line_result?;
+    let mut reader = reader.take(META_FILE_BYTES);
+    let mut buf = Vec::new();
+    for _ in 0..META_MAX_LINES {
+        buf.clear();
+        if read_line_capped(&mut reader, &mut buf, META_LINE_BYTES)? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
         let trimmed = line.trim();
         if trimmed.is_empty() {
             continue;
         }
         let Ok(v) = serde_json::from_str::<Value>(trimmed) else {
+            // A line cut at the budget: a `session_meta` line still starts with what's needed.
+            if trimmed.contains(r#""type":"session_meta""#) {
+                created_at = created_at.or_else(|| string_field(trimmed, "timestamp"));
+                id = id.or_else(|| string_field(trimmed, "id"));
+                cwd = cwd.or_else(|| string_field(trimmed, "cwd").map(PathBuf::from));
+            }
             continue;
         };
         match v.get("type").and_then(|t| t.as_str()) {
@@ -289,6 +1007,14 @@ fn read_session_meta(path: &Path) -> Result<Option<SessionItem>> {
                     .and_then(|x| x.as_str())
                     .map(|s| s.to_string());
             }
+            Some("turn_context") => {
+                model = model.or_else(|| {
+                    v.get("payload")
+                        .and_then(|p| p.get("model"))
+                        .and_then(|x| x.as_str())
+                        .map(|s| s.to_string())
+                });
+            }
             Some("response_item") => {
                 let Some(payload) = v.get("payload") else {
                     continue;
@@ -313,10 +1039,21 @@ fn read_session_meta(path: &Path) -> Result<Option<SessionItem>> {
         }
 
         if id.is_some() && cwd.is_some() && best_user_text.is_some() {
-            break;
+            let left = model_lines_left.get_or_insert(MODEL_LOOKAHEAD_LINES);
+            if model.is_some() || *left == 0 {
+                break;
+            }
+            *left -= 1;
         }
     }
 
+    let from_name = meta_from_file_name(path);
+    if id.is_none() {
+        id = from_name.as_ref().map(|(_, id)| id.clone());
+    }
+    if created_at.is_none() {
+        created_at = from_name.map(|(ts, _)| ts);
+    }
     let (Some(id), Some(cwd)) = (id, cwd) else {
         return Ok(None);
     };
@@ -329,10 +1066,76 @@ fn read_session_meta(path: &Path) -> Result<Option<SessionItem>> {
         cli_version,
         model_provider,
         source,
-        path: path.to_path_buf(),
+        model,
+        path: Some(path.to_path_buf()),
+        outcome: Outcome::Unknown,
     }))
 }
 
+/// Read one line into `buf`, keeping at most `cap` bytes of it; returns the bytes consumed
+/// (0 at the end of the input).
+fn read_line_capped(reader: &mut impl BufRead, buf: &mut Vec<u8>, cap: usize) -> io::Result<usize> {
+    let mut consumed = 0;
+    loop {
+        let avail = reader.fill_buf()?;
+        if avail.is_empty() {
+            return Ok(consumed);
+        }
+        let (chunk, done) = match avail.iter().position(|&b| b == b'\n') {
+            Some(i) => (&avail[..=i], true),
+            None => (avail, false),
+        };
+        let keep = chunk.len().min(cap.saturating_sub(buf.len()));
+        buf.extend_from_slice(&chunk[..keep]);
+        let n = chunk.len();
+        reader.consume(n);
+        consumed += n;
+        if done {
+            return Ok(consumed);
+        }
+    }
+}
+
+/// The first `"key":"…"` string in a (possibly truncated) JSON line, skipping escaped
+/// occurrences inside other strings.
+fn string_field(line: &str, key: &str) -> Option<String> {
+    let pat = format!(r#""{key}":"#);
+    let mut from = 0;
+    while let Some(i) = line[from..].find(&pat).map(|i| i + from) {
+        from = i + pat.len();
+        if line[..i].ends_with('\\') {
+            continue;
+        }
+        let rest = line[from..].trim_start();
+        return serde_json::Deserializer::from_str(rest)
+            .into_iter::<String>()
+            .next()?
+            .ok();
+    }
+    None
+}
+
+/// Ids of all rollouts under `sessions/`, read from their file names alone.
+pub fn rollout_file_ids(cfg: &Config) -> Result<HashSet<String>> {
+    Ok(all_rollout_files(cfg)?
+        .iter()
+        .filter_map(|p| meta_from_file_name(p))
+        .map(|(_, id)| id)
+        .collect())
+}
+
+/// `(timestamp, id)` from a `rollout-2026-02-01T10-00-00-<id>.jsonl` file name. Codex names
+/// rollouts in local time, which is read as UTC here (only used when the head has no timestamp).
+fn meta_from_file_name(path: &Path) -> Option<(String, String)> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
+    let (ts, id) = (stem.get(..19)?, stem.get(20..)?);
+    let (date, time) = ts.split_once('T')?;
+    let created_at = format!("{date}T{}Z", time.replace('-', ":"));
+    timefmt::parse_rfc3339(&created_at)?;
+    (!id.is_empty()).then(|| (created_at, id.to_string()))
+}
+
 fn extract_text_from_message_payload(payload: &Value) -> Option<String> {
     let content = payload.get("content")?.as_array()?;
     for item in content {
@@ -346,7 +1149,8 @@ fn extract_text_from_message_payload(payload: &Value) -> Option<String> {
     None
 }
 
-fn looks_like_boilerplate(text: &str) -> bool {
+/// Injected context (AGENTS.md, environment, shell output) rather than something the user typed.
+pub fn looks_like_boilerplate(text: &str) -> bool {
     let t = text.trim_start();
     t.starts_with("# AGENTS.md instructions")
         || t.starts_with("<environment_context>")
@@ -396,6 +1200,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lists_newest_matching_sessions_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let home = tmp.path().to_path_buf();
+        for (day, n, cwd) in [
+            ("01", 3, "/work/api"),
+            ("02", 3, "/work/web"),
+            ("03", 2, "/work/api"),
+        ] {
+            let dir = home.join("sessions/2026/02").join(day);
+            fs::create_dir_all(&dir).unwrap();
+            for i in 0..n {
+                let id = format!("{day}-{i}");
+                let line = format!(
+                    r#"{{"timestamp":"2026-02-{day}T10:00:0{i}Z","type":"session_meta","payload":{{"id":"{id}","cwd":"{cwd}"}}}}"#
+                );
+                let name = format!("rollout-2026-02-{day}T10-00-0{i}-{id}.jsonl");
+                fs::write(dir.join(name), line).unwrap();
+            }
+        }
+        let mut cfg = Config::default();
+        cfg.sessions.codex_home = home.clone();
+        let query = SessionQuery::ForCwd {
+            cwd: PathBuf::from("/work/api"),
+            limit: 3,
+        };
+        let ids: Vec<String> = list_recent_sessions(&cfg, query)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        // The index grows by continuing its scan, and looks past its pages when asked to.
+        let mut index = SessionIndex::default();
+        let ids_of = |items: &[SessionItem]| items.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        let first = ids_of(index.recent(&cfg, 2).unwrap());
+        let grown = ids_of(index.recent(&cfg, 5).unwrap());
+        let mut small = SessionIndex::default();
+        small.recent(&cfg, 1).unwrap();
+        let web = SessionQuery::ForCwd {
+            cwd: PathBuf::from("/work/web"),
+            limit: 1,
+        };
+        let newest_web = small.newest_matching(&cfg, web).unwrap().map(|s| s.id);
+        assert_eq!(ids, ["03-1", "03-0", "01-2"]);
+        assert_eq!(first, ["03-1", "03-0"]);
+        assert_eq!(grown, ["03-1", "03-0", "02-2", "02-1", "02-0"]);
+        assert_eq!(newest_web.as_deref(), Some("02-2"));
+    }
+
+    #[test]
+    fn finds_sessions_by_id_with_or_without_it_in_the_file_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let home = tmp.path().to_path_buf();
+        let dir = home.join("sessions/2026/03/01");
+        fs::create_dir_all(&dir).unwrap();
+        for (name, id) in [
+            ("rollout-2026-03-01T10-00-00-aaaa.jsonl", "aaaa"),
+            ("rollout-2026-03-01T10-00-01-renamed.jsonl", "bbbb"),
+        ] {
+            let line = format!(
+                r#"{{"timestamp":"2026-03-01T10:00:00Z","type":"session_meta","payload":{{"id":"{id}","cwd":"/work"}}}}"#
+            );
+            fs::write(dir.join(name), line).unwrap();
+        }
+        let mut cfg = Config::default();
+        cfg.sessions.codex_home = home.clone();
+        let by_name = find_session_by_id(&cfg, "aaaa").unwrap().map(|s| s.id);
+        let by_meta = find_rollout_by_id(&cfg, "bbbb").unwrap();
+        let missing = find_session_by_id(&cfg, "cccc").unwrap();
+        assert_eq!(by_name.as_deref(), Some("aaaa"));
+        assert!(by_meta.is_some_and(|p| p.ends_with("rollout-2026-03-01T10-00-01-renamed.jsonl")));
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn salvages_meta_from_oversized_head() {
+        let line = format!(
+            r#"{{"timestamp":"2026-01-19T15:21:26.203Z","type":"session_meta","payload":{{"cwd":"/work/api","instructions":"{}"}}}}"#,
+            "x".repeat(META_LINE_BYTES)
+        );
+        let path = Path::new("rollout-2026-01-19T16-21-26-019bd6d8-b99b.jsonl");
+        let item = parse_session_meta(line.as_bytes(), path).unwrap().unwrap();
+        assert_eq!(item.cwd, Path::new("/work/api"));
+        assert_eq!(item.id, "019bd6d8-b99b");
+        assert_eq!(item.created_at.as_deref(), Some("2026-01-19T15:21:26.203Z"));
+    }
+
+    #[test]
+    fn collects_paths_from_apply_patch_calls() {
+        let payload: Value = serde_json::from_str(
+            r#"{"type":"custom_tool_call","name":"apply_patch","input":"*** Begin Patch\n*** Update File: /work/api/src/tui.rs\n@@\n-a\n+b\n*** Add File: README.md\n+hi\n*** End Patch"}"#,
+        )
+        .unwrap();
+        let paths = patched_paths(&payload);
+        assert_eq!(paths, vec!["/work/api/src/tui.rs", "README.md"]);
+        assert_eq!(
+            relative_to(&paths[0], Some(Path::new("/work/api"))),
+            "src/tui.rs"
+        );
+    }
+
+    #[test]
+    fn last_turn_event_decides_outcome() {
+        let started = r#"{"type":"event_msg","payload":{"type":"task_started"}}"#;
+        let done = r#"{"type":"event_msg","payload":{"type":"task_complete"}}"#;
+        let aborted =
+            r#"{"type":"event_msg","payload":{"type":"turn_aborted","reason":"interrupted"}}"#;
+        let reply = r#"{"type":"response_item","payload":{"type":"message","role":"assistant","content":[]}}"#;
+        assert_eq!(
+            outcome_from_lines([started, done].into_iter()),
+            Outcome::Completed
+        );
+        assert_eq!(
+            outcome_from_lines([done, started, reply].into_iter()),
+            Outcome::Running
+        );
+        assert_eq!(
+            outcome_from_lines([started, aborted].into_iter()),
+            Outcome::Failed
+        );
+        assert_eq!(outcome_from_lines([reply].into_iter()), Outcome::Completed);
+        assert_eq!(outcome_from_lines(std::iter::empty()), Outcome::Unknown);
+    }
+
     #[test]
     fn extracts_user_message_text() {
         let line = r#"{"timestamp":"2026-01-19T21:55:21.488Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"hello there\n\nmore"}]}}"#;
@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ProjectSort;
+
+/// Launcher state that isn't configuration, kept next to the config as `state.json`
+/// (machine-local: `config sync` leaves it out).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct State {
+    /// Tags added to sessions from the picker, keyed by session id.
+    #[serde(default)]
+    pub session_tags: BTreeMap<String, BTreeSet<String>>,
+    /// Recent picker filters, per view.
+    #[serde(default)]
+    pub filter_history: FilterHistory,
+    /// Quick-mode queries, newest first (`--again` repeats the first).
+    #[serde(default)]
+    pub quick_history: Vec<QuickQuery>,
+    /// Codex launches made from here, keyed by project folder.
+    #[serde(default)]
+    pub launches: BTreeMap<PathBuf, LaunchCount>,
+    /// Projects and sessions kept at the top of the picker (`pin`, or `p` in the picker).
+    #[serde(default)]
+    pub pins: Pins,
+    /// Projects tab order last picked with `s` in the picker (see [`State::project_sort`]).
+    #[serde(default)]
+    pub sort: Option<ProjectSort>,
+    /// The `display.sort` that `sort` was picked over.
+    #[serde(default)]
+    pub sort_over: Option<ProjectSort>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pins {
+    /// Project folders.
+    #[serde(default)]
+    pub projects: BTreeSet<PathBuf>,
+    /// Session ids.
+    #[serde(default)]
+    pub sessions: BTreeSet<String>,
+}
+
+impl Pins {
+    /// Pin `dir`, or unpin it if it was pinned; returns whether it is pinned now.
+    pub fn toggle_project(&mut self, dir: &Path) -> bool {
+        !self.projects.remove(dir) && self.projects.insert(dir.to_path_buf())
+    }
+
+    /// Pin the session `id`, or unpin it; returns whether it is pinned now.
+    pub fn toggle_session(&mut self, id: &str) -> bool {
+        !self.sessions.remove(id) && self.sessions.insert(id.to_string())
+    }
+
+    /// Apply what changed from `before` to `after` (pins added and removed meanwhile), keeping
+    /// pins others made since `before` was read.
+    pub fn merge_changes(&mut self, before: &Pins, after: &Pins) {
+        for p in before.projects.difference(&after.projects) {
+            self.projects.remove(p);
+        }
+        self.projects
+            .extend(after.projects.difference(&before.projects).cloned());
+        for id in before.sessions.difference(&after.sessions) {
+            self.sessions.remove(id);
+        }
+        self.sessions
+            .extend(after.sessions.difference(&before.sessions).cloned());
+    }
+}
+
+/// How often codex was started for a project, counted at launch (so also when no rollout came
+/// of it).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LaunchCount {
+    pub count: u32,
+    /// Unix seconds of the latest launch.
+    pub last: i64,
+}
+
+/// How many quick-mode queries are remembered.
+const QUICK_HISTORY_LEN: usize = 50;
+
+/// A quick-mode run: `codex-launch <query>` or `codex-launch --resume <query>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickQuery {
+    #[serde(default)]
+    pub resume: bool,
+    pub query: String,
+}
+
+/// How many filters are remembered per view.
+const FILTER_HISTORY_LEN: usize = 20;
+
+/// Filters typed in the picker, newest first, keyed by view.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FilterHistory(BTreeMap<String, Vec<String>>);
+
+impl FilterHistory {
+    pub fn recent(&self, view: &str) -> &[String] {
+        self.0.get(view).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Move `filter` to the front of the view's history (blank filters are skipped).
+    pub fn remember(&mut self, view: &str, filter: &str) {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return;
+        }
+        let past = self.0.entry(view.to_string()).or_default();
+        past.retain(|p| p != filter);
+        past.insert(0, filter.to_string());
+        past.truncate(FILTER_HISTORY_LEN);
+    }
+}
+
+pub fn state_path(config_path: &Path) -> PathBuf {
+    state_path_in(config_path.parent().unwrap_or_else(|| Path::new(".")))
+}
+
+/// `state.json` in the config folder `dir` (`Config::dir`).
+pub fn state_path_in(dir: &Path) -> PathBuf {
+    dir.join("state.json")
+}
+
+impl State {
+    /// The Projects tab order: the one last picked with `s`, unless `display.sort` (`configured`)
+    /// was edited since it was picked; then the setting wins.
+    pub fn project_sort(&self, configured: ProjectSort) -> ProjectSort {
+        match self.sort {
+            Some(sort) if self.sort_over == Some(configured) => sort,
+            _ => configured,
+        }
+    }
+
+    /// For reading only: a missing or unreadable state file is an empty state.
+    pub fn load(path: &Path) -> Self {
+        Self::load_for_update(path).unwrap_or_default()
+    }
+
+    /// The state to modify and `save` back. A missing file is an empty state, but one that
+    /// can't be read or parsed is an error, so saving never replaces it with an empty one.
+    pub fn load_for_update(path: &Path) -> Result<Self> {
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        serde_json::from_str(&s).with_context(|| {
+            format!(
+                "{} is damaged; fix or remove it to save launcher state again",
+                path.display()
+            )
+        })
+    }
+
+    /// Write atomically (a per-process temp file + rename) so concurrent invocations never
+    /// leave a torn file behind.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        let s = serde_json::to_string_pretty(self)?;
+        fs::write(&tmp, s).with_context(|| format!("failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Move `query` to the front of the quick-mode history.
+    pub fn remember_quick(&mut self, query: QuickQuery) {
+        self.quick_history.retain(|q| *q != query);
+        self.quick_history.insert(0, query);
+        self.quick_history.truncate(QUICK_HISTORY_LEN);
+    }
+
+    pub fn record_launch(&mut self, dir: &Path, now: i64) {
+        let entry = self.launches.entry(dir.to_path_buf()).or_default();
+        entry.count += 1;
+        entry.last = now;
+    }
+
+    pub fn tags_for(&self, session_id: &str) -> Option<&BTreeSet<String>> {
+        self.session_tags.get(session_id).filter(|t| !t.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pins(projects: &[&str], sessions: &[&str]) -> Pins {
+        Pins {
+            projects: projects.iter().map(PathBuf::from).collect(),
+            sessions: sessions.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn merge_keeps_pins_changed_meanwhile() {
+        // This picker opened with /a and s1 pinned, then unpinned /a and pinned /c and s2.
+        let before = pins(&["/a"], &["s1"]);
+        let after = pins(&["/c"], &["s1", "s2"]);
+        // Meanwhile another invocation pinned /b and unpinned s1.
+        let mut fresh = pins(&["/a", "/b"], &[]);
+        fresh.merge_changes(&before, &after);
+        assert_eq!(fresh, pins(&["/b", "/c"], &["s2"]));
+    }
+
+    #[test]
+    fn merge_without_changes_leaves_the_file_as_is() {
+        let before = pins(&["/a"], &["s1"]);
+        let mut fresh = pins(&["/b"], &[]);
+        fresh.merge_changes(&before, &before.clone());
+        assert_eq!(fresh, pins(&["/b"], &[]));
+    }
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::config::SyncConfig;
+use crate::ui;
+
+/// Machine-local files that live next to the config but shouldn't follow it across machines.
+const IGNORED: &[&str] = &["index/", "state.json", "targets-cache.json", "cache.json"];
+
+/// Keep the config directory in a git repo: init on first use, commit local edits, then (with a
+/// remote configured) pull --rebase and push.
+pub fn sync(dir: &Path, sync_cfg: &SyncConfig, dry_run: bool) -> Result<()> {
+    let git = Git { dir, dry_run };
+
+    if !dir.join(".git").exists() {
+        ui::print_info(&format!("Initializing git repo in {}", dir.display()));
+        git.run(&["init", "-b", &sync_cfg.branch])?;
+    }
+    git.ignore_local_files()?;
+
+    if let Some(remote) = sync_cfg.remote.as_deref() {
+        match git.output(&["remote", "get-url", "origin"]) {
+            Some(url) if url == remote => {}
+            Some(_) => git.run(&["remote", "set-url", "origin", remote])?,
+            None => git.run(&["remote", "add", "origin", remote])?,
+        }
+    }
+
+    git.run(&["add", "-A"])?;
+    let has_changes = git
+        .output(&["status", "--porcelain"])
+        .is_some_and(|s| !s.is_empty());
+    if has_changes || dry_run {
+        git.run(&["commit", "-m", "codex-launch config sync"])?;
+    }
+
+    if sync_cfg.remote.is_none() {
+        ui::print_info("No `sync.remote` configured; committed locally only.");
+        return Ok(());
+    }
+    let remote_has_branch = git
+        .output(&["ls-remote", "--heads", "origin", &sync_cfg.branch])
+        .is_some_and(|s| !s.is_empty());
+    if remote_has_branch {
+        git.run(&["pull", "--rebase", "origin", &sync_cfg.branch])?;
+    }
+    git.run(&["push", "-u", "origin", &sync_cfg.branch])?;
+    Ok(())
+}
+
+struct Git<'a> {
+    dir: &'a Path,
+    dry_run: bool,
+}
+
+impl Git<'_> {
+    fn command(&self, args: &[&str]) -> Command {
+        let mut cmd = Command::new("git");
+        cmd.current_dir(self.dir).args(args);
+        cmd
+    }
+
+    fn run(&self, args: &[&str]) -> Result<()> {
+        let mut cmd = self.command(args);
+        if self.dry_run {
+            ui::print_info(&format!("DRY RUN: {}", ui::format_command(&cmd)));
+            return Ok(());
+        }
+        let status = cmd
+            .status()
+            .with_context(|| format!("failed to run {}", ui::format_command(&cmd)))?;
+        if !status.success() {
+            anyhow::bail!("git {} failed ({status})", args.join(" "));
+        }
+        Ok(())
+    }
+
+    /// Add the [`IGNORED`] entries missing from `.gitignore` (repos made by older versions lack
+    /// the newer ones) and stop tracking files that were committed before they were ignored.
+    fn ignore_local_files(&self) -> Result<()> {
+        let path = self.dir.join(".gitignore");
+        let mut text = fs::read_to_string(&path).unwrap_or_default();
+        let missing: Vec<&str> = IGNORED
+            .iter()
+            .copied()
+            .filter(|e| !text.lines().any(|l| l.trim() == *e))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        if text.is_empty() {
+            text.push_str("# Machine-local launcher data\n");
+        } else if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        for entry in missing.iter() {
+            text.push_str(entry);
+            text.push('\n');
+        }
+        if !self.dry_run {
+            fs::write(&path, text)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        for entry in missing {
+            self.run(&[
+                "rm",
+                "-r",
+                "--cached",
+                "--quiet",
+                "--ignore-unmatch",
+                "--",
+                entry,
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Trimmed stdout of a read-only git query, or `None` if it failed.
+    fn output(&self, args: &[&str]) -> Option<String> {
+        let out = self.command(args).output().ok()?;
+        if !out.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
+    }
+}
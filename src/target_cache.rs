@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ignore::IGNORE_FILE_NAME;
+use crate::projects::RootScan;
+
+/// Root scans from earlier runs, kept next to the config as `targets-cache.json` so big roots
+/// aren't listed on every launch. An entry is reused while the root folder's mtime (and its
+/// ignore file's) is unchanged; `--refresh` drops the file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TargetCache {
+    #[serde(default)]
+    roots: BTreeMap<PathBuf, CachedRoot>,
+    #[serde(skip)]
+    changed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedRoot {
+    mtime: SystemTime,
+    #[serde(default)]
+    ignore_mtime: Option<SystemTime>,
+    /// `projects.root_scan_limit` the scan ran with.
+    limit: usize,
+    repos: Vec<PathBuf>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+pub fn cache_path(config_dir: &Path) -> PathBuf {
+    config_dir.join("targets-cache.json")
+}
+
+/// Forget all cached scans (`--refresh`).
+pub fn clear(config_dir: &Path) -> Result<()> {
+    let path = cache_path(config_dir);
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl TargetCache {
+    /// A missing or unreadable cache is an empty one.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache back if a root was (re)scanned, atomically (a per-process temp file +
+    /// rename) so concurrent invocations never interleave their writes.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        let s = serde_json::to_string(self)?;
+        fs::write(&tmp, s).with_context(|| format!("failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// The cached scan of `root`, if the folder hasn't changed since.
+    pub fn get(&self, root: &Path, limit: usize) -> Option<RootScan> {
+        let entry = self.roots.get(root)?;
+        let fresh = entry.limit == limit
+            && Some(entry.mtime) == mtime(root)
+            && entry.ignore_mtime == mtime(&root.join(IGNORE_FILE_NAME));
+        fresh.then(|| RootScan {
+            repos: entry.repos.clone(),
+            truncated: entry.truncated,
+        })
+    }
+
+    pub fn insert(&mut self, root: &Path, limit: usize, scan: &RootScan) {
+        let Some(root_mtime) = mtime(root) else {
+            return;
+        };
+        self.changed = true;
+        self.roots.insert(
+            root.to_path_buf(),
+            CachedRoot {
+                mtime: root_mtime,
+                ignore_mtime: mtime(&root.join(IGNORE_FILE_NAME)),
+                limit,
+                repos: scan.repos.clone(),
+                truncated: scan.truncated,
+            },
+        );
+    }
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -1,10 +1,18 @@
-use time::OffsetDateTime;
+use std::sync::OnceLock;
+
 use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime, UtcOffset};
+
+use crate::config::DateStyle;
 
 pub fn parse_rfc3339(s: &str) -> Option<OffsetDateTime> {
     OffsetDateTime::parse(s, &Rfc3339).ok()
 }
 
+pub fn from_unix(secs: i64) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp(secs).ok()
+}
+
 pub fn format_short(dt: OffsetDateTime) -> String {
     // Example: "Jan20 00:06"
     let month = match dt.month() {
@@ -24,6 +32,37 @@ pub fn format_short(dt: OffsetDateTime) -> String {
     format!("{month}{:02} {:02}:{:02}", dt.day(), dt.hour(), dt.minute())
 }
 
+/// The local UTC offset, or UTC when it can't be determined. Looked up once: on some platforms
+/// the lookup fails once other threads run, so `main` calls this before spawning any.
+pub fn local_offset() -> UtcOffset {
+    static OFFSET: OnceLock<UtcOffset> = OnceLock::new();
+    *OFFSET.get_or_init(|| UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC))
+}
+
+/// A row's time column in the configured style.
+pub fn format_when(dt: OffsetDateTime, style: DateStyle) -> String {
+    match style {
+        DateStyle::Calendar => format_calendar(dt, OffsetDateTime::now_utc(), local_offset()),
+        DateStyle::Age => format!("{} {}", format_age(dt), format_short(dt)),
+    }
+}
+
+/// `Today 14:02` / `Yesterday 09:30` / `Mon 18:12` within the last week, `Jan17 10:11` this
+/// year, `2024-01-17` before. Days and times are in `offset` (the local one).
+fn format_calendar(dt: OffsetDateTime, now: OffsetDateTime, offset: UtcOffset) -> String {
+    let (dt, now) = (dt.to_offset(offset), now.to_offset(offset));
+    let clock = format!("{:02}:{:02}", dt.hour(), dt.minute());
+    let days = (now.date() - dt.date()).whole_days();
+    match days {
+        0 => format!("Today {clock}"),
+        1 => format!("Yesterday {clock}"),
+        2..=6 => format!("{} {clock}", &dt.weekday().to_string()[..3]),
+        _ if dt.year() == now.year() && days > 0 => format_short(dt),
+        _ if days > 0 => format!("{}-{:02}-{:02}", dt.year(), u8::from(dt.month()), dt.day()),
+        _ => format_short(dt),
+    }
+}
+
 pub fn format_age(dt: OffsetDateTime) -> String {
     let now = OffsetDateTime::now_utc();
     let delta = now - dt;
@@ -51,3 +90,52 @@ pub fn format_age(dt: OffsetDateTime) -> String {
     let years = weeks / 52;
     format!("{years}y")
 }
+
+/// Parse a relative age like `45m`, `12h`, `30d` or `2w` (as used by `--since`).
+pub fn parse_since(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let unit = s.chars().last()?;
+    let n = i64::from(s[..s.len() - unit.len_utf8()].parse::<u32>().ok()?);
+    match unit {
+        'm' => Some(Duration::minutes(n)),
+        'h' => Some(Duration::hours(n)),
+        'd' => Some(Duration::days(n)),
+        'w' => Some(Duration::weeks(n)),
+        _ => None,
+    }
+}
+
+/// Whether an RFC 3339 timestamp falls within the last `age`. Unparseable timestamps don't.
+pub fn is_within(ts: Option<&str>, age: Duration) -> bool {
+    ts.and_then(parse_rfc3339)
+        .is_some_and(|dt| OffsetDateTime::now_utc() - dt <= age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_recent_days_by_calendar() {
+        let now = parse_rfc3339("2026-01-21T15:00:00Z").unwrap();
+        let at = |s: &str| format_calendar(parse_rfc3339(s).unwrap(), now, UtcOffset::UTC);
+        assert_eq!(at("2026-01-21T00:05:00Z"), "Today 00:05");
+        assert_eq!(at("2026-01-20T23:59:00Z"), "Yesterday 23:59");
+        assert_eq!(at("2026-01-16T18:12:00Z"), "Fri 18:12");
+        assert_eq!(at("2026-01-14T10:11:00Z"), "Jan14 10:11");
+        assert_eq!(at("2025-12-30T10:11:00Z"), "2025-12-30");
+        // 02:30 UTC is still the previous evening five hours west.
+        let west = UtcOffset::from_hms(-5, 0, 0).unwrap();
+        let late = parse_rfc3339("2026-01-21T02:30:00Z").unwrap();
+        assert_eq!(format_calendar(late, now, west), "Yesterday 21:30");
+    }
+
+    #[test]
+    fn parses_since_units() {
+        assert_eq!(parse_since("30d"), Some(Duration::days(30)));
+        assert_eq!(parse_since("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_since("90m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_since("7"), None);
+        assert_eq!(parse_since("d"), None);
+    }
+}
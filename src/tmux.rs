@@ -0,0 +1,52 @@
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::RunOpts;
+use crate::config::Config;
+use crate::projects::ProjectTarget;
+use crate::ui;
+
+/// tmux session the windows go into when not already inside tmux.
+const SESSION: &str = "codex-launch";
+
+/// Start a new codex session for each target, each in its own tmux window: in the current tmux
+/// session when run inside tmux, otherwise in a `codex-launch` session that is attached at the end.
+pub fn launch_all(cfg: &Config, targets: &[ProjectTarget], run: RunOpts) -> Result<()> {
+    if targets.is_empty() {
+        return Ok(());
+    }
+    let inside = std::env::var_os("TMUX").is_some();
+    let mut session_exists = inside || has_session();
+    for target in targets.iter() {
+        let line = crate::codex_new_plan(cfg, target, &target.path, run)?.shell_line();
+        let mut cmd = Command::new("tmux");
+        if session_exists {
+            cmd.arg("new-window");
+            if !inside {
+                cmd.args(["-t", SESSION]);
+            }
+        } else {
+            cmd.args(["new-session", "-d", "-s", SESSION]);
+            session_exists = true;
+        }
+        cmd.arg("-n").arg(&target.label).arg(line);
+        crate::run_command(cmd, run)?;
+        crate::record_launch(cfg, &target.path, run);
+    }
+    ui::print_info(&format!("Opened {} tmux window(s)", targets.len()));
+    if inside {
+        return Ok(());
+    }
+    let mut attach = Command::new("tmux");
+    attach.args(["attach-session", "-t", SESSION]);
+    crate::run_command(attach, run)
+}
+
+fn has_session() -> bool {
+    Command::new("tmux")
+        .args(["has-session", "-t", SESSION])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
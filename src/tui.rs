@@ -1,27 +1,169 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::{self, Write};
 use std::panic;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::cursor;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
-use crossterm::style::{self, Stylize};
+use crossterm::style::{self, Color, Stylize};
 use crossterm::terminal::{self, ClearType};
 use crossterm::{QueueableCommand, execute};
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 
-use crate::projects::ProjectTarget;
-use crate::sessions::SessionItem;
+use crate::batch::BatchAction;
+use crate::config::{CustomAction, DateStyle, DisplayConfig, ProjectSort};
+use crate::icons;
+use crate::line_edit::LineEditor;
+use crate::pathfmt;
+use crate::plain_ui;
+use crate::projects::{ProjectTarget, TargetKind};
+use crate::repoinfo::{self, GitStatus};
+use crate::sessions::{self, BadgeKind, SessionItem};
+use crate::state::{FilterHistory, LaunchCount, Pins, State};
+use crate::timefmt;
 
 #[derive(Debug, Clone)]
 pub enum ProjectPick {
     New(ProjectTarget),
+    /// Start a new session, but ask for launch options (sandbox/approval/model) first.
+    NewWithOptions(ProjectTarget),
+    /// New session with codex started in a subfolder (e.g. a monorepo package).
+    NewInSubdir(ProjectTarget),
     Resume(SessionItem),
+    /// Apply a batch action to the marked sessions (or the highlighted one if none are marked).
+    Batch {
+        action: BatchAction,
+        sessions: Vec<SessionItem>,
+    },
+    /// Create a new project folder (prompting for root and name) and start Codex there.
+    CreateProject,
+    /// Open an interactive shell in the project folder.
+    Shell(ProjectTarget),
+    /// Open the project folder in `$VISUAL`/`$EDITOR`.
+    Editor(ProjectTarget),
+    /// Run a configured `[[actions]]` command in the project folder.
+    Action {
+        target: ProjectTarget,
+        command: String,
+    },
+    /// Start a new session in each of these projects, one tmux window each.
+    LaunchAll(Vec<ProjectTarget>),
+    /// Ask for a new display label for the project and save it to the config.
+    Rename(ProjectTarget),
     OpenConfig,
     Quit,
 }
 
+/// Loads `(scoped, all)` session lists holding at most the given number of sessions.
+pub type SessionLoader<'a> =
+    Box<dyn FnMut(usize) -> Result<(Vec<SessionItem>, Vec<SessionItem>)> + 'a>;
+
+/// The session tabs' contents, grown a page at a time on demand ("load more").
+pub struct SessionPages<'a> {
+    pub(crate) scoped: Vec<SessionItem>,
+    pub(crate) all: Vec<SessionItem>,
+    limit: usize,
+    pub(crate) page_size: usize,
+    exhausted: bool,
+    load: SessionLoader<'a>,
+    /// Pinned sessions, listed even when they are older than the loaded pages, and whether
+    /// each belongs in the scoped list.
+    pinned: Vec<(SessionItem, bool)>,
+}
+
+impl<'a> SessionPages<'a> {
+    pub fn new(limit: usize, page_size: usize, mut load: SessionLoader<'a>) -> Result<Self> {
+        let (scoped, all) = load(limit)?;
+        Ok(Self {
+            exhausted: all.len() < limit,
+            scoped,
+            all,
+            limit,
+            page_size: page_size.max(1),
+            load,
+            pinned: Vec::new(),
+        })
+    }
+
+    /// Keep `pinned` in the all list, and those `in_scope` accepts in the scoped one, after the
+    /// loaded pages when they aren't in them.
+    pub fn with_pinned(
+        mut self,
+        pinned: Vec<SessionItem>,
+        in_scope: impl Fn(&SessionItem) -> bool,
+    ) -> Self {
+        self.pinned = pinned
+            .into_iter()
+            .map(|s| {
+                let scoped = in_scope(&s);
+                (s, scoped)
+            })
+            .collect();
+        let (scoped, all) = (
+            std::mem::take(&mut self.scoped),
+            std::mem::take(&mut self.all),
+        );
+        self.set(scoped, all);
+        self
+    }
+
+    fn set(&mut self, mut scoped: Vec<SessionItem>, mut all: Vec<SessionItem>) {
+        for (list, scoped_list) in [(&mut scoped, true), (&mut all, false)] {
+            for (s, in_scope) in &self.pinned {
+                if (*in_scope || !scoped_list) && !list.iter().any(|l| l.id == s.id) {
+                    list.push(s.clone());
+                }
+            }
+        }
+        self.scoped = scoped;
+        self.all = all;
+    }
+
+    /// Fetch the next page; a no-op once a load came back short.
+    pub(crate) fn load_more(&mut self) -> Result<()> {
+        if self.exhausted {
+            return Ok(());
+        }
+        let limit = self.limit + self.page_size;
+        let (scoped, all) = (self.load)(limit)?;
+        self.exhausted = all.len() < limit;
+        self.set(scoped, all);
+        self.limit = limit;
+        Ok(())
+    }
+
+    /// Fetch every remaining session at once.
+    pub(crate) fn load_all(&mut self) -> Result<()> {
+        if self.exhausted {
+            return Ok(());
+        }
+        let (scoped, all) = (self.load)(usize::MAX)?;
+        self.exhausted = true;
+        self.limit = all.len();
+        self.set(scoped, all);
+        Ok(())
+    }
+}
+
+/// How long cached strings holding an age (HEAD column, preview) are reused before being
+/// recomputed; regaining focus recomputes them too, and rereads the git status column. Row
+/// times are formatted on every draw.
+const RELABEL_EVERY: Duration = Duration::from_secs(60);
+
+/// Rows moved by PageUp/PageDown (and `ctrl+f` / `ctrl+b` with vim keys).
+const PAGE_ROWS: usize = 10;
+
+/// `FilterHistory` keys of the picker's views.
+const PROJECTS_HISTORY: &str = "projects";
+const SESSIONS_HISTORY: &str = "sessions";
+const PROJECT_SESSIONS_HISTORY: &str = "project_sessions";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tab {
     Projects,
@@ -35,123 +177,329 @@ enum View {
     ProjectSessions {
         target: ProjectTarget,
         sessions: Vec<SessionItem>,
+        limit: usize,
+        /// `sessions` holds all of the target's sessions, loaded once a filter was typed.
+        complete: bool,
+    },
+    /// What to do with a project (opened with `tab`).
+    ActionMenu {
+        target: ProjectTarget,
+        cursor: usize,
     },
 }
 
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum MenuItem {
+    New,
+    NewInSubdir,
+    ResumeLatest,
+    Browse,
+    Shell,
+    Editor,
+    Custom(usize),
+}
+
+pub(crate) fn menu_items(actions: &[CustomAction]) -> Vec<(String, MenuItem)> {
+    let mut items = vec![
+        ("New session".to_string(), MenuItem::New),
+        (
+            "New session in a subfolder…".to_string(),
+            MenuItem::NewInSubdir,
+        ),
+        ("Resume latest session".to_string(), MenuItem::ResumeLatest),
+        ("Browse sessions".to_string(), MenuItem::Browse),
+        ("Open shell here".to_string(), MenuItem::Shell),
+        ("Open in editor".to_string(), MenuItem::Editor),
+    ];
+    for (i, a) in actions.iter().enumerate() {
+        items.push((a.name.clone(), MenuItem::Custom(i)));
+    }
+    items
+}
+
+/// Settings and launcher state the picker reads but doesn't own.
+pub struct PickerContext<'a> {
+    /// How many sessions the project view starts with for a target (load-more adds pages).
+    pub project_limit: &'a dyn Fn(&Path) -> usize,
+    pub display: &'a DisplayConfig,
+    pub state: &'a State,
+    pub actions: &'a [CustomAction],
+    /// Shown in the footer until the next key press (e.g. the result of a batch action).
+    pub status: Option<String>,
+}
+
+/// The picker's filter inputs, one per view (the two session tabs share one).
+#[derive(Default)]
+struct Filters {
+    projects: LineEditor,
+    sessions: LineEditor,
+    project_sessions: LineEditor,
+}
+
+impl Filters {
+    /// Whether something is typed into `view`'s filter (menus have none).
+    fn typing(&self, view: &View) -> bool {
+        let filter = match view {
+            View::Tab(Tab::Projects) => &self.projects,
+            View::Tab(Tab::SessionsScoped | Tab::SessionsAll) => &self.sessions,
+            View::ProjectSessions { .. } => &self.project_sessions,
+            View::ActionMenu { .. } => return false,
+        };
+        !filter.text().is_empty()
+    }
+}
+
+/// Run the picker. Filters that led to a pick are added to `history`, which also feeds `↑`
+/// recall in an empty filter; `p` toggles entries in `pins` and `s` changes `sort`, whatever
+/// the outcome. `targets` come in `recent` order; with `display.git_status` their `git` is
+/// filled in as it is read.
 pub fn pick_project(
-    targets: &[ProjectTarget],
-    sessions_scoped: &[SessionItem],
-    sessions_all: &[SessionItem],
-    per_project_limit: usize,
+    targets: &mut [ProjectTarget],
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    history: &mut FilterHistory,
+    pins: &mut Pins,
+    sort: &mut ProjectSort,
 ) -> Result<ProjectPick> {
     let mut stdout = io::stdout();
     let _guard = TerminalGuard::enter(&mut stdout)?;
 
+    let mut filters = Filters::default();
     let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
         pick_project_inner(
             &mut stdout,
             targets,
-            sessions_scoped,
-            sessions_all,
-            per_project_limit,
+            pages,
+            ctx,
+            history,
+            pins,
+            sort,
+            &mut filters,
         )
     }));
     match result {
-        Ok(r) => r,
+        Ok(r) => {
+            if let Ok(pick) = &r
+                && !matches!(pick, ProjectPick::Quit)
+            {
+                history.remember(PROJECTS_HISTORY, filters.projects.text());
+                history.remember(SESSIONS_HISTORY, filters.sessions.text());
+                history.remember(PROJECT_SESSIONS_HISTORY, filters.project_sessions.text());
+            }
+            r
+        }
         Err(_) => anyhow::bail!(
             "UI crashed (panic). Terminal should be restored; re-run with `--no-ui` if needed."
         ),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn pick_project_inner(
     stdout: &mut io::Stdout,
-    targets: &[ProjectTarget],
-    sessions_scoped: &[SessionItem],
-    sessions_all: &[SessionItem],
-    per_project_limit: usize,
+    targets: &mut [ProjectTarget],
+    pages: &mut SessionPages,
+    ctx: &PickerContext,
+    history: &FilterHistory,
+    pins: &mut Pins,
+    sort: &mut ProjectSort,
+    filters: &mut Filters,
 ) -> Result<ProjectPick> {
+    // `~` switches `paths` and `s` switches `sort` while the picker is open.
+    let mut display = ctx.display.clone();
+    display.sort = *sort;
     let matcher = SkimMatcherV2::default().ignore_case();
 
     let mut view = View::Tab(Tab::Projects);
 
-    let mut project_filter = String::new();
     let mut project_cursor: usize = 0;
 
-    let mut sessions_filter = String::new();
     let mut sessions_cursor: usize = 0;
 
-    let mut project_sessions_filter = String::new();
     let mut project_sessions_cursor: usize = 0;
 
+    let mut details = DetailCache::default();
+    let mut previews = PreviewCache::default();
+    let mut show_preview = display.preview;
+    let mut decor = ProjectDecor::default();
+    if display.git_status {
+        decor.read_git(targets);
+    }
+    let mut marked: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut marked_projects: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut status = ctx.status.clone();
+    let mut relabeled = Instant::now();
+    let mut vim = display.vim_keys.then(VimKeys::default);
+    // Index of the top visible row of the last drawn list, where the `alt+1..9` labels start.
+    let mut first_row: usize = 0;
+
     loop {
+        if relabeled.elapsed() >= RELABEL_EVERY {
+            decor.heads.clear();
+            previews.lines.clear();
+            if display.git_status {
+                decor.read_git(targets);
+            }
+            relabeled = Instant::now();
+        }
+        decor.take_git(targets);
+        decor.take_heads();
         let (cols, rows) = terminal::size()?;
         let cols = cols as usize;
         let rows = rows as usize;
 
         match &mut view {
             View::Tab(Tab::Projects) => {
-                let filtered = filter_targets(targets, &matcher, &project_filter);
+                let filtered = shown_targets(
+                    targets,
+                    &matcher,
+                    filters.projects.text(),
+                    pins,
+                    display.sort,
+                );
                 if project_cursor >= filtered.len() && !filtered.is_empty() {
                     project_cursor = filtered.len() - 1;
                 }
-                render_projects(
+                let preview = if show_preview {
+                    selected_target(targets, &filtered, project_cursor)
+                        .map(|t| previews.lines(&t.path, cols, rows / 2))
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                first_row = render_projects(
                     stdout,
                     targets,
                     &filtered,
                     project_cursor,
-                    &project_filter,
+                    &filters.projects,
+                    &marked_projects,
+                    pins,
+                    &preview,
+                    &mut decor,
+                    display.launches.then_some(&ctx.state.launches),
+                    &display,
                     cols,
                     rows,
                 )?;
             }
             View::Tab(tab @ (Tab::SessionsScoped | Tab::SessionsAll)) => {
                 let items = match tab {
-                    Tab::SessionsScoped => sessions_scoped,
-                    Tab::SessionsAll => sessions_all,
+                    Tab::SessionsScoped => &pages.scoped,
+                    Tab::SessionsAll => &pages.all,
                     _ => unreachable!(),
                 };
-                let filtered = filter_sessions(items, &matcher, &sessions_filter);
+                let filtered = shown_sessions(items, &matcher, filters.sessions.text(), pins);
                 if sessions_cursor >= filtered.len() && !filtered.is_empty() {
                     sessions_cursor = filtered.len() - 1;
                 }
-                render_sessions(
+                let detail = selected_session(items, &filtered, sessions_cursor)
+                    .map(|s| details.lines(&s, ctx.state, cols))
+                    .unwrap_or_default();
+                first_row = render_sessions(
                     stdout,
                     *tab,
                     items,
                     &filtered,
                     sessions_cursor,
-                    &sessions_filter,
+                    &filters.sessions,
+                    !pages.exhausted,
+                    &marked,
+                    pins,
+                    &detail,
+                    &display,
                     cols,
                     rows,
                 )?;
             }
-            View::ProjectSessions { target, sessions } => {
-                let filtered = filter_sessions(sessions, &matcher, &project_sessions_filter);
+            View::ProjectSessions {
+                target,
+                sessions,
+                complete,
+                ..
+            } => {
+                // A filter searches all of the target's sessions, not just the loaded slice.
+                if !*complete && !filters.project_sessions.text().is_empty() {
+                    draw_status(stdout, "Loading older sessions…", cols, rows)?;
+                    pages.load_all()?;
+                    *sessions = sessions_for_target(target, &pages.all, usize::MAX);
+                    *complete = true;
+                }
+                let filtered =
+                    shown_sessions(sessions, &matcher, filters.project_sessions.text(), pins);
                 // Cursor includes "Start new session" at row 0, so the maximum valid
                 // cursor position is `filtered.len()` (the last session row).
                 if project_sessions_cursor > filtered.len() {
                     project_sessions_cursor = filtered.len();
                 }
-                render_project_sessions(
+                // Row 0 is "Start new session", which has no details.
+                let detail = project_sessions_cursor
+                    .checked_sub(1)
+                    .and_then(|c| selected_session(sessions, &filtered, c))
+                    .map(|s| details.lines(&s, ctx.state, cols))
+                    .unwrap_or_default();
+                first_row = render_project_sessions(
                     stdout,
                     target,
                     sessions,
                     &filtered,
                     project_sessions_cursor,
-                    &project_sessions_filter,
+                    &filters.project_sessions,
+                    !*complete && !pages.exhausted,
+                    &marked,
+                    pins,
+                    &detail,
+                    &display,
                     cols,
                     rows,
                 )?;
             }
+            View::ActionMenu { target, cursor } => {
+                render_action_menu(stdout, target, &menu_items(ctx.actions), *cursor, cols)?;
+            }
+        }
+
+        if let Some(msg) = &status {
+            draw_status(stdout, msg, cols, rows)?;
         }
 
         if !event::poll(Duration::from_millis(250))? {
             continue;
         }
         let ev = event::read()?;
+        if let Event::FocusGained = ev {
+            decor.heads.clear();
+            previews.lines.clear();
+            if display.git_status {
+                decor.read_git(targets);
+            }
+            relabeled = Instant::now();
+            continue;
+        }
+        // A paste (bracketed) is filter text, never a run of shortcut keys.
+        if let Event::Paste(text) = &ev {
+            status = None;
+            match &view {
+                View::Tab(Tab::Projects) => {
+                    filters.projects.insert_str(text);
+                    project_cursor = 0;
+                }
+                View::Tab(Tab::SessionsScoped | Tab::SessionsAll) => {
+                    filters.sessions.insert_str(text);
+                    sessions_cursor = 0;
+                }
+                View::ProjectSessions { .. } => {
+                    filters.project_sessions.insert_str(text);
+                    project_sessions_cursor = 0;
+                }
+                View::ActionMenu { .. } => {}
+            }
+            continue;
+        }
         if let Event::Key(k) = ev {
-            // Global actions.
+            status = None;
+            // Global actions; their letters go into the filter once something is typed there.
+            let typing = filters.typing(&view);
             match (k.code, k.modifiers) {
                 (KeyCode::Esc, _) => match &view {
                     View::Tab(Tab::Projects) => return Ok(ProjectPick::Quit),
@@ -164,131 +512,392 @@ fn pick_project_inner(
                         continue;
                     }
                     View::ProjectSessions { .. } => {}
+                    View::ActionMenu { .. } => {
+                        view = View::Tab(Tab::Projects);
+                        continue;
+                    }
                 },
-                (KeyCode::Char('q'), KeyModifiers::NONE) => return Ok(ProjectPick::Quit),
+                (KeyCode::Char('q'), KeyModifiers::NONE) if !typing => {
+                    return Ok(ProjectPick::Quit);
+                }
                 (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(ProjectPick::Quit),
-                (KeyCode::Char('o'), KeyModifiers::NONE) => return Ok(ProjectPick::OpenConfig),
+                (KeyCode::Char('o'), KeyModifiers::NONE) if !typing => {
+                    return Ok(ProjectPick::OpenConfig);
+                }
+                (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                    show_preview = !show_preview;
+                    continue;
+                }
+                (KeyCode::Char('~'), _) if !typing => {
+                    display.paths = display.paths.toggled();
+                    continue;
+                }
                 _ => {}
             }
 
             match &mut view {
                 View::Tab(Tab::Projects) => {
-                    let filtered = filter_targets(targets, &matcher, &project_filter);
+                    let filtered = shown_targets(
+                        targets,
+                        &matcher,
+                        filters.projects.text(),
+                        pins,
+                        display.sort,
+                    );
                     if project_cursor >= filtered.len() && !filtered.is_empty() {
                         project_cursor = filtered.len() - 1;
                     }
                     match handle_list_key(
                         k,
-                        &mut project_filter,
+                        &mut filters.projects,
+                        history.recent(PROJECTS_HISTORY),
+                        vim.as_mut(),
+                        first_row,
                         &mut project_cursor,
                         filtered.len(),
                         Tab::Projects,
                     )? {
                         ListOutcome::Continue => {}
                         ListOutcome::SwitchTab(tab) => {
-                            sessions_filter.clear();
+                            filters.sessions.clear();
                             sessions_cursor = 0;
                             view = View::Tab(tab);
                         }
                         ListOutcome::Activate => {
                             if let Some(t) = selected_target(targets, &filtered, project_cursor) {
-                                project_sessions_filter.clear();
+                                filters.project_sessions.clear();
                                 project_sessions_cursor = 0;
-                                let sessions =
-                                    sessions_for_target(&t, sessions_all, per_project_limit);
+                                let limit = (ctx.project_limit)(&t.path);
                                 view = View::ProjectSessions {
+                                    sessions: sessions_for_target(&t, &pages.all, limit),
                                     target: t,
-                                    sessions,
+                                    limit,
+                                    complete: false,
                                 };
                             }
                         }
+                        ListOutcome::CreateProject => return Ok(ProjectPick::CreateProject),
+                        ListOutcome::Menu => {
+                            if let Some(target) =
+                                selected_target(targets, &filtered, project_cursor)
+                            {
+                                view = View::ActionMenu { target, cursor: 0 };
+                            }
+                        }
+                        ListOutcome::ToggleMark => {
+                            if let Some(t) = selected_target(targets, &filtered, project_cursor) {
+                                if !marked_projects.remove(&t.path) {
+                                    marked_projects.insert(t.path);
+                                }
+                                project_cursor = (project_cursor + 1).min(filtered.len() - 1);
+                            }
+                        }
+                        ListOutcome::LaunchAll => {
+                            let picked: Vec<ProjectTarget> = if marked_projects.is_empty() {
+                                selected_target(targets, &filtered, project_cursor)
+                                    .into_iter()
+                                    .collect()
+                            } else {
+                                targets
+                                    .iter()
+                                    .filter(|t| marked_projects.contains(&t.path))
+                                    .cloned()
+                                    .collect()
+                            };
+                            if !picked.is_empty() {
+                                return Ok(ProjectPick::LaunchAll(picked));
+                            }
+                        }
+                        ListOutcome::LoadMore | ListOutcome::Batch(_) => {}
                         ListOutcome::StartNew => {
                             if let Some(t) = selected_target(targets, &filtered, project_cursor) {
                                 return Ok(ProjectPick::New(t));
                             }
                         }
+                        ListOutcome::StartNewWithOptions => {
+                            if let Some(t) = selected_target(targets, &filtered, project_cursor) {
+                                return Ok(ProjectPick::NewWithOptions(t));
+                            }
+                        }
+                        ListOutcome::ResumeLatest => {
+                            if let Some(t) = selected_target(targets, &filtered, project_cursor) {
+                                match latest_session(&t, pages)? {
+                                    Some(s) => return Ok(ProjectPick::Resume(s)),
+                                    None => {
+                                        status = Some(format!("No sessions in {} yet", t.label))
+                                    }
+                                }
+                            }
+                        }
+                        ListOutcome::Rename => {
+                            if let Some(t) = selected_target(targets, &filtered, project_cursor) {
+                                return Ok(ProjectPick::Rename(t));
+                            }
+                        }
+                        ListOutcome::CycleSort => {
+                            display.sort = display.sort.next();
+                            *sort = display.sort;
+                            project_cursor = 0;
+                            status = Some(format!("Sorted by {}", display.sort.name()));
+                        }
+                        ListOutcome::TogglePin => {
+                            if let Some(t) = selected_target(targets, &filtered, project_cursor) {
+                                let verb = pin_verb(pins.toggle_project(&t.path));
+                                status = Some(format!("{verb} {}", t.label));
+                            }
+                        }
                     }
                 }
                 View::Tab(Tab::SessionsScoped) => {
-                    let filtered = filter_sessions(sessions_scoped, &matcher, &sessions_filter);
+                    let filtered =
+                        shown_sessions(&pages.scoped, &matcher, filters.sessions.text(), pins);
                     if sessions_cursor >= filtered.len() && !filtered.is_empty() {
                         sessions_cursor = filtered.len() - 1;
                     }
                     match handle_list_key(
                         k,
-                        &mut sessions_filter,
+                        &mut filters.sessions,
+                        history.recent(SESSIONS_HISTORY),
+                        vim.as_mut(),
+                        first_row,
                         &mut sessions_cursor,
                         filtered.len(),
                         Tab::SessionsScoped,
                     )? {
-                        ListOutcome::Continue => {}
+                        ListOutcome::Continue => {
+                            if reached_end(
+                                k,
+                                vim.is_some(),
+                                &filters.sessions,
+                                sessions_cursor,
+                                filtered.len(),
+                            ) {
+                                pages.load_more()?;
+                            }
+                        }
+                        ListOutcome::LoadMore => pages.load_more()?,
                         ListOutcome::SwitchTab(tab) => {
                             view = View::Tab(tab);
                         }
                         ListOutcome::Activate => {
                             if let Some(s) =
-                                selected_session(sessions_scoped, &filtered, sessions_cursor)
+                                selected_session(&pages.scoped, &filtered, sessions_cursor)
                             {
                                 return Ok(ProjectPick::Resume(s));
                             }
                         }
-                        ListOutcome::StartNew => {}
+                        ListOutcome::ToggleMark => {
+                            if let Some(s) =
+                                selected_session(&pages.scoped, &filtered, sessions_cursor)
+                            {
+                                toggle_mark(&mut marked, &s);
+                                sessions_cursor = (sessions_cursor + 1).min(filtered.len() - 1);
+                            }
+                        }
+                        ListOutcome::TogglePin => {
+                            if let Some(s) =
+                                selected_session(&pages.scoped, &filtered, sessions_cursor)
+                            {
+                                status = Some(toggle_pin(pins, &s));
+                            }
+                        }
+                        ListOutcome::Batch(action) => {
+                            let highlighted =
+                                selected_session(&pages.scoped, &filtered, sessions_cursor);
+                            if let Some(sessions) = batch_sessions(&marked, &pages.all, highlighted)
+                            {
+                                return Ok(ProjectPick::Batch { action, sessions });
+                            }
+                        }
+                        ListOutcome::StartNew
+                        | ListOutcome::StartNewWithOptions
+                        | ListOutcome::ResumeLatest
+                        | ListOutcome::Rename
+                        | ListOutcome::CycleSort
+                        | ListOutcome::Menu
+                        | ListOutcome::CreateProject
+                        | ListOutcome::LaunchAll => {}
                     }
                 }
                 View::Tab(Tab::SessionsAll) => {
-                    let filtered = filter_sessions(sessions_all, &matcher, &sessions_filter);
+                    let filtered =
+                        shown_sessions(&pages.all, &matcher, filters.sessions.text(), pins);
                     if sessions_cursor >= filtered.len() && !filtered.is_empty() {
                         sessions_cursor = filtered.len() - 1;
                     }
                     match handle_list_key(
                         k,
-                        &mut sessions_filter,
+                        &mut filters.sessions,
+                        history.recent(SESSIONS_HISTORY),
+                        vim.as_mut(),
+                        first_row,
                         &mut sessions_cursor,
                         filtered.len(),
                         Tab::SessionsAll,
                     )? {
-                        ListOutcome::Continue => {}
+                        ListOutcome::Continue => {
+                            if reached_end(
+                                k,
+                                vim.is_some(),
+                                &filters.sessions,
+                                sessions_cursor,
+                                filtered.len(),
+                            ) {
+                                pages.load_more()?;
+                            }
+                        }
+                        ListOutcome::LoadMore => pages.load_more()?,
                         ListOutcome::SwitchTab(tab) => {
                             view = View::Tab(tab);
                         }
                         ListOutcome::Activate => {
                             if let Some(s) =
-                                selected_session(sessions_all, &filtered, sessions_cursor)
+                                selected_session(&pages.all, &filtered, sessions_cursor)
                             {
                                 return Ok(ProjectPick::Resume(s));
                             }
                         }
-                        ListOutcome::StartNew => {}
+                        ListOutcome::ToggleMark => {
+                            if let Some(s) =
+                                selected_session(&pages.all, &filtered, sessions_cursor)
+                            {
+                                toggle_mark(&mut marked, &s);
+                                sessions_cursor = (sessions_cursor + 1).min(filtered.len() - 1);
+                            }
+                        }
+                        ListOutcome::TogglePin => {
+                            if let Some(s) =
+                                selected_session(&pages.all, &filtered, sessions_cursor)
+                            {
+                                status = Some(toggle_pin(pins, &s));
+                            }
+                        }
+                        ListOutcome::Batch(action) => {
+                            let highlighted =
+                                selected_session(&pages.all, &filtered, sessions_cursor);
+                            if let Some(sessions) = batch_sessions(&marked, &pages.all, highlighted)
+                            {
+                                return Ok(ProjectPick::Batch { action, sessions });
+                            }
+                        }
+                        ListOutcome::StartNew
+                        | ListOutcome::StartNewWithOptions
+                        | ListOutcome::ResumeLatest
+                        | ListOutcome::Rename
+                        | ListOutcome::CycleSort
+                        | ListOutcome::Menu
+                        | ListOutcome::CreateProject
+                        | ListOutcome::LaunchAll => {}
                     }
                 }
-                View::ProjectSessions { target, sessions } => {
+                View::ActionMenu { target, cursor } => {
+                    let items = menu_items(ctx.actions);
+                    match (k.code, k.modifiers) {
+                        (KeyCode::Left, _) => view = View::Tab(Tab::Projects),
+                        (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                            *cursor = cursor.saturating_sub(1);
+                        }
+                        (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                            *cursor = (*cursor + 1).min(items.len() - 1);
+                        }
+                        (KeyCode::Enter, _) => {
+                            let target = target.clone();
+                            match items[*cursor].1 {
+                                MenuItem::New => return Ok(ProjectPick::New(target)),
+                                MenuItem::NewInSubdir => {
+                                    return Ok(ProjectPick::NewInSubdir(target));
+                                }
+                                MenuItem::ResumeLatest => match latest_session(&target, pages)? {
+                                    Some(s) => return Ok(ProjectPick::Resume(s)),
+                                    None => {
+                                        status =
+                                            Some(format!("No sessions in {} yet", target.label));
+                                        view = View::Tab(Tab::Projects);
+                                    }
+                                },
+                                MenuItem::Browse => {
+                                    filters.project_sessions.clear();
+                                    project_sessions_cursor = 0;
+                                    let limit = (ctx.project_limit)(&target.path);
+                                    view = View::ProjectSessions {
+                                        sessions: sessions_for_target(&target, &pages.all, limit),
+                                        target,
+                                        limit,
+                                        complete: false,
+                                    };
+                                }
+                                MenuItem::Shell => return Ok(ProjectPick::Shell(target)),
+                                MenuItem::Editor => return Ok(ProjectPick::Editor(target)),
+                                MenuItem::Custom(i) => {
+                                    return Ok(ProjectPick::Action {
+                                        target,
+                                        command: ctx.actions[i].command.clone(),
+                                    });
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                View::ProjectSessions {
+                    target,
+                    sessions,
+                    limit,
+                    complete,
+                } => {
                     match (k.code, k.modifiers) {
                         (KeyCode::Esc, _) => {
                             view = View::Tab(Tab::Projects);
                             continue;
                         }
-                        (KeyCode::Left, _) => {
+                        (KeyCode::Left, KeyModifiers::NONE) => {
                             view = View::Tab(Tab::Projects);
                             continue;
                         }
                         _ => {}
                     }
 
-                    let filtered = filter_sessions(sessions, &matcher, &project_sessions_filter);
+                    let filtered =
+                        shown_sessions(sessions, &matcher, filters.project_sessions.text(), pins);
                     if project_sessions_cursor > filtered.len() {
                         project_sessions_cursor = filtered.len();
                     }
 
                     match handle_project_sessions_key(
                         k,
-                        &mut project_sessions_filter,
+                        &mut filters.project_sessions,
+                        history.recent(PROJECT_SESSIONS_HISTORY),
+                        vim.as_mut(),
+                        first_row,
                         &mut project_sessions_cursor,
                         filtered.len(),
                     )? {
-                        ProjectSessionsOutcome::Continue => {}
+                        ProjectSessionsOutcome::Continue => {
+                            // Row 0 is "Start new session", so the last row is `filtered.len()`.
+                            if !*complete
+                                && reached_end(
+                                    k,
+                                    vim.is_some(),
+                                    &filters.project_sessions,
+                                    project_sessions_cursor,
+                                    filtered.len() + 1,
+                                )
+                            {
+                                *limit += pages.page_size;
+                                *sessions = load_project_sessions(target, pages, *limit)?;
+                            }
+                        }
+                        ProjectSessionsOutcome::LoadMore if *complete => {}
+                        ProjectSessionsOutcome::LoadMore => {
+                            *limit += pages.page_size;
+                            *sessions = load_project_sessions(target, pages, *limit)?;
+                        }
                         ProjectSessionsOutcome::StartNew => {
                             return Ok(ProjectPick::New(target.clone()));
                         }
+                        ProjectSessionsOutcome::StartNewWithOptions => {
+                            return Ok(ProjectPick::NewWithOptions(target.clone()));
+                        }
                         ProjectSessionsOutcome::Resume { filtered_idx } => {
                             if let Some(s) = filtered
                                 .get(filtered_idx)
@@ -298,6 +907,27 @@ fn pick_project_inner(
                                 return Ok(ProjectPick::Resume(s));
                             }
                         }
+                        ProjectSessionsOutcome::ToggleMark { filtered_idx } => {
+                            if let Some(s) = selected_session(sessions, &filtered, filtered_idx) {
+                                toggle_mark(&mut marked, &s);
+                                project_sessions_cursor =
+                                    (project_sessions_cursor + 1).min(filtered.len());
+                            }
+                        }
+                        ProjectSessionsOutcome::TogglePin { filtered_idx } => {
+                            if let Some(s) = selected_session(sessions, &filtered, filtered_idx) {
+                                status = Some(toggle_pin(pins, &s));
+                            }
+                        }
+                        ProjectSessionsOutcome::Batch(action) => {
+                            let highlighted = project_sessions_cursor
+                                .checked_sub(1)
+                                .and_then(|c| selected_session(sessions, &filtered, c));
+                            if let Some(sessions) = batch_sessions(&marked, &pages.all, highlighted)
+                            {
+                                return Ok(ProjectPick::Batch { action, sessions });
+                            }
+                        }
                     }
                 }
             }
@@ -305,11 +935,94 @@ fn pick_project_inner(
     }
 }
 
+/// The newest session of `target`. Older pages are only loaded to find it when the target's
+/// `last_session_at` says it has one; otherwise just the loaded sessions are checked.
+pub(crate) fn latest_session(
+    target: &ProjectTarget,
+    pages: &mut SessionPages,
+) -> Result<Option<SessionItem>> {
+    if target.last_session_at.is_none() {
+        return Ok(sessions_for_target(target, &pages.all, 1).pop());
+    }
+    Ok(load_project_sessions(target, pages, 1)?.pop())
+}
+
+/// Up to `limit` sessions of `target`, loading older pages until there are enough (or no more).
+pub(crate) fn load_project_sessions(
+    target: &ProjectTarget,
+    pages: &mut SessionPages,
+    limit: usize,
+) -> Result<Vec<SessionItem>> {
+    loop {
+        let sessions = sessions_for_target(target, &pages.all, limit);
+        if sessions.len() >= limit || pages.exhausted || target.kind == TargetKind::Remote {
+            return Ok(sessions);
+        }
+        pages.load_more()?;
+    }
+}
+
+/// Pin or unpin `s`; returns the status line saying which.
+pub(crate) fn toggle_pin(pins: &mut Pins, s: &SessionItem) -> String {
+    let verb = pin_verb(pins.toggle_session(&s.id));
+    let what = s.summary.as_deref().map(plain_ui::one_line);
+    format!("{verb} {}", what.unwrap_or_else(|| s.id.clone()))
+}
+
+pub(crate) fn pin_verb(pinned: bool) -> &'static str {
+    if pinned { "Pinned" } else { "Unpinned" }
+}
+
+/// Marks are keyed by rollout, so sessions only known from history.jsonl can't be marked.
+fn toggle_mark(marked: &mut BTreeSet<PathBuf>, s: &SessionItem) {
+    let Some(path) = &s.path else {
+        return;
+    };
+    if !marked.remove(path) {
+        marked.insert(path.clone());
+    }
+}
+
+/// Sessions a batch action applies to: the marked ones, or else the highlighted one.
+fn batch_sessions(
+    marked: &BTreeSet<PathBuf>,
+    all: &[SessionItem],
+    highlighted: Option<SessionItem>,
+) -> Option<Vec<SessionItem>> {
+    if marked.is_empty() {
+        return highlighted.map(|s| vec![s]);
+    }
+    Some(
+        all.iter()
+            .filter(|s| s.path.as_ref().is_some_and(|p| marked.contains(p)))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Maps the batch keys (shift-D/A/T/E/K) to their action.
+fn batch_action(key: KeyEvent) -> Option<BatchAction> {
+    if key.modifiers != KeyModifiers::SHIFT {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char('D') => Some(BatchAction::Delete),
+        KeyCode::Char('A') => Some(BatchAction::Archive),
+        KeyCode::Char('T') => Some(BatchAction::Tag),
+        KeyCode::Char('E') => Some(BatchAction::Export),
+        KeyCode::Char('K') => Some(BatchAction::Signal),
+        _ => None,
+    }
+}
+
 fn sessions_for_target(
     target: &ProjectTarget,
     sessions_all: &[SessionItem],
     limit: usize,
 ) -> Vec<SessionItem> {
+    if target.kind == TargetKind::Remote {
+        return Vec::new();
+    }
     let repo_root = crate::sessions::git_root_for_path(&target.path);
     let target_is_repo_root = repo_root.as_ref().is_some_and(|r| r == &target.path);
     let mut out = Vec::new();
@@ -350,6 +1063,182 @@ fn selected_session(
         .cloned()
 }
 
+/// Edited files as counted by `read_rollout`, most-edited first.
+type Touched = Vec<(String, usize)>;
+
+/// Files edited per session, read from the rollout on another thread the first time a session
+/// is highlighted (a long rollout takes a while to scan).
+struct DetailCache {
+    touched: HashMap<String, Touched>,
+    /// Ids whose rollout is being read.
+    reading: HashSet<String>,
+    tx: Sender<(String, Touched)>,
+    rx: Receiver<(String, Touched)>,
+}
+
+impl Default for DetailCache {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            touched: HashMap::new(),
+            reading: HashSet::new(),
+            tx,
+            rx,
+        }
+    }
+}
+
+impl DetailCache {
+    /// Store the files read since the last call.
+    fn take_read(&mut self) {
+        for (id, files) in self.rx.try_iter() {
+            self.reading.remove(&id);
+            self.touched.insert(id, files);
+        }
+    }
+
+    /// Detail pane lines for `s`: id and cwd, then the files it edited (most-edited first).
+    fn lines(&mut self, s: &SessionItem, state: &State, cols: usize) -> Vec<String> {
+        self.take_read();
+        if !self.touched.contains_key(&s.id) && self.reading.insert(s.id.clone()) {
+            let (id, path, tx) = (s.id.clone(), s.path.clone(), self.tx.clone());
+            thread::spawn(move || {
+                let mut files = path
+                    .and_then(|p| sessions::read_rollout(&p).ok())
+                    .map(|c| c.touched_files.into_iter().collect::<Touched>())
+                    .unwrap_or_default();
+                files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                let _ = tx.send((id, files));
+            });
+        }
+        let mut header = format!("── {} · {}", s.id, s.cwd.display());
+        if let Some(tags) = state.tags_for(&s.id) {
+            for tag in tags {
+                header.push_str(&format!(" #{tag}"));
+            }
+        }
+        let edited = match self.touched.get(&s.id) {
+            None => "   edited: (reading…)".to_string(),
+            Some(files) if files.is_empty() => "   edited: (no files recorded)".to_string(),
+            Some(files) => {
+                let list = files
+                    .iter()
+                    .map(|(p, n)| format!("{p} ×{n}"))
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                format!("   edited ({}): {list}", files.len())
+            }
+        };
+        vec![truncate(header, cols), truncate(edited, cols)]
+    }
+}
+
+/// Project preview contents (repo facts plus README head), read once per project.
+#[derive(Default)]
+struct PreviewCache {
+    lines: HashMap<PathBuf, Vec<String>>,
+}
+
+impl PreviewCache {
+    /// At most `max_lines` preview lines for the project at `dir`, cut to `cols`.
+    fn lines(&mut self, dir: &Path, cols: usize, max_lines: usize) -> Vec<String> {
+        let lines = self.lines.entry(dir.to_path_buf()).or_insert_with(|| {
+            let mut header = format!("── {}", pathfmt::compact_path(dir, cols));
+            if let Some(lang) = icons::language_name(dir) {
+                header.push_str(&format!(" · {lang}"));
+            }
+            if let Some(commit) = repoinfo::last_commit(dir) {
+                header.push_str(&format!(" · HEAD {}", commit.summary()));
+            }
+            let mut lines = vec![header];
+            match repoinfo::readme_head(dir, 30) {
+                Some(readme) => lines.extend(readme.into_iter().map(|l| format!("   {l}"))),
+                None => lines.push("   (no README)".to_string()),
+            }
+            lines
+        });
+        lines
+            .iter()
+            .take(max_lines)
+            .map(|l| truncate(l.clone(), cols))
+            .collect()
+    }
+}
+
+/// Per-project row extras (icons, HEAD commit), computed when a row is first drawn.
+struct ProjectDecor {
+    icons: HashMap<PathBuf, String>,
+    heads: HashMap<PathBuf, String>,
+    /// Folders whose HEAD commit is being read on another thread (`git log` per folder).
+    reading_heads: HashSet<PathBuf>,
+    heads_tx: Sender<(PathBuf, String)>,
+    heads_rx: Receiver<(PathBuf, String)>,
+    /// Branch and dirtiness of the targets, read on another thread (`display.git_status`).
+    git: Option<Receiver<(PathBuf, Option<GitStatus>)>>,
+}
+
+impl Default for ProjectDecor {
+    fn default() -> Self {
+        let (heads_tx, heads_rx) = mpsc::channel();
+        Self {
+            icons: HashMap::new(),
+            heads: HashMap::new(),
+            reading_heads: HashSet::new(),
+            heads_tx,
+            heads_rx,
+            git: None,
+        }
+    }
+}
+
+impl ProjectDecor {
+    /// (Re)read the git status of every target in the background.
+    fn read_git(&mut self, targets: &[ProjectTarget]) {
+        let dirs = targets.iter().map(|t| t.path.clone()).collect();
+        self.git = Some(repoinfo::git_status_in_background(dirs));
+    }
+
+    /// Store the statuses read since the last call.
+    fn take_git(&self, targets: &mut [ProjectTarget]) {
+        let Some(rx) = &self.git else {
+            return;
+        };
+        for (dir, status) in rx.try_iter() {
+            if let Some(t) = targets.iter_mut().find(|t| t.path == dir) {
+                t.git = status;
+            }
+        }
+    }
+
+    fn icons(&mut self, dir: &Path) -> &str {
+        self.icons
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| icons::project(dir))
+    }
+
+    /// Store the HEAD commits read since the last call.
+    fn take_heads(&mut self) {
+        for (dir, head) in self.heads_rx.try_iter() {
+            self.reading_heads.remove(&dir);
+            self.heads.insert(dir, head);
+        }
+    }
+
+    /// `dir`'s HEAD commit, empty until it has been read in the background.
+    fn head(&mut self, dir: &Path) -> &str {
+        if !self.heads.contains_key(dir) && self.reading_heads.insert(dir.to_path_buf()) {
+            let (dir, tx) = (dir.to_path_buf(), self.heads_tx.clone());
+            thread::spawn(move || {
+                let head = repoinfo::last_commit(&dir)
+                    .map(|c| c.summary())
+                    .unwrap_or_default();
+                let _ = tx.send((dir, head));
+            });
+        }
+        self.heads.get(dir).map_or("", String::as_str)
+    }
+}
+
 struct TerminalGuard {
     use_alt_screen: bool,
 }
@@ -368,7 +1257,12 @@ impl TerminalGuard {
                 cursor::MoveTo(0, 0)
             )?;
         }
-        execute!(stdout, cursor::Hide)?;
+        execute!(
+            stdout,
+            cursor::Hide,
+            event::EnableBracketedPaste,
+            event::EnableFocusChange
+        )?;
 
         Ok(Self { use_alt_screen })
     }
@@ -377,7 +1271,12 @@ impl TerminalGuard {
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
         let mut stdout = io::stdout();
-        let _ = execute!(stdout, cursor::Show);
+        let _ = execute!(
+            stdout,
+            event::DisableFocusChange,
+            event::DisableBracketedPaste,
+            cursor::Show
+        );
         if self.use_alt_screen {
             let _ = execute!(stdout, terminal::LeaveAlternateScreen);
         } else {
@@ -397,7 +1296,44 @@ fn should_use_alt_screen() -> bool {
     true
 }
 
-fn filter_targets(targets: &[ProjectTarget], matcher: &SkimMatcherV2, filter: &str) -> Vec<usize> {
+/// `filter_targets`, pinned projects first; in `sort` order while nothing is typed.
+pub(crate) fn shown_targets(
+    targets: &[ProjectTarget],
+    matcher: &SkimMatcherV2,
+    filter: &str,
+    pins: &Pins,
+    sort: ProjectSort,
+) -> Vec<usize> {
+    let mut shown = filter_targets(targets, matcher, filter);
+    if filter.trim().is_empty() {
+        match sort {
+            ProjectSort::Recent => {}
+            ProjectSort::Label => shown.sort_by_cached_key(|i| targets[*i].label.to_lowercase()),
+            ProjectSort::Path => shown.sort_by(|a, b| targets[*a].path.cmp(&targets[*b].path)),
+            ProjectSort::Sessions => shown.sort_by_key(|i| Reverse(targets[*i].session_count)),
+        }
+    }
+    shown.sort_by_key(|i| !pins.projects.contains(&targets[*i].path));
+    shown
+}
+
+/// `filter_sessions`, pinned sessions first.
+pub(crate) fn shown_sessions(
+    items: &[SessionItem],
+    matcher: &SkimMatcherV2,
+    filter: &str,
+    pins: &Pins,
+) -> Vec<usize> {
+    let mut shown = filter_sessions(items, matcher, filter);
+    shown.sort_by_key(|i| !pins.sessions.contains(&items[*i].id));
+    shown
+}
+
+pub(crate) fn filter_targets(
+    targets: &[ProjectTarget],
+    matcher: &SkimMatcherV2,
+    filter: &str,
+) -> Vec<usize> {
     let q = filter.trim();
     if q.is_empty() {
         return (0..targets.len()).collect();
@@ -411,11 +1347,15 @@ fn filter_targets(targets: &[ProjectTarget], matcher: &SkimMatcherV2, filter: &s
             matcher.fuzzy_match(&hay, q).map(|score| (score, i))
         })
         .collect::<Vec<_>>();
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.sort_by_key(|(score, _)| Reverse(*score));
     scored.into_iter().map(|(_, i)| i).collect()
 }
 
-fn filter_sessions(items: &[SessionItem], matcher: &SkimMatcherV2, filter: &str) -> Vec<usize> {
+pub(crate) fn filter_sessions(
+    items: &[SessionItem],
+    matcher: &SkimMatcherV2,
+    filter: &str,
+) -> Vec<usize> {
     let q = filter.trim();
     if q.is_empty() {
         return (0..items.len()).collect();
@@ -434,7 +1374,7 @@ fn filter_sessions(items: &[SessionItem], matcher: &SkimMatcherV2, filter: &str)
             matcher.fuzzy_match(&hay, q).map(|score| (score, i))
         })
         .collect::<Vec<_>>();
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.sort_by_key(|(score, _)| Reverse(*score));
     scored.into_iter().map(|(_, i)| i).collect()
 }
 
@@ -442,25 +1382,67 @@ enum ListOutcome {
     Continue,
     SwitchTab(Tab),
     Activate,
+    /// Open the action menu for the highlighted project.
+    Menu,
+    CreateProject,
+    LoadMore,
+    ToggleMark,
+    Batch(BatchAction),
+    /// Open the marked projects in tmux windows.
+    LaunchAll,
     StartNew,
+    StartNewWithOptions,
+    /// Resume the highlighted project's most recent session.
+    ResumeLatest,
+    Rename,
+    TogglePin,
+    /// Switch the Projects tab to the next sort order.
+    CycleSort,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_list_key(
     key: KeyEvent,
-    filter: &mut String,
+    filter: &mut LineEditor,
+    past: &[String],
+    vim: Option<&mut VimKeys>,
+    first_row: usize,
     cursor_idx: &mut usize,
     len: usize,
     tab: Tab,
 ) -> Result<ListOutcome> {
+    if let Some(idx) = jump_target(key, first_row, len) {
+        *cursor_idx = idx;
+        return Ok(ListOutcome::Activate);
+    }
+    if key.code == KeyCode::Up && *cursor_idx == 0 && recall_filter(filter, past) {
+        return Ok(ListOutcome::Continue);
+    }
+    if types_into_filter(key, filter) {
+        if filter.handle_key(key) {
+            *cursor_idx = 0;
+        }
+        return Ok(ListOutcome::Continue);
+    }
+    if let Some(vim) = vim
+        && vim.motion(key, cursor_idx, len)
+    {
+        return Ok(ListOutcome::Continue);
+    }
+    if tab != Tab::Projects
+        && let Some(action) = batch_action(key)
+    {
+        return Ok(ListOutcome::Batch(action));
+    }
     match (key.code, key.modifiers) {
-        (KeyCode::Left, _) => {
+        (KeyCode::Left, KeyModifiers::NONE) => {
             return Ok(ListOutcome::SwitchTab(match tab {
                 Tab::Projects => Tab::Projects,
                 Tab::SessionsScoped => Tab::Projects,
                 Tab::SessionsAll => Tab::SessionsScoped,
             }));
         }
-        (KeyCode::Right, _) => {
+        (KeyCode::Right, KeyModifiers::NONE) => {
             return Ok(ListOutcome::SwitchTab(match tab {
                 Tab::Projects => Tab::SessionsScoped,
                 Tab::SessionsScoped => Tab::SessionsAll,
@@ -471,69 +1453,231 @@ fn handle_list_key(
         (KeyCode::Char('n'), KeyModifiers::NONE) if tab == Tab::Projects => {
             return Ok(ListOutcome::StartNew);
         }
+        (KeyCode::Char('r'), KeyModifiers::NONE) if tab == Tab::Projects => {
+            return Ok(ListOutcome::ResumeLatest);
+        }
+        (KeyCode::Char('R'), KeyModifiers::SHIFT) if tab == Tab::Projects => {
+            return Ok(ListOutcome::Rename);
+        }
+        (KeyCode::Char('s'), KeyModifiers::NONE) if tab == Tab::Projects => {
+            return Ok(ListOutcome::CycleSort);
+        }
+        // Many terminals can't report Shift-Enter, so `N` works too.
+        (KeyCode::Enter, KeyModifiers::SHIFT) | (KeyCode::Char('N'), KeyModifiers::SHIFT)
+            if tab == Tab::Projects =>
+        {
+            return Ok(ListOutcome::StartNewWithOptions);
+        }
         (KeyCode::Enter, _) => return Ok(ListOutcome::Activate),
+        (KeyCode::Tab, _) if tab == Tab::Projects => return Ok(ListOutcome::Menu),
+        (KeyCode::Char('n'), KeyModifiers::CONTROL) if tab == Tab::Projects => {
+            return Ok(ListOutcome::CreateProject);
+        }
+        (KeyCode::Char('m' | '+'), KeyModifiers::NONE | KeyModifiers::SHIFT)
+            if tab != Tab::Projects =>
+        {
+            return Ok(ListOutcome::LoadMore);
+        }
+        (KeyCode::Char(' '), KeyModifiers::NONE) => return Ok(ListOutcome::ToggleMark),
+        (KeyCode::Char('p'), KeyModifiers::NONE) => return Ok(ListOutcome::TogglePin),
+        (KeyCode::Char('L'), KeyModifiers::SHIFT) if tab == Tab::Projects => {
+            return Ok(ListOutcome::LaunchAll);
+        }
 
         (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
-            if len > 0 {
-                *cursor_idx = cursor_idx.saturating_sub(1);
-            }
+            *cursor_idx = cursor_idx.saturating_sub(1);
         }
         (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
-            if len > 0 {
-                *cursor_idx = (*cursor_idx + 1).min(len.saturating_sub(1));
-            }
+            *cursor_idx = (*cursor_idx + 1).min(len.saturating_sub(1));
         }
         (KeyCode::PageUp, _) => {
-            *cursor_idx = cursor_idx.saturating_sub(10);
+            *cursor_idx = cursor_idx.saturating_sub(PAGE_ROWS);
         }
         (KeyCode::PageDown, _) => {
-            if len > 0 {
-                *cursor_idx = (*cursor_idx + 10).min(len.saturating_sub(1));
-            }
+            *cursor_idx = (*cursor_idx + PAGE_ROWS).min(len.saturating_sub(1));
         }
         (KeyCode::Home, _) => {
             *cursor_idx = 0;
         }
         (KeyCode::End, _) => {
-            if len > 0 {
-                *cursor_idx = len - 1;
-            }
+            *cursor_idx = len.saturating_sub(1);
         }
 
-        (KeyCode::Backspace, _) => {
-            filter.pop();
-            *cursor_idx = 0;
-        }
-        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-            filter.clear();
-            *cursor_idx = 0;
-        }
-        (KeyCode::Char(ch), KeyModifiers::NONE) => {
-            if !ch.is_control() {
-                filter.push(ch);
+        _ => {
+            if filter.handle_key(key) {
                 *cursor_idx = 0;
             }
         }
-        _ => {}
     }
     Ok(ListOutcome::Continue)
 }
 
+/// Letter keys (`n`, `p`, `s`, `R`, …) are commands only while the filter is empty; once
+/// something is typed they go into the filter.
+fn types_into_filter(key: KeyEvent, filter: &LineEditor) -> bool {
+    !filter.text().is_empty()
+        && matches!(key.code, KeyCode::Char(c) if c != ' ')
+        && matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT)
+}
+
+/// The row `alt+1`..`alt+9` picks: the Nth visible one, counting from `first_row`.
+fn jump_target(key: KeyEvent, first_row: usize, len: usize) -> Option<usize> {
+    let KeyCode::Char(digit @ '1'..='9') = key.code else {
+        return None;
+    };
+    if key.modifiers != KeyModifiers::ALT {
+        return None;
+    }
+    let idx = first_row + (digit as usize - '1' as usize);
+    (idx < len).then_some(idx)
+}
+
+/// `↑` on the first row steps back through the view's earlier filters (newest first) while the
+/// filter is empty or still shows a recalled one. Returns whether it recalled something.
+fn recall_filter(filter: &mut LineEditor, past: &[String]) -> bool {
+    let next = if filter.text().is_empty() {
+        0
+    } else {
+        match past.iter().position(|p| p == filter.text()) {
+            Some(i) => i + 1,
+            None => return false,
+        }
+    };
+    let Some(entry) = past.get(next) else {
+        return false;
+    };
+    filter.clear();
+    filter.insert_str(entry);
+    true
+}
+
+/// Whether a downward move left the cursor on the last row, which pulls in the next page.
+/// `filter` is the list's filter after `key` was handled: a letter typed into it moved nothing.
+fn reached_end(
+    key: KeyEvent,
+    vim: bool,
+    filter: &LineEditor,
+    cursor_idx: usize,
+    len: usize,
+) -> bool {
+    if types_into_filter(key, filter) {
+        return false;
+    }
+    let moved_down = matches!(
+        (key.code, key.modifiers),
+        (KeyCode::Down | KeyCode::PageDown | KeyCode::End, _)
+            | (KeyCode::Char('j'), KeyModifiers::NONE)
+    ) || vim
+        && matches!(
+            (key.code, key.modifiers),
+            (KeyCode::Char('G'), _) | (KeyCode::Char('d' | 'f'), KeyModifiers::CONTROL)
+        );
+    moved_down && len > 0 && cursor_idx + 1 >= len
+}
+
+/// Vim-style list motions, on with `display.vim_keys`: `gg` / `G` to the first / last row,
+/// `ctrl+d` / `ctrl+u` half a page and `ctrl+f` / `ctrl+b` a page. Like the other letter
+/// commands, `g` / `G` move only while the filter is empty and are typed into it otherwise; the
+/// `ctrl` motions win over the filter's editing keys of the same name.
+#[derive(Default)]
+struct VimKeys {
+    /// The first `g` of `gg` was pressed.
+    pending_g: bool,
+}
+
+impl VimKeys {
+    /// Move `cursor_idx` if `key` is a motion; returns whether it was.
+    fn motion(&mut self, key: KeyEvent, cursor_idx: &mut usize, len: usize) -> bool {
+        let last = len.saturating_sub(1);
+        let pending_g = std::mem::take(&mut self.pending_g);
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('g'), KeyModifiers::NONE) if pending_g => *cursor_idx = 0,
+            (KeyCode::Char('g'), KeyModifiers::NONE) => self.pending_g = true,
+            (KeyCode::Char('G'), KeyModifiers::NONE | KeyModifiers::SHIFT) => *cursor_idx = last,
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                *cursor_idx = (*cursor_idx + PAGE_ROWS / 2).min(last);
+            }
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                *cursor_idx = cursor_idx.saturating_sub(PAGE_ROWS / 2);
+            }
+            (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
+                *cursor_idx = (*cursor_idx + PAGE_ROWS).min(last);
+            }
+            (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+                *cursor_idx = cursor_idx.saturating_sub(PAGE_ROWS);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
 enum ProjectSessionsOutcome {
     Continue,
     StartNew,
+    StartNewWithOptions,
     Resume { filtered_idx: usize },
+    LoadMore,
+    ToggleMark { filtered_idx: usize },
+    TogglePin { filtered_idx: usize },
+    Batch(BatchAction),
 }
 
 fn handle_project_sessions_key(
     key: KeyEvent,
-    filter: &mut String,
+    filter: &mut LineEditor,
+    past: &[String],
+    vim: Option<&mut VimKeys>,
+    first_row: usize,
     cursor_idx: &mut usize,
     sessions_len: usize,
 ) -> Result<ProjectSessionsOutcome> {
     // Cursor includes the "Start new session" row at index 0.
     let len = sessions_len + 1;
+    if let Some(idx) = jump_target(key, first_row, len) {
+        *cursor_idx = idx;
+        return Ok(match idx.checked_sub(1) {
+            None => ProjectSessionsOutcome::StartNew,
+            Some(filtered_idx) => ProjectSessionsOutcome::Resume { filtered_idx },
+        });
+    }
+    if key.code == KeyCode::Up && *cursor_idx == 0 && recall_filter(filter, past) {
+        return Ok(ProjectSessionsOutcome::Continue);
+    }
+    if types_into_filter(key, filter) {
+        if filter.handle_key(key) {
+            *cursor_idx = 0;
+        }
+        return Ok(ProjectSessionsOutcome::Continue);
+    }
+    if let Some(vim) = vim
+        && vim.motion(key, cursor_idx, len)
+    {
+        return Ok(ProjectSessionsOutcome::Continue);
+    }
+    if let Some(action) = batch_action(key) {
+        return Ok(ProjectSessionsOutcome::Batch(action));
+    }
     match (key.code, key.modifiers) {
+        (KeyCode::Enter, KeyModifiers::SHIFT) if *cursor_idx == 0 => {
+            return Ok(ProjectSessionsOutcome::StartNewWithOptions);
+        }
+        (KeyCode::Char('N'), KeyModifiers::SHIFT) => {
+            return Ok(ProjectSessionsOutcome::StartNewWithOptions);
+        }
+        (KeyCode::Char('m' | '+'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            return Ok(ProjectSessionsOutcome::LoadMore);
+        }
+        (KeyCode::Char(' '), KeyModifiers::NONE) if *cursor_idx > 0 => {
+            return Ok(ProjectSessionsOutcome::ToggleMark {
+                filtered_idx: *cursor_idx - 1,
+            });
+        }
+        (KeyCode::Char('p'), KeyModifiers::NONE) if *cursor_idx > 0 => {
+            return Ok(ProjectSessionsOutcome::TogglePin {
+                filtered_idx: *cursor_idx - 1,
+            });
+        }
         (KeyCode::Enter, _) => {
             if *cursor_idx == 0 {
                 return Ok(ProjectSessionsOutcome::StartNew);
@@ -544,69 +1688,75 @@ fn handle_project_sessions_key(
         }
 
         (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
-            if len > 0 {
-                *cursor_idx = cursor_idx.saturating_sub(1);
-            }
+            *cursor_idx = cursor_idx.saturating_sub(1);
         }
         (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
-            if len > 0 {
-                *cursor_idx = (*cursor_idx + 1).min(len.saturating_sub(1));
-            }
+            *cursor_idx = (*cursor_idx + 1).min(len.saturating_sub(1));
         }
         (KeyCode::PageUp, _) => {
-            *cursor_idx = cursor_idx.saturating_sub(10);
+            *cursor_idx = cursor_idx.saturating_sub(PAGE_ROWS);
         }
         (KeyCode::PageDown, _) => {
-            if len > 0 {
-                *cursor_idx = (*cursor_idx + 10).min(len.saturating_sub(1));
-            }
+            *cursor_idx = (*cursor_idx + PAGE_ROWS).min(len.saturating_sub(1));
         }
         (KeyCode::Home, _) => {
             *cursor_idx = 0;
         }
         (KeyCode::End, _) => {
-            if len > 0 {
-                *cursor_idx = len - 1;
-            }
+            *cursor_idx = len.saturating_sub(1);
         }
 
-        (KeyCode::Backspace, _) => {
-            filter.pop();
-            *cursor_idx = 0;
-        }
-        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-            filter.clear();
-            *cursor_idx = 0;
-        }
-        (KeyCode::Char(ch), KeyModifiers::NONE) => {
-            if !ch.is_control() {
-                filter.push(ch);
+        _ => {
+            if filter.handle_key(key) {
                 *cursor_idx = 0;
             }
         }
-        _ => {}
     }
     Ok(ProjectSessionsOutcome::Continue)
 }
 
+/// `launched 14×, last Tue 18:12`, or blank for a project never launched from here.
+fn launch_note(count: Option<&LaunchCount>, dates: DateStyle) -> String {
+    let Some(count) = count else {
+        return String::new();
+    };
+    match timefmt::from_unix(count.last) {
+        Some(last) => format!(
+            "launched {}×, last {}",
+            count.count,
+            timefmt::format_when(last, dates)
+        ),
+        None => format!("launched {}×", count.count),
+    }
+}
+
+/// Draw the Projects tab; returns the index of the top visible row.
+#[allow(clippy::too_many_arguments)]
 fn render_projects(
     stdout: &mut io::Stdout,
     targets: &[ProjectTarget],
     filtered: &[usize],
     cursor_idx: usize,
-    filter: &str,
+    filter: &LineEditor,
+    marked: &BTreeSet<PathBuf>,
+    pins: &Pins,
+    preview: &[String],
+    decor: &mut ProjectDecor,
+    launches: Option<&BTreeMap<PathBuf, LaunchCount>>,
+    display: &DisplayConfig,
     cols: usize,
     rows: usize,
-) -> Result<()> {
+) -> Result<usize> {
     let mut out = String::new();
 
     out.push_str(&tabs_line(Tab::Projects));
     out.push('\n');
-    let help = "⏎ sessions · n new · ←/→ tabs · o config · q quit";
+    let help = "⏎ sessions · n new · r resume latest · R rename · N new with options · space mark · p pin · s sort · L launch marked in tmux · ^n new project · tab actions · ←/→ tabs · ^p preview · ~ full paths · o config · q quit";
     out.push_str(&format!("{}\n", truncate(help.to_string(), cols).dim()));
-    out.push_str(&format!("{} {}\n", "Filter:".bold(), filter));
+    out.push_str(&format!("{} {}\n", "Filter:".bold(), filter.render()));
 
-    let list_rows = rows.saturating_sub(5).max(1);
+    let preview = detail_if_room(preview, rows);
+    let list_rows = rows.saturating_sub(5 + preview.len()).max(1);
     let start = cursor_idx.saturating_sub(list_rows / 2);
     let end = (start + list_rows).min(filtered.len());
 
@@ -617,48 +1767,87 @@ fn render_projects(
         .take(end.saturating_sub(start))
     {
         let t = &targets[*idx];
-        let mut line = t.to_string();
-        if line.chars().count() > cols.saturating_sub(2) {
-            line = truncate(line, cols.saturating_sub(3));
+        let head = display.last_commit.then(|| decor.head(&t.path));
+        let launched = launches.map(|l| launch_note(l.get(&t.path), display.dates));
+        let mut line = t.row_text(
+            display.git_status,
+            head,
+            launched.as_deref(),
+            display.dates,
+            display.paths,
+        );
+        if display.icons {
+            line = format!("{} {line}", decor.icons(&t.path));
+        }
+        if line.chars().count() > cols.saturating_sub(4) {
+            line = truncate(line, cols.saturating_sub(5));
         }
+        let mark = if marked.contains(&t.path) {
+            '*'
+        } else if pins.projects.contains(&t.path) {
+            '★'
+        } else {
+            ' '
+        };
+        let label = jump_label(row_offset - start);
         if row_offset == cursor_idx {
-            out.push_str(&format!("{}\n", format!("> {line}").reverse()));
+            out.push_str(&format!("{}\n", format!(">{mark}{label}{line}").reverse()));
         } else {
-            out.push_str(&format!("  {line}\n"));
+            out.push_str(&format!(" {mark}{label}{line}\n"));
         }
     }
 
     out.push_str(&format!(
         "{}\n",
-        format!("{} / {}", filtered.len(), targets.len()).dim()
+        format!(
+            "{} / {}{}",
+            filtered.len(),
+            targets.len(),
+            selected_note(marked)
+        )
+        .dim()
     ));
+    push_detail(&mut out, preview);
 
-    draw(stdout, out)
+    draw(stdout, out)?;
+    Ok(start)
 }
 
+/// Draw a session tab; returns the index of the top visible row.
+#[allow(clippy::too_many_arguments)]
 fn render_sessions(
     stdout: &mut io::Stdout,
     tab: Tab,
     items: &[SessionItem],
     filtered: &[usize],
     cursor_idx: usize,
-    filter: &str,
+    filter: &LineEditor,
+    has_more: bool,
+    marked: &BTreeSet<PathBuf>,
+    pins: &Pins,
+    detail: &[String],
+    display: &DisplayConfig,
     cols: usize,
     rows: usize,
-) -> Result<()> {
+) -> Result<usize> {
     let mut out = String::new();
 
     out.push_str(&tabs_line(tab));
     out.push('\n');
     let help = match tab {
-        Tab::SessionsScoped => "⏎ resume · esc back · ←/→ tabs · o config · q quit",
-        Tab::SessionsAll => "⏎ resume · esc back · ← tabs · o config · q quit",
+        Tab::SessionsScoped => {
+            "⏎ resume · space mark · p pin · D/A/T/E/K delete/archive/tag/export/signal · m more · esc back · ←/→ tabs · o config · q quit"
+        }
+        Tab::SessionsAll => {
+            "⏎ resume · space mark · p pin · D/A/T/E/K delete/archive/tag/export/signal · m more · esc back · ← tabs · o config · q quit"
+        }
         _ => "⏎ resume · esc back · o config · q quit",
     };
     out.push_str(&format!("{}\n", truncate(help.to_string(), cols).dim()));
-    out.push_str(&format!("{} {}\n", "Filter:".bold(), filter));
+    out.push_str(&format!("{} {}\n", "Filter:".bold(), filter.render()));
 
-    let list_rows = rows.saturating_sub(5).max(1);
+    let detail = detail_if_room(detail, rows);
+    let list_rows = rows.saturating_sub(5 + detail.len()).max(1);
     let start = cursor_idx.saturating_sub(list_rows / 2);
     let end = (start + list_rows).min(filtered.len());
 
@@ -669,35 +1858,57 @@ fn render_sessions(
         .take(end.saturating_sub(start))
     {
         let s = &items[*idx];
-        let mut line = s.to_string();
-        if line.chars().count() > cols.saturating_sub(2) {
-            line = truncate(line, cols.saturating_sub(3));
-        }
+        let line = fit_with_badges(
+            with_source_icon(s.row_text(display.dates, display.paths), s, display),
+            None,
+            s,
+            display,
+            cols.saturating_sub(4),
+            row_offset == cursor_idx,
+        );
+        let mark = mark_char(marked, pins, s);
+        let label = jump_label(row_offset - start);
         if row_offset == cursor_idx {
-            out.push_str(&format!("{}\n", format!("> {line}").reverse()));
+            out.push_str(&format!("{}\n", format!(">{mark}{label}{line}").reverse()));
         } else {
-            out.push_str(&format!("  {line}\n"));
+            out.push_str(&format!(" {mark}{label}{line}\n"));
         }
     }
 
+    let more = if has_more { " · m load more" } else { "" };
     out.push_str(&format!(
         "{}\n",
-        format!("{} / {}", filtered.len(), items.len()).dim()
+        format!(
+            "{} / {}{more}{}",
+            filtered.len(),
+            items.len(),
+            selected_note(marked)
+        )
+        .dim()
     ));
+    push_detail(&mut out, detail);
 
-    draw(stdout, out)
+    draw(stdout, out)?;
+    Ok(start)
 }
 
+/// Draw a project's session list; returns the index of the top visible row.
+#[allow(clippy::too_many_arguments)]
 fn render_project_sessions(
     stdout: &mut io::Stdout,
     target: &ProjectTarget,
     sessions: &[SessionItem],
     filtered: &[usize],
     cursor_idx: usize,
-    filter: &str,
+    filter: &LineEditor,
+    has_more: bool,
+    marked: &BTreeSet<PathBuf>,
+    pins: &Pins,
+    detail: &[String],
+    display: &DisplayConfig,
     cols: usize,
     rows: usize,
-) -> Result<()> {
+) -> Result<usize> {
     let mut out = String::new();
 
     out.push_str(&format!(
@@ -705,16 +1916,19 @@ fn render_project_sessions(
         "Project:".bold(),
         truncate(target.label.clone(), cols.saturating_sub(10))
     ));
-    let help = "⏎ select · esc back · o config · q quit";
+    let help = "⏎ select · N new with options · m more · space mark · p pin · D/A/T/E/K delete/archive/tag/export/signal · esc back · o config · q quit";
     out.push_str(&format!("{}\n", truncate(help.to_string(), cols).dim()));
-    out.push_str(&format!("{} {}\n", "Filter:".bold(), filter));
+    out.push_str(&format!("{} {}\n", "Filter:".bold(), filter.render()));
 
     // Cursor includes "Start new session" at row 0.
-    let mut lines: Vec<String> = Vec::new();
-    lines.push("Start new session".to_string());
+    let mut lines: Vec<(String, Option<&SessionItem>)> = Vec::new();
+    lines.push(("Start new session".to_string(), None));
     for idx in filtered.iter() {
         if let Some(s) = sessions.get(*idx) {
-            lines.push(session_line_no_path(s));
+            lines.push((
+                with_source_icon(session_line_no_path(s, display.dates), s, display),
+                Some(s),
+            ));
         }
     }
 
@@ -723,35 +1937,133 @@ fn render_project_sessions(
         // (Callers ensure this, but keep it safe for rendering.)
     }
 
-    let list_rows = rows.saturating_sub(5).max(1);
+    let detail = detail_if_room(detail, rows);
+    let list_rows = rows.saturating_sub(5 + detail.len()).max(1);
     let start = cursor_idx.saturating_sub(list_rows / 2);
     let end = (start + list_rows).min(lines.len());
 
-    for (row_offset, line) in lines
+    for (row_offset, (line, session)) in lines
         .iter()
         .enumerate()
         .skip(start)
         .take(end.saturating_sub(start))
     {
-        let mut line = line.clone();
-        if line.chars().count() > cols.saturating_sub(2) {
-            line = truncate(line, cols.saturating_sub(3));
-        }
+        let (line, mark) = match session {
+            Some(s) => (
+                fit_with_badges(
+                    line.clone(),
+                    subpath(&target.path, &s.cwd).as_deref(),
+                    s,
+                    display,
+                    cols.saturating_sub(4),
+                    row_offset == cursor_idx,
+                ),
+                mark_char(marked, pins, s),
+            ),
+            None => (truncate(line.clone(), cols.saturating_sub(4)), ' '),
+        };
+        let label = jump_label(row_offset - start);
         if row_offset == cursor_idx {
-            out.push_str(&format!("{}\n", format!("> {line}").reverse()));
+            out.push_str(&format!("{}\n", format!(">{mark}{label}{line}").reverse()));
         } else {
-            out.push_str(&format!("  {line}\n"));
+            out.push_str(&format!(" {mark}{label}{line}\n"));
         }
     }
 
     out.push_str(&format!(
         "{}\n",
-        format!("{} sessions", sessions.len()).dim()
+        format!(
+            "{} sessions{}{}",
+            sessions.len(),
+            if has_more { " · m load more" } else { "" },
+            selected_note(marked)
+        )
+        .dim()
     ));
+    push_detail(&mut out, detail);
+
+    draw(stdout, out)?;
+    Ok(start)
+}
+
+fn render_action_menu(
+    stdout: &mut io::Stdout,
+    target: &ProjectTarget,
+    items: &[(String, MenuItem)],
+    cursor_idx: usize,
+    cols: usize,
+) -> Result<()> {
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "{}  {}\n",
+        "Project:".bold(),
+        truncate(target.label.clone(), cols.saturating_sub(10))
+    ));
+    let help = "⏎ run · esc back · q quit";
+    out.push_str(&format!("{}\n\n", truncate(help.to_string(), cols).dim()));
+
+    for (i, (label, _)) in items.iter().enumerate() {
+        let line = truncate(label.clone(), cols.saturating_sub(2));
+        if i == cursor_idx {
+            out.push_str(&format!("{}\n", format!("> {line}").reverse()));
+        } else {
+            out.push_str(&format!("  {line}\n"));
+        }
+    }
 
     draw(stdout, out)
 }
 
+/// The detail pane only shows when the terminal is tall enough to keep a useful list.
+fn detail_if_room(detail: &[String], rows: usize) -> &[String] {
+    if rows >= 12 { detail } else { &[] }
+}
+
+fn push_detail(out: &mut String, detail: &[String]) {
+    for line in detail {
+        out.push_str(&format!("{}\n", line.as_str().dim()));
+    }
+}
+
+/// The `alt+N` index shown before the `offset`th visible row: `1`–`9`, then blank.
+fn jump_label(offset: usize) -> String {
+    if offset < 9 {
+        format!("{} ", offset + 1)
+    } else {
+        "  ".to_string()
+    }
+}
+
+/// `*` for a marked row, `★` for a pinned one.
+fn mark_char(marked: &BTreeSet<PathBuf>, pins: &Pins, s: &SessionItem) -> char {
+    if s.path.as_ref().is_some_and(|p| marked.contains(p)) {
+        '*'
+    } else if pins.sessions.contains(&s.id) {
+        '★'
+    } else {
+        ' '
+    }
+}
+
+fn selected_note(marked: &BTreeSet<PathBuf>) -> String {
+    if marked.is_empty() {
+        String::new()
+    } else {
+        format!(" · {} selected", marked.len())
+    }
+}
+
+/// Overlay a one-off status message on the bottom row of the current frame.
+fn draw_status(stdout: &mut io::Stdout, msg: &str, cols: usize, rows: usize) -> Result<()> {
+    stdout
+        .queue(cursor::MoveTo(0, rows.saturating_sub(1) as u16))?
+        .queue(terminal::Clear(ClearType::CurrentLine))?
+        .queue(style::Print(truncate(msg.to_string(), cols).bold()))?;
+    stdout.flush()?;
+    Ok(())
+}
+
 fn draw(stdout: &mut io::Stdout, out: String) -> Result<()> {
     stdout
         .queue(terminal::Clear(ClearType::All))?
@@ -797,19 +2109,13 @@ fn tab_label(label: &str, active: bool) -> String {
     }
 }
 
-fn session_line_no_path(s: &SessionItem) -> String {
+fn session_line_no_path(s: &SessionItem, dates: DateStyle) -> String {
     let id_short = s.id.chars().take(8).collect::<String>();
     let when = s
         .created_at
         .as_deref()
         .and_then(crate::timefmt::parse_rfc3339)
-        .map(|dt| {
-            format!(
-                "{} {}",
-                crate::timefmt::format_age(dt),
-                crate::timefmt::format_short(dt)
-            )
-        })
+        .map(|dt| crate::timefmt::format_when(dt, dates))
         .unwrap_or_else(|| "-".to_string());
 
     let summary = s
@@ -818,22 +2124,248 @@ fn session_line_no_path(s: &SessionItem) -> String {
         .map(|x| x.to_string())
         .unwrap_or_default();
 
-    let mut meta = Vec::new();
-    if let Some(p) = s.model_provider.as_deref() {
-        meta.push(p);
+    let glyph = s.outcome.glyph();
+    if summary.trim().is_empty() {
+        format!("{glyph} {:<15}  {:<8}", when, id_short)
+    } else {
+        format!("{glyph} {:<15}  {:<8}  {}", when, id_short, summary.trim())
     }
-    if let Some(src) = s.source.as_deref() {
-        meta.push(src);
+}
+
+/// Where a session ran relative to its project folder (`crates/tui`), if it was a subfolder.
+fn subpath(project: &Path, cwd: &Path) -> Option<String> {
+    let rel = cwd.strip_prefix(project).ok()?;
+    (!rel.as_os_str().is_empty()).then(|| rel.display().to_string())
+}
+
+fn with_source_icon(text: String, s: &SessionItem, display: &DisplayConfig) -> String {
+    if display.icons {
+        format!("{} {text}", icons::session_source(s.source.as_deref()))
+    } else {
+        text
     }
-    let meta = if meta.is_empty() {
-        String::new()
+}
+
+/// `text` cut to fit `width` together with the session's colored badges, which always stay visible.
+/// A `suffix` (a session's subfolder) is kept visible too, in grey after the text. On the
+/// `selected` row both stay uncolored: a color's reset would also end the row's reverse video.
+fn fit_with_badges(
+    text: String,
+    suffix: Option<&str>,
+    s: &SessionItem,
+    display: &DisplayConfig,
+    width: usize,
+    selected: bool,
+) -> String {
+    let badges = s.badges();
+    let badges_width: usize = badges.iter().map(|(_, v)| v.chars().count() + 1).sum();
+    let suffix_width = suffix.map_or(0, |x| x.chars().count() + 2);
+    let room = width.saturating_sub(badges_width + suffix_width + 1);
+    let mut out = if text.chars().count() > room {
+        truncate(text, room)
     } else {
-        format!("  [{}]", meta.join(" "))
+        text
     };
+    if let Some(suffix) = suffix {
+        out.push_str("  ");
+        if selected {
+            out.push_str(suffix);
+        } else {
+            out.push_str(&suffix.with(Color::DarkGrey).to_string());
+        }
+    }
+    if !badges.is_empty() {
+        out.push(' ');
+    }
+    for (kind, value) in badges {
+        out.push(' ');
+        if selected {
+            out.push_str(value);
+        } else {
+            out.push_str(&value.with(badge_color(display, kind, value)).to_string());
+        }
+    }
+    out
+}
 
-    if summary.trim().is_empty() {
-        format!("{:<12}  {:<8}{}", when, id_short, meta)
-    } else {
-        format!("{:<12}  {:<8}  {}{}", when, id_short, summary.trim(), meta)
+fn badge_color(display: &DisplayConfig, kind: BadgeKind, value: &str) -> Color {
+    let configured = display
+        .badge_colors
+        .get(value)
+        .or_else(|| display.badge_colors.get(kind.name()))
+        .and_then(|c| Color::try_from(c.as_str()).ok());
+    configured.unwrap_or(match kind {
+        BadgeKind::Provider => Color::DarkGreen,
+        BadgeKind::Source => Color::DarkCyan,
+        BadgeKind::Model => Color::DarkMagenta,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn filter(text: &str) -> LineEditor {
+        let mut ed = LineEditor::default();
+        ed.insert_str(text);
+        ed
+    }
+
+    fn session(id: &str, path: Option<&str>) -> SessionItem {
+        SessionItem {
+            id: id.to_string(),
+            created_at: None,
+            cwd: PathBuf::from("/code/api"),
+            summary: None,
+            cli_version: None,
+            model_provider: None,
+            source: None,
+            model: None,
+            path: path.map(PathBuf::from),
+            outcome: Default::default(),
+        }
+    }
+
+    #[test]
+    fn letters_type_into_a_non_empty_filter_only() {
+        let n = key(KeyCode::Char('n'), KeyModifiers::NONE);
+        let big_n = key(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert!(!types_into_filter(n, &filter("")));
+        assert!(!types_into_filter(big_n, &filter("")));
+        assert!(types_into_filter(n, &filter("a")));
+        assert!(types_into_filter(big_n, &filter("a")));
+
+        let space = key(KeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(!types_into_filter(space, &filter("a")), "space still marks");
+        let ctrl_n = key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert!(!types_into_filter(ctrl_n, &filter("a")));
+        assert!(!types_into_filter(
+            key(KeyCode::Enter, KeyModifiers::NONE),
+            &filter("a")
+        ));
+    }
+
+    #[test]
+    fn alt_digits_jump_to_visible_rows() {
+        let alt = |c| key(KeyCode::Char(c), KeyModifiers::ALT);
+        assert_eq!(jump_target(alt('1'), 0, 5), Some(0));
+        assert_eq!(jump_target(alt('3'), 10, 20), Some(12));
+        assert_eq!(
+            jump_target(alt('9'), 0, 5),
+            None,
+            "past the end of the list"
+        );
+        assert_eq!(jump_target(alt('0'), 0, 5), None);
+        assert_eq!(
+            jump_target(key(KeyCode::Char('1'), KeyModifiers::NONE), 0, 5),
+            None
+        );
+    }
+
+    #[test]
+    fn batch_takes_marked_sessions_or_the_highlighted_one() {
+        let all = [
+            session("a", Some("/r/a.jsonl")),
+            session("b", Some("/r/b.jsonl")),
+            session("h", None),
+        ];
+        let ids = |picked: Option<Vec<SessionItem>>| {
+            picked.map(|v| v.into_iter().map(|s| s.id).collect::<Vec<_>>())
+        };
+
+        let none = BTreeSet::new();
+        assert_eq!(
+            ids(batch_sessions(&none, &all, Some(all[2].clone()))),
+            Some(vec!["h".into()])
+        );
+        assert_eq!(ids(batch_sessions(&none, &all, None)), None);
+
+        let marked = BTreeSet::from([PathBuf::from("/r/b.jsonl")]);
+        assert_eq!(
+            ids(batch_sessions(&marked, &all, Some(all[0].clone()))),
+            Some(vec!["b".into()]),
+            "marks win over the highlighted row"
+        );
+    }
+
+    #[test]
+    fn vim_motions_jump_and_page() {
+        let mut vim = VimKeys::default();
+        let mut cursor = 5;
+        let g = key(KeyCode::Char('g'), KeyModifiers::NONE);
+        assert!(vim.motion(g, &mut cursor, 100));
+        assert_eq!(cursor, 5, "a single g only waits for the second");
+        assert!(vim.motion(g, &mut cursor, 100));
+        assert_eq!(cursor, 0);
+
+        assert!(vim.motion(
+            key(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            &mut cursor,
+            100
+        ));
+        assert_eq!(cursor, 99);
+        assert!(vim.motion(
+            key(KeyCode::Char('u'), KeyModifiers::CONTROL),
+            &mut cursor,
+            100
+        ));
+        assert_eq!(cursor, 99 - PAGE_ROWS / 2);
+        assert!(vim.motion(
+            key(KeyCode::Char('b'), KeyModifiers::CONTROL),
+            &mut cursor,
+            100
+        ));
+        assert_eq!(cursor, 99 - PAGE_ROWS / 2 - PAGE_ROWS);
+        assert!(vim.motion(
+            key(KeyCode::Char('f'), KeyModifiers::CONTROL),
+            &mut cursor,
+            100
+        ));
+        assert!(vim.motion(
+            key(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            &mut cursor,
+            100
+        ));
+        assert_eq!(cursor, 99, "paging down stops at the last row");
+
+        cursor = 3;
+        assert!(vim.motion(g, &mut cursor, 100));
+        assert!(!vim.motion(
+            key(KeyCode::Char('x'), KeyModifiers::NONE),
+            &mut cursor,
+            100
+        ));
+        assert!(vim.motion(g, &mut cursor, 100));
+        assert_eq!(cursor, 3, "another key in between cancels a pending g");
+    }
+
+    #[test]
+    fn reached_end_needs_a_downward_move_onto_the_last_row() {
+        let empty = filter("");
+        let down = key(KeyCode::Down, KeyModifiers::NONE);
+        assert!(reached_end(down, false, &empty, 9, 10));
+        assert!(!reached_end(down, false, &empty, 8, 10));
+        assert!(!reached_end(down, false, &empty, 0, 0));
+        assert!(!reached_end(
+            key(KeyCode::Up, KeyModifiers::NONE),
+            false,
+            &empty,
+            9,
+            10
+        ));
+
+        let big_g = key(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert!(reached_end(big_g, true, &empty, 9, 10));
+        assert!(!reached_end(big_g, false, &empty, 9, 10));
+        let j = key(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert!(
+            !reached_end(j, false, &filter("proj"), 0, 1),
+            "j went into the filter"
+        );
+        assert!(!reached_end(big_g, true, &filter("G"), 0, 1));
     }
 }
@@ -1,12 +1,16 @@
 use std::ffi::OsStr;
 use std::fmt;
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 
 use anyhow::Result;
 use console::style;
 use crossterm::terminal;
-use inquire::{Select, validator::Validation};
+use inquire::{MultiSelect, Select, validator::Validation};
 
+use crate::codex_config::{APPROVAL_POLICIES, CodexCliConfig, SANDBOX_MODES};
+use crate::github::Repo;
+use crate::procs::CodexProcess;
 use crate::projects::ProjectTarget;
 use crate::sessions::SessionItem;
 
@@ -56,10 +60,284 @@ pub fn pick_session(items: &[SessionItem]) -> Result<SessionItem> {
     Ok(picked.value)
 }
 
+pub fn pick_repo(repos: &[Repo]) -> Result<Repo> {
+    let width = terminal_width().saturating_sub(4);
+    let options = repos
+        .iter()
+        .cloned()
+        .map(|r| UiOption {
+            line: truncate_to_width(
+                format!(
+                    "{}  {}",
+                    r.name_with_owner,
+                    style(r.description.as_deref().unwrap_or("")).dim()
+                ),
+                width,
+            ),
+            value: r,
+        })
+        .collect::<Vec<_>>();
+    let picked = Select::new("Clone and launch:", options)
+        .with_help_message("↑↓ to move, enter to select, type to filter")
+        .with_page_size(20.min(repos.len().max(1)))
+        .prompt()?;
+    Ok(picked.value)
+}
+
+/// Which root a new project goes under.
+pub fn pick_root(roots: &[PathBuf]) -> Result<PathBuf> {
+    let options = roots
+        .iter()
+        .map(|p| UiOption {
+            value: p.clone(),
+            line: p.display().to_string(),
+        })
+        .collect::<Vec<_>>();
+    let picked = Select::new("Create under:", options)
+        .with_help_message("↑↓ to move, enter to select, type to filter")
+        .with_page_size(20.min(roots.len().max(1)))
+        .prompt()?;
+    Ok(picked.value)
+}
+
+/// Which of several codex processes in the session's folder to signal.
+pub fn pick_process(
+    session: &SessionItem,
+    procs: Vec<CodexProcess>,
+) -> Result<Option<CodexProcess>> {
+    let options = procs
+        .into_iter()
+        .map(|p| UiOption {
+            line: format!("{:>7}  {}", p.pid, p.command),
+            value: p,
+        })
+        .collect::<Vec<_>>();
+    let picked = Select::new(
+        &format!("Several codex processes run in {}:", session.cwd.display()),
+        options,
+    )
+    .with_help_message("↑↓ to move, enter to select, esc to skip")
+    .prompt_skippable()?;
+    Ok(picked.map(|o| o.value))
+}
+
+/// The signal to send (`INT` / `TERM`), or `None` to leave the processes alone. Each process's
+/// full command line is shown first, so it's clear what would be signalled.
+pub fn pick_signal(procs: &[CodexProcess]) -> Result<Option<&'static str>> {
+    for p in procs {
+        print_info(&format!("{:>7}  {}", p.pid, p.command));
+    }
+    let pids = procs
+        .iter()
+        .map(|p| p.pid.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let options = vec![
+        UiOption {
+            value: Some("INT"),
+            line: "SIGINT: interrupt the running turn".to_string(),
+        },
+        UiOption {
+            value: Some("TERM"),
+            line: "SIGTERM: quit codex".to_string(),
+        },
+        UiOption {
+            value: None,
+            line: "Cancel".to_string(),
+        },
+    ];
+    let picked = Select::new(&format!("Signal codex (pid {pids})?"), options)
+        .with_starting_cursor(2)
+        .prompt_skippable()?;
+    Ok(picked.and_then(|o| o.value))
+}
+
+/// Multi-select over `add-root --scan` candidates; returns the chosen folders.
+pub fn pick_roots(candidates: &[(PathBuf, usize)]) -> Result<Vec<PathBuf>> {
+    let options = candidates
+        .iter()
+        .map(|(p, repos)| UiOption {
+            value: p.clone(),
+            line: format!("{}  {}", p.display(), style(format!("{repos} repos")).dim()),
+        })
+        .collect::<Vec<_>>();
+    let picked = MultiSelect::new("Add as roots:", options)
+        .with_help_message("↑↓ to move, space to toggle, enter to add, type to filter")
+        .with_page_size(20.min(candidates.len().max(1)))
+        .prompt()?;
+    Ok(picked.into_iter().map(|o| o.value).collect())
+}
+
+/// Multi-select over configured roots and paths (`(kind, path)`); returns the chosen paths.
+pub fn pick_config_entries(entries: &[(&str, PathBuf)]) -> Result<Vec<PathBuf>> {
+    let options = entries
+        .iter()
+        .map(|(kind, p)| UiOption {
+            value: p.clone(),
+            line: format!("{}  {}", p.display(), style(kind).dim()),
+        })
+        .collect::<Vec<_>>();
+    let picked = MultiSelect::new("Remove from config:", options)
+        .with_help_message("↑↓ to move, space to toggle, enter to remove, type to filter")
+        .with_page_size(20.min(entries.len().max(1)))
+        .prompt()?;
+    Ok(picked.into_iter().map(|o| o.value).collect())
+}
+
+/// Returns `None` when the user keeps Codex's default (no `--profile`).
+pub fn pick_profile(codex_cfg: &CodexCliConfig) -> Result<Option<String>> {
+    let mut options = vec![UiOption {
+        value: None,
+        line: "(default)".to_string(),
+    }];
+    for name in codex_cfg.profiles.keys() {
+        options.push(UiOption {
+            value: Some(name.clone()),
+            line: format!("{name}  {}", style(codex_cfg.describe_profile(name)).dim()),
+        });
+    }
+    let page_size = 20.min(options.len());
+    let picked = Select::new("Codex profile:", options)
+        .with_help_message("↑↓ to move, enter to select, esc for default")
+        .with_page_size(page_size)
+        .prompt_skippable()?;
+    Ok(picked.and_then(|o| o.value))
+}
+
+/// Ask for sandbox mode, approval policy and model; each prompt can be skipped (esc) to keep
+/// Codex's default. Returns the extra `codex` args to append.
+pub fn pick_launch_options(codex_cfg: &CodexCliConfig, models: &[String]) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let sandbox = pick_one_or_default(
+        "Sandbox mode:",
+        codex_cfg.sandbox_mode.as_deref(),
+        SANDBOX_MODES.iter().map(|s| s.to_string()).collect(),
+    )?;
+    if let Some(mode) = sandbox {
+        args.extend(["--sandbox".to_string(), mode]);
+    }
+    let approval = pick_one_or_default(
+        "Approval policy:",
+        codex_cfg.approval_policy.as_deref(),
+        APPROVAL_POLICIES.iter().map(|s| s.to_string()).collect(),
+    )?;
+    if let Some(policy) = approval {
+        args.extend(["--ask-for-approval".to_string(), policy]);
+    }
+    if !models.is_empty() {
+        let model = pick_one_or_default("Model:", codex_cfg.model.as_deref(), models.to_vec())?;
+        if let Some(model) = model {
+            args.extend(["--model".to_string(), model]);
+        }
+    }
+    Ok(args)
+}
+
+fn pick_one_or_default(
+    prompt: &str,
+    current: Option<&str>,
+    choices: Vec<String>,
+) -> Result<Option<String>> {
+    let default_line = match current {
+        Some(c) => format!("(default: {c})"),
+        None => "(default)".to_string(),
+    };
+    let mut options = vec![UiOption {
+        value: None,
+        line: default_line,
+    }];
+    options.extend(choices.into_iter().map(|c| UiOption {
+        line: c.clone(),
+        value: Some(c),
+    }));
+    let page_size = 20.min(options.len());
+    let picked = Select::new(prompt, options)
+        .with_help_message("↑↓ to move, enter to select, esc for default")
+        .with_page_size(page_size)
+        .prompt_skippable()?;
+    Ok(picked.and_then(|o| o.value))
+}
+
+pub fn confirm(prompt: &str) -> Result<bool> {
+    confirm_with_default(prompt, false)
+}
+
+/// Yes/no prompt answered with `default` on enter; esc means no.
+pub fn confirm_with_default(prompt: &str, default: bool) -> Result<bool> {
+    Ok(inquire::Confirm::new(prompt)
+        .with_default(default)
+        .prompt_skippable()?
+        .unwrap_or(false))
+}
+
+/// Free-text prompt; `None` when skipped (esc).
+pub fn prompt_text(prompt: &str, default: Option<&str>) -> Result<Option<String>> {
+    let mut text = inquire::Text::new(prompt).with_validator(validate_nonempty);
+    if let Some(d) = default {
+        text = text.with_default(d);
+    }
+    Ok(text.prompt_skippable()?.map(|s| s.trim().to_string()))
+}
+
+/// Where in `root` to start codex: one of `packages`, another folder typed in (relative to
+/// `root`), or `None` for `root` itself (also on esc).
+pub fn pick_subdir(root: &Path, packages: &[PathBuf]) -> Result<Option<PathBuf>> {
+    let mut options = vec![UiOption {
+        value: Some(PathBuf::new()),
+        line: format!("{} (project root)", root.display()),
+    }];
+    options.extend(packages.iter().map(|p| UiOption {
+        value: Some(p.clone()),
+        line: p.display().to_string(),
+    }));
+    options.push(UiOption {
+        value: None,
+        line: "Other folder…".to_string(),
+    });
+    let Some(picked) = Select::new("Start Codex in:", options)
+        .with_help_message("↑↓ to move, enter to select, type to filter")
+        .with_page_size(15)
+        .prompt_skippable()?
+    else {
+        return Ok(None);
+    };
+    let sub = match picked.value {
+        Some(sub) => sub,
+        None => {
+            let root = root.to_path_buf();
+            let typed = inquire::Text::new("Folder (relative to the project):")
+                .with_validator(move |input: &str| {
+                    let sub = Path::new(input.trim());
+                    // `..` and absolute paths would leave the project root.
+                    let inside = sub
+                        .components()
+                        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+                    Ok(if !inside {
+                        Validation::Invalid("must be a folder inside the project".into())
+                    } else if root.join(sub).is_dir() {
+                        Validation::Valid
+                    } else {
+                        Validation::Invalid("no such folder in the project".into())
+                    })
+                })
+                .prompt_skippable()?;
+            match typed {
+                Some(t) => PathBuf::from(t.trim()),
+                None => return Ok(None),
+            }
+        }
+    };
+    Ok((!sub.as_os_str().is_empty()).then_some(sub))
+}
+
 pub fn print_info(msg: &str) {
     eprintln!("{} {}", style("info").dim(), msg);
 }
 
+pub fn print_warn(msg: &str) {
+    eprintln!("{} {}", style("warn").yellow(), msg);
+}
+
 pub fn format_command(cmd: &Command) -> String {
     let program = cmd.get_program().to_string_lossy().to_string();
     let args = cmd
@@ -79,8 +357,79 @@ pub fn format_command(cmd: &Command) -> String {
     }
 }
 
-fn shell_escape(s: &OsStr) -> String {
-    let t = s.to_string_lossy();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CommandFormat {
+    /// `cd <dir> && [env K=V …] program args…`
+    Shell,
+    /// `{"cwd": …, "env": {…}, "program": …, "args": […]}`
+    Json,
+}
+
+/// Render a command so another tool can run it verbatim (unlike `format_command`, which is for
+/// humans). `hooks` are shell snippets that must run first, in the same directory.
+pub fn render_command(cmd: &Command, hooks: &[String], format: CommandFormat) -> String {
+    let env = cmd
+        .get_envs()
+        .filter_map(|(k, v)| {
+            Some((
+                k.to_string_lossy().to_string(),
+                v?.to_string_lossy().to_string(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    match format {
+        CommandFormat::Shell => {
+            let mut parts = Vec::new();
+            if !env.is_empty() {
+                parts.push("env".to_string());
+                for (k, v) in env.iter() {
+                    parts.push(shell_escape(OsStr::new(&format!("{k}={v}"))));
+                }
+            }
+            parts.push(shell_escape(cmd.get_program()));
+            parts.extend(cmd.get_args().map(shell_escape));
+            let mut steps = Vec::new();
+            if let Some(dir) = cmd.get_current_dir() {
+                steps.push(format!("cd {}", shell_escape(dir.as_os_str())));
+            }
+            steps.extend(hooks.iter().map(|h| format!("( {h} )")));
+            steps.push(parts.join(" "));
+            steps.join(" && ")
+        }
+        CommandFormat::Json => serde_json::json!({
+            "cwd": cmd.get_current_dir().map(|d| d.to_string_lossy().to_string()),
+            "env": env.into_iter().collect::<std::collections::BTreeMap<_, _>>(),
+            "hooks": hooks,
+            "program": cmd.get_program().to_string_lossy(),
+            "args": cmd.get_args().map(|a| a.to_string_lossy()).collect::<Vec<_>>(),
+        })
+        .to_string(),
+    }
+}
+
+pub(crate) fn shell_escape(s: &OsStr) -> String {
+    let bytes = s.as_encoded_bytes();
+    match std::str::from_utf8(bytes) {
+        Ok(t) => shell_quote(t),
+        // Bytes that aren't UTF-8 (possible in Unix file names) are spelled out as printf octal
+        // escapes, which any POSIX shell understands, so the word still names the same file.
+        Err(_) => bytes
+            .utf8_chunks()
+            .map(|chunk| {
+                let mut word = match chunk.valid() {
+                    "" => String::new(),
+                    valid => shell_quote(valid),
+                };
+                for b in chunk.invalid() {
+                    word.push_str(&format!("\"$(printf '\\{b:03o}')\""));
+                }
+                word
+            })
+            .collect(),
+    }
+}
+
+fn shell_quote(t: &str) -> String {
     if t.is_empty() {
         "''".to_string()
     } else if t
@@ -116,7 +465,6 @@ fn truncate_to_width(s: String, max_chars: usize) -> String {
     out
 }
 
-#[allow(dead_code)]
 fn validate_nonempty(input: &str) -> Result<Validation, inquire::CustomUserError> {
     if input.trim().is_empty() {
         Ok(Validation::Invalid("must not be empty".into()))
@@ -124,3 +472,23 @@ fn validate_nonempty(input: &str) -> Result<Validation, inquire::CustomUserError
         Ok(Validation::Valid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn shell_escape_keeps_non_utf8_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"caf\xe9 it's");
+        let word = shell_escape(name);
+        let out = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("printf %s {word}"))
+            .output()
+            .unwrap();
+        assert_eq!(out.stdout, name.as_bytes());
+    }
+}
@@ -0,0 +1,162 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::ui;
+
+/// What a full read of a rollout found.
+#[derive(Debug, Default, PartialEq)]
+struct Report {
+    /// Non-empty lines.
+    lines: usize,
+    /// 1-based numbers of lines that aren't JSON, not counting the tail below.
+    malformed: Vec<usize>,
+    /// The first line is the `session_meta` record.
+    has_meta: bool,
+    /// Lines at the end that don't parse (a write cut short by a crash).
+    tail_lines: usize,
+    /// Bytes to keep to drop that tail.
+    keep_bytes: usize,
+}
+
+fn analyze(data: &[u8]) -> Report {
+    let mut report = Report::default();
+    let mut offset = 0;
+    let mut bad_run = Vec::new();
+    for (i, raw) in data.split_inclusive(|&b| b == b'\n').enumerate() {
+        offset += raw.len();
+        let line = String::from_utf8_lossy(raw);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        report.lines += 1;
+        match serde_json::from_str::<Value>(line) {
+            Ok(v) => {
+                if report.lines == 1 {
+                    report.has_meta = v.get("type").and_then(Value::as_str) == Some("session_meta");
+                }
+                report.malformed.append(&mut bad_run);
+                report.keep_bytes = offset;
+            }
+            Err(_) => bad_run.push(i + 1),
+        }
+    }
+    report.tail_lines = bad_run.len();
+    report
+}
+
+/// Parse the whole rollout at `path` and print what's wrong with it; with `repair`, cut off a
+/// tail of unparseable lines (keeping a `.bak` copy). Fails while problems remain.
+pub fn verify(path: &Path, repair: bool, dry_run: bool) -> Result<()> {
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let report = analyze(&data);
+    println!("rollout:   {}", path.display());
+    println!("lines:     {}", report.lines);
+    println!(
+        "meta:      {}",
+        if report.has_meta {
+            "ok"
+        } else {
+            "missing (the first line isn't a session_meta record)"
+        }
+    );
+    if report.malformed.is_empty() {
+        println!("malformed: none");
+    } else {
+        let shown: Vec<String> = report
+            .malformed
+            .iter()
+            .take(10)
+            .map(usize::to_string)
+            .collect();
+        let more = report.malformed.len().saturating_sub(shown.len());
+        let more = if more > 0 {
+            format!(" (+{more} more)")
+        } else {
+            String::new()
+        };
+        println!("malformed: lines {}{more}", shown.join(", "));
+    }
+    let mut tail_left = report.tail_lines > 0;
+    if !tail_left {
+        println!("tail:      ok");
+    } else {
+        println!(
+            "tail:      truncated: the last {} line(s), {} bytes, don't parse",
+            report.tail_lines,
+            data.len() - report.keep_bytes
+        );
+        if repair && report.keep_bytes == 0 {
+            ui::print_warn("nothing in the file parses; not trimming it");
+        } else if repair && dry_run {
+            ui::print_info(&format!(
+                "DRY RUN: would trim {} to {} bytes (backup: {})",
+                path.display(),
+                report.keep_bytes,
+                backup_path(path).display()
+            ));
+        } else if repair {
+            trim(path, report.keep_bytes)?;
+            tail_left = false;
+        }
+    }
+
+    if report.lines == 0 {
+        anyhow::bail!("the rollout is empty");
+    }
+    if !report.has_meta || !report.malformed.is_empty() || tail_left {
+        let hint = if tail_left && !repair {
+            "; --repair trims the truncated tail"
+        } else {
+            ""
+        };
+        anyhow::bail!("the rollout has problems{hint}");
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Copy `path` to its `.bak` and cut it down to `len` bytes.
+fn trim(path: &Path, len: usize) -> Result<()> {
+    let backup = backup_path(path);
+    fs::copy(path, &backup).with_context(|| format!("failed to write {}", backup.display()))?;
+    fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|f| f.set_len(len as u64))
+        .with_context(|| format!("failed to trim {}", path.display()))?;
+    ui::print_info(&format!(
+        "Trimmed {} to {len} bytes (backup: {})",
+        path.display(),
+        backup.display()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_a_truncated_tail_from_malformed_lines() {
+        let meta = r#"{"type":"session_meta","payload":{"id":"x"}}"#;
+        let data = format!("{meta}\nnot json\n{{\"type\":\"event\"}}\n\n{{\"type\":\"resp");
+        let report = analyze(data.as_bytes());
+        assert_eq!(report.lines, 4);
+        assert!(report.has_meta);
+        assert_eq!(report.malformed, [2]);
+        assert_eq!(report.tail_lines, 1);
+        assert_eq!(
+            &data[..report.keep_bytes],
+            format!("{meta}\nnot json\n{{\"type\":\"event\"}}\n")
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use globset::GlobBuilder;
+
+use crate::pathfmt;
+use crate::projects;
+
+/// How deep `**` in a member pattern is followed.
+const MAX_GLOB_DEPTH: usize = 4;
+
+/// Member crates of the Cargo workspace at `root`, as (relative folder, crate name).
+/// Members come from `[workspace] members` (globs allowed) minus `exclude`.
+pub fn cargo_members(root: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(s) = fs::read_to_string(root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = s.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let Some(ws) = manifest.get("workspace").and_then(|w| w.as_table()) else {
+        return Vec::new();
+    };
+    let exclude: Vec<PathBuf> = strings(ws.get("exclude"))
+        .iter()
+        .flat_map(|p| glob_dirs(root, p))
+        .collect();
+    let mut out: Vec<(PathBuf, String)> = Vec::new();
+    for pattern in strings(ws.get("members")) {
+        for rel in glob_dirs(root, &pattern) {
+            if rel.as_os_str().is_empty() || exclude.contains(&rel) {
+                continue;
+            }
+            if out.iter().any(|(r, _)| *r == rel) {
+                continue;
+            }
+            let Some(name) = crate_name(&root.join(&rel)) else {
+                continue;
+            };
+            out.push((rel, name));
+        }
+    }
+    out
+}
+
+/// Packages of a pnpm/npm/yarn workspace at `root`, as (relative folder, package name).
+/// Globs come from `pnpm-workspace.yaml` or `package.json` `workspaces`; `!pattern` excludes.
+pub fn js_members(root: &Path) -> Vec<(PathBuf, String)> {
+    let patterns = match fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+        Ok(s) => pnpm_packages(&s),
+        Err(_) => package_json(root)
+            .and_then(|p| {
+                let ws = p.get("workspaces")?;
+                let list = ws.get("packages").unwrap_or(ws);
+                Some(json_strings(list))
+            })
+            .unwrap_or_default(),
+    };
+    let (excludes, includes): (Vec<&String>, Vec<&String>) =
+        patterns.iter().partition(|p| p.starts_with('!'));
+    let exclude: Vec<PathBuf> = excludes
+        .iter()
+        .flat_map(|p| glob_dirs(root, &p[1..]))
+        .collect();
+    let mut out: Vec<(PathBuf, String)> = Vec::new();
+    for pattern in includes {
+        for rel in glob_dirs(root, pattern) {
+            if rel.as_os_str().is_empty()
+                || exclude.contains(&rel)
+                || out.iter().any(|(r, _)| *r == rel)
+            {
+                continue;
+            }
+            let Some(pkg) = package_json(&root.join(&rel)) else {
+                continue;
+            };
+            let name = pkg
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| pathfmt::basename(&rel));
+            out.push((rel, name));
+        }
+    }
+    out
+}
+
+/// The `packages:` list of a `pnpm-workspace.yaml` (block style, one `- glob` per line).
+fn pnpm_packages(s: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut in_packages = false;
+    for line in s.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if in_packages && let Some(item) = trimmed.strip_prefix('-') {
+            let item = item.split(" #").next().unwrap_or(item).trim();
+            out.push(item.trim_matches(['\'', '"']).to_string());
+        }
+    }
+    out
+}
+
+fn package_json(dir: &Path) -> Option<serde_json::Value> {
+    let s = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&s).ok()
+}
+
+fn json_strings(v: &serde_json::Value) -> Vec<String> {
+    v.as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `[package] name` of the crate at `dir`; `None` when it has no Cargo.toml.
+fn crate_name(dir: &Path) -> Option<String> {
+    let s = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let name = s
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|m| m.get("package")?.get("name")?.as_str().map(str::to_string));
+    Some(name.unwrap_or_else(|| pathfmt::basename(dir)))
+}
+
+fn strings(v: Option<&toml::Value>) -> Vec<String> {
+    v.and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Folders under `root` (relative) matching a workspace pattern like `crates/*` or `apps/**`.
+fn glob_dirs(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    if !pattern.contains(['*', '?', '[', '{']) {
+        let rel = PathBuf::from(if pattern == "." { "" } else { pattern });
+        return if root.join(&rel).is_dir() {
+            vec![rel]
+        } else {
+            Vec::new()
+        };
+    }
+    let Ok(glob) = GlobBuilder::new(pattern).literal_separator(true).build() else {
+        return Vec::new();
+    };
+    let matcher = glob.compile_matcher();
+    let depth = if pattern.contains("**") {
+        MAX_GLOB_DEPTH
+    } else {
+        pattern.split('/').count()
+    };
+    let mut out = Vec::new();
+    let mut stack = vec![(PathBuf::new(), 0)];
+    while let Some((rel, level)) = stack.pop() {
+        if level == depth {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(root.join(&rel)) else {
+            continue;
+        };
+        for ent in entries.flatten() {
+            let path = ent.path();
+            if !path.is_dir() || projects::is_hidden_or_noise(&path) {
+                continue;
+            }
+            let child = rel.join(ent.file_name());
+            if matcher.is_match(&child) {
+                out.push(child.clone());
+            }
+            stack.push((child, level + 1));
+        }
+    }
+    out.sort();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_cargo_workspace_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_path_buf();
+        let write = |rel: &str, s: &str| {
+            let p = root.join(rel);
+            fs::create_dir_all(p.parent().unwrap()).unwrap();
+            fs::write(p, s).unwrap();
+        };
+        write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/old\"]\n",
+        );
+        write(
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"acme-core\"\n",
+        );
+        write("crates/old/Cargo.toml", "[package]\nname = \"acme-old\"\n");
+        write("crates/notes/README.md", "");
+        write("tools/cli/Cargo.toml", "[package]\nname = \"acme\"\n");
+
+        let members = cargo_members(&root);
+        assert_eq!(
+            members,
+            [
+                (PathBuf::from("crates/core"), "acme-core".to_string()),
+                (PathBuf::from("tools/cli"), "acme".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_pnpm_workspace_globs() {
+        let s = "packages:\n  - 'apps/*'\n  - \"packages/**\" # all of them\n  - '!**/test/**'\ncatalog:\n  - x\n";
+        assert_eq!(pnpm_packages(s), ["apps/*", "packages/**", "!**/test/**"]);
+    }
+}